@@ -21,104 +21,340 @@
 
 use super::*;
 use frame_benchmarking::v2::*;
-use frame_support::BoundedVec;
-use frame_system::RawOrigin;
+use frame_support::{
+    traits::fungible::{Inspect, Mutate},
+    BoundedVec,
+};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use sp_runtime::traits::Saturating;
 use sp_std::vec;
 
+/// Caller account funded for a few nodes with full metadata and payload deposits.
+fn funded_caller<T: Config>() -> T::AccountId {
+    let caller: T::AccountId = whitelisted_caller();
+    fund::<T>(&caller, 10);
+    caller
+}
+
+/// Fund account for `nodes` nodes with full metadata and payload deposits.
+fn fund<T: Config>(who: &T::AccountId, nodes: u32) {
+    let deposit = T::NodeDepositBase::get()
+        .saturating_add(T::ByteDeposit::get().saturating_mul((2 * MAX_DATA_SIZE).into()));
+    let amount = deposit
+        .saturating_mul(nodes.into())
+        .saturating_add(T::Currency::minimum_balance());
+    T::Currency::set_balance(who, amount);
+}
+
+/// Create chain of `depth` nodes below a new root node, returns the deepest node.
+fn create_chain<T: Config>(owner: &T::AccountId, depth: u32) -> NodeId {
+    let mut node_id = Pallet::<T>::do_create_node(owner.clone(), None, None, None).unwrap();
+    for _ in 0..depth {
+        node_id = Pallet::<T>::do_create_node(owner.clone(), Some(node_id), None, None).unwrap();
+    }
+    node_id
+}
+
+/// Create chain of `depth` nodes below a root node moved as a large subtree with descendant
+/// paths pending update, returns the deepest node.
+fn create_moving_chain<T: Config>(owner: &T::AccountId, depth: u32) -> NodeId {
+    let node_id = create_chain::<T>(owner, depth);
+    let root = Pallet::<T>::nodes(node_id)
+        .and_then(|node| node.path.first().copied())
+        .unwrap_or(node_id);
+    for _ in 0..MAX_MOVABLE_SUBTREE_SIZE {
+        let _ = Pallet::<T>::do_create_node(owner.clone(), Some(root), None, None);
+    }
+    let new_parent = Pallet::<T>::do_create_node(owner.clone(), None, None, None).unwrap();
+    let _ = Pallet::<T>::move_node(RawOrigin::Signed(owner.clone()).into(), root, new_parent);
+    node_id
+}
+
+/// Create node with `depth` ancestors resolved through a pending large move.
+fn create_deep_node<T: Config>(owner: &T::AccountId, depth: u32) -> NodeId {
+    if depth == 0 {
+        return Pallet::<T>::do_create_node(owner.clone(), None, None, None).unwrap();
+    }
+    create_moving_chain::<T>(owner, depth - 1)
+}
+
+/// Grant `role` on node to caller allowed to grow owner deposits, returns the caller.
+fn grantee<T: Config>(owner: &T::AccountId, node_id: NodeId, role: Role) -> T::AccountId {
+    let who: T::AccountId = whitelisted_caller();
+    let _ = Pallet::<T>::grant_role(
+        RawOrigin::Signed(owner.clone()).into(),
+        node_id,
+        who.clone(),
+        role,
+    );
+    let _ = Pallet::<T>::set_deposit_allowance(
+        RawOrigin::Signed(owner.clone()).into(),
+        who.clone(),
+        T::Currency::balance(owner),
+    );
+    who
+}
+
 #[benchmarks]
 mod benchmarks {
     use super::*;
 
     #[benchmark]
-    fn create_node() {
-        let caller: T::AccountId = whitelisted_caller();
+    fn create_node(d: Linear<0, { MAX_TREE_DEPTH - 1 }>) {
+        let owner: T::AccountId = account("owner", 0, 0);
+        fund::<T>(&owner, MAX_TREE_DEPTH + MAX_MOVABLE_SUBTREE_SIZE + 2);
         let meta = BoundedVec::try_from(vec![1u8; 100]).ok();
         let payload = BoundedVec::try_from(vec![2u8; 100]).ok();
 
+        // Setup: parent with `d` ancestors, every ancestor gets its subtree height raised
+        // and child creator role is found after checking all of them
+        let parent = create_deep_node::<T>(&owner, d);
+        let caller = grantee::<T>(&owner, parent, Role::ChildCreator);
+        let node_id = <NextNodeId<T>>::get();
+
         #[extrinsic_call]
-        _(RawOrigin::Signed(caller.clone()), None, meta, payload);
+        _(RawOrigin::Signed(caller), Some(parent), meta, payload);
 
-        assert_eq!(<NextNodeId<T>>::get(), NodeId(1));
+        let node = <Nodes<T>>::get(node_id).unwrap();
+        assert_eq!(node.path.len() as u32, d + 1);
+        assert_eq!(node.owner, owner);
     }
 
     #[benchmark]
-    fn set_meta() {
+    fn set_meta(d: Linear<0, MAX_TREE_DEPTH>) {
+        let owner: T::AccountId = account("owner", 0, 0);
+        fund::<T>(&owner, MAX_TREE_DEPTH + MAX_MOVABLE_SUBTREE_SIZE + 2);
+
+        // Setup: node with `d` ancestors, meta editor role is found after checking all of them
+        let node_id = create_deep_node::<T>(&owner, d);
+        let caller = grantee::<T>(&owner, node_id, Role::MetaEditor);
+
+        let meta = BoundedVec::try_from(vec![1u8; MAX_DATA_SIZE as usize]).ok();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), node_id, meta);
+
+        assert!(<Nodes<T>>::get(node_id).unwrap().meta.is_some());
+    }
+
+    #[benchmark]
+    fn set_payload(d: Linear<0, MAX_TREE_DEPTH>) {
+        let owner: T::AccountId = account("owner", 0, 0);
+        fund::<T>(&owner, MAX_TREE_DEPTH + MAX_MOVABLE_SUBTREE_SIZE + 2);
+
+        // Setup: node with `d` ancestors, writer role is found after checking all of them
+        let node_id = create_deep_node::<T>(&owner, d);
+        let caller = grantee::<T>(&owner, node_id, Role::Writer);
+
+        // Setup: worst case keeps every payload version in history
+        let _ = Pallet::<T>::set_payload_history(
+            RawOrigin::Signed(owner.clone()).into(),
+            node_id,
+            MAX_PAYLOAD_HISTORY,
+        );
+
+        // Setup: expiring payload to be replaced by a larger one
+        let ttl: BlockNumberFor<T> = 10u32.into();
+        let _ = Pallet::<T>::set_payload(
+            RawOrigin::Signed(owner).into(),
+            node_id,
+            BoundedVec::try_from(vec![1u8; 100]).ok(),
+            Some(ttl),
+        );
+
+        let payload = BoundedVec::try_from(vec![1u8; MAX_DATA_SIZE as usize]).ok();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), node_id, payload, Some(ttl));
+
+        assert!(<Nodes<T>>::get(node_id).unwrap().payload.is_some());
+        assert!(Pallet::<T>::payload_at(node_id, 2).is_some());
+    }
+
+    #[benchmark]
+    fn move_node() {
         let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller, MAX_TREE_DEPTH + MAX_MOVABLE_SUBTREE_SIZE + 2);
 
-        // Setup: create a node first
-        let _ =
-            Pallet::<T>::create_node(RawOrigin::Signed(caller.clone()).into(), None, None, None);
+        // Setup: largest subtree moved at once, all descendant paths are rewritten
+        let node_id = Pallet::<T>::do_create_node(caller.clone(), None, None, None).unwrap();
+        for _ in 0..MAX_MOVABLE_SUBTREE_SIZE {
+            let _ = Pallet::<T>::do_create_node(caller.clone(), Some(node_id), None, None);
+        }
 
-        let meta = BoundedVec::try_from(vec![1u8; 100]).ok();
+        // Setup: deepest new parent that fits the subtree, every ancestor is checked
+        // for pending deletion and gets its subtree height raised
+        let new_parent = create_chain::<T>(&caller, MAX_TREE_DEPTH - 2);
 
         #[extrinsic_call]
-        _(RawOrigin::Signed(caller), NodeId(0), meta);
+        _(RawOrigin::Signed(caller), node_id, new_parent);
 
-        assert!(<Nodes<T>>::get(NodeId(0)).unwrap().meta.is_some());
+        assert_eq!(<Nodes<T>>::get(node_id).unwrap().parent, Some(new_parent));
+        assert_eq!(<SubtreeHeight<T>>::get(new_parent), 2);
     }
 
     #[benchmark]
-    fn set_payload() {
+    fn delete_node() {
         let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller, MAX_TREE_DEPTH + 1);
+
+        // Setup: deepest leaf, moving subtree is looked up along its path
+        let node_id = create_chain::<T>(&caller, MAX_TREE_DEPTH);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), node_id);
+
+        assert!(<Nodes<T>>::get(node_id).is_none());
+    }
 
-        // Setup: create a node first
+    #[benchmark]
+    fn grant_role() {
+        let caller = funded_caller::<T>();
         let _ =
             Pallet::<T>::create_node(RawOrigin::Signed(caller.clone()).into(), None, None, None);
 
-        let payload = BoundedVec::try_from(vec![1u8; 100]).ok();
+        // Fill node roles up to the last free slot
+        for i in 0..MAX_ROLES_PER_NODE - 1 {
+            let who: T::AccountId = account("writer", i, 0);
+            let _ = Pallet::<T>::grant_role(
+                RawOrigin::Signed(caller.clone()).into(),
+                NodeId(0),
+                who,
+                Role::Writer,
+            );
+        }
+        let who: T::AccountId = account("writer", MAX_ROLES_PER_NODE, 0);
 
         #[extrinsic_call]
-        _(RawOrigin::Signed(caller), NodeId(0), payload);
+        _(RawOrigin::Signed(caller), NodeId(0), who, Role::Writer);
 
-        assert!(<Nodes<T>>::get(NodeId(0)).unwrap().payload.is_some());
+        assert_eq!(
+            <NodeRoles<T>>::get(NodeId(0)).len() as u32,
+            MAX_ROLES_PER_NODE
+        );
     }
 
     #[benchmark]
-    fn move_node() {
-        let caller: T::AccountId = whitelisted_caller();
-
-        // Setup: create parent node
+    fn revoke_role() {
+        let caller = funded_caller::<T>();
         let _ =
             Pallet::<T>::create_node(RawOrigin::Signed(caller.clone()).into(), None, None, None);
 
-        // Create child node
-        let _ = Pallet::<T>::create_node(
-            RawOrigin::Signed(caller.clone()).into(),
-            Some(NodeId(0)),
-            None,
-            None,
+        for i in 0..MAX_ROLES_PER_NODE {
+            let who: T::AccountId = account("writer", i, 0);
+            let _ = Pallet::<T>::grant_role(
+                RawOrigin::Signed(caller.clone()).into(),
+                NodeId(0),
+                who,
+                Role::Writer,
+            );
+        }
+        let who: T::AccountId = account("writer", MAX_ROLES_PER_NODE - 1, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), NodeId(0), who, Role::Writer);
+
+        assert_eq!(
+            <NodeRoles<T>>::get(NodeId(0)).len() as u32,
+            MAX_ROLES_PER_NODE - 1
         );
+    }
 
-        // Create new parent
+    #[benchmark]
+    fn offer_transfer() {
+        let caller = funded_caller::<T>();
+        let to: T::AccountId = account("new_owner", 0, 0);
         let _ =
             Pallet::<T>::create_node(RawOrigin::Signed(caller.clone()).into(), None, None, None);
 
         #[extrinsic_call]
-        _(RawOrigin::Signed(caller), NodeId(1), NodeId(2));
+        _(RawOrigin::Signed(caller), NodeId(0), to.clone());
 
-        assert_eq!(<Nodes<T>>::get(NodeId(1)).unwrap().parent, Some(NodeId(2)));
+        assert_eq!(<PendingTransfers<T>>::get(NodeId(0)), Some(to));
     }
 
     #[benchmark]
-    fn delete_node() {
+    fn accept_transfer() {
+        let owner: T::AccountId = account("owner", 0, 0);
+        fund::<T>(&owner, MAX_TREE_DEPTH + MAX_MOVABLE_SUBTREE_SIZE + 2);
         let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller, MAX_MOVABLE_SUBTREE_SIZE + 2);
+        let data = || BoundedVec::try_from(vec![1u8; MAX_DATA_SIZE as usize]).ok();
 
-        // Setup: create a parent node
+        // Setup: largest transferable subtree attached at the deepest level,
+        // every ancestor is checked for pending deletion
+        let parent = create_chain::<T>(&owner, MAX_TREE_DEPTH - 2);
+        let node_id =
+            Pallet::<T>::do_create_node(owner.clone(), Some(parent), data(), data()).unwrap();
+        let mut leaf = node_id;
+        for _ in 0..MAX_MOVABLE_SUBTREE_SIZE {
+            leaf =
+                Pallet::<T>::do_create_node(owner.clone(), Some(node_id), data(), data()).unwrap();
+        }
+        let _ =
+            Pallet::<T>::offer_transfer(RawOrigin::Signed(owner).into(), node_id, caller.clone());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), node_id);
+
+        assert_eq!(<Nodes<T>>::get(leaf).unwrap().owner, caller);
+    }
+
+    #[benchmark]
+    fn delete_subtree() {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller, MAX_TREE_DEPTH + 1);
+
+        // Setup: every ancestor of the deepest node is checked for pending deletion
+        let node_id = create_chain::<T>(&caller, MAX_TREE_DEPTH);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), node_id);
+
+        assert!(<DeletionQueue<T>>::contains_key(node_id));
+    }
+
+    #[benchmark]
+    fn set_payload_history() {
+        let caller = funded_caller::<T>();
         let _ =
             Pallet::<T>::create_node(RawOrigin::Signed(caller.clone()).into(), None, None, None);
 
-        // Create child node to delete
-        let _ = Pallet::<T>::create_node(
+        // Setup: full history to be cleared
+        let _ = Pallet::<T>::set_payload_history(
             RawOrigin::Signed(caller.clone()).into(),
-            Some(NodeId(0)),
-            None,
-            None,
+            NodeId(0),
+            MAX_PAYLOAD_HISTORY,
+        );
+        for _ in 0..MAX_PAYLOAD_HISTORY {
+            let _ = Pallet::<T>::set_payload(
+                RawOrigin::Signed(caller.clone()).into(),
+                NodeId(0),
+                BoundedVec::try_from(vec![1u8; 100]).ok(),
+                None,
+            );
+        }
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), NodeId(0), MAX_PAYLOAD_HISTORY);
+
+        assert_eq!(
+            <PayloadHistoryDepth<T>>::get(NodeId(0)),
+            MAX_PAYLOAD_HISTORY
         );
+        assert_eq!(<PayloadHistory<T>>::iter_prefix(NodeId(0)).count(), 0);
+    }
+
+    #[benchmark]
+    fn set_deposit_allowance() {
+        let caller: T::AccountId = whitelisted_caller();
+        let who: T::AccountId = account("grantee", 0, 0);
+        let amount: BalanceOf<T> = 1u32.into();
 
         #[extrinsic_call]
-        _(RawOrigin::Signed(caller), NodeId(1));
+        _(RawOrigin::Signed(caller.clone()), who.clone(), amount);
 
-        assert!(<Nodes<T>>::get(NodeId(1)).is_none());
+        assert_eq!(<DepositAllowance<T>>::get(&caller, &who), amount);
     }
 
     impl_benchmark_test_suite!(Pallet, crate::tests::new_test_ext(), crate::tests::Runtime);
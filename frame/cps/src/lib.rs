@@ -25,7 +25,7 @@
 //!
 //! ### Storage Layout
 //!
-//! The pallet uses three storage items:
+//! The pallet uses four storage items:
 //!
 //! 1. **`Nodes`**: Primary storage mapping `NodeId` → `Node<AccountId, Config>`
 //!    - Uses `Blake2_128Concat` hasher for cryptographic security
@@ -35,9 +35,46 @@
 //!    - Enables O(1) lookup of all children for a given parent
 //!    - Uses `Blake2_128Concat` hasher
 //!
-//! 3. **`RootNodes`**: Global list of `BoundedVec<NodeId>`
-//!    - Tracks all nodes without parents
-//!    - Limited by `MaxRootNodes` const
+//! 3. **`RootsByOwner`**: Index mapping `(AccountId, NodeId)` → `()`
+//!    - Tracks nodes without parents of each owner
+//!    - Limited by `MAX_ROOTS_PER_OWNER` const per account, counted in `RootCount`
+//!
+//! 4. **`NodeDeposit`**: Storage deposit held from node owner
+//!    - `NodeDepositBase` per node plus `ByteDeposit` per byte of meta and payload
+//!    - Held with `CpsNode` hold reason and released on node deletion
+//!
+//! 5. **`NodeRoles`**: Access roles granted by node owner to other accounts
+//!    - Roles are inherited by the whole subtree through node `path`
+//!    - Limited by `MaxRolesPerNode` const
+//!    - Grantees grow owner deposits only within `DepositAllowance` set by the owner
+//!
+//! 6. **`DeletionQueue`** and **`DeletionCursor`**: Subtrees scheduled by `delete_subtree`
+//!    - Removed leaf-first in `on_idle` batches of `MAX_DELETION_BATCH` nodes,
+//!      pending subtrees take turns batch by batch
+//!    - No nodes could be attached to a subtree pending deletion
+//!
+//! 7. **`MoveQueue`** and **`PathUpdateCursor`**: Large subtrees moved by `move_node`
+//!    - Descendant paths are rewritten depth-first in `on_idle` batches of
+//!      `MAX_PATH_UPDATE_BATCH` nodes
+//!    - Queued roots are indexed in `MovingRoots`, so lookups cost a read per ancestor
+//!    - `resolved_path` returns actual ancestry of nodes with outdated paths,
+//!      `live_node` and `CpsApi` lookups return nodes with resolved paths
+//!    - Moving subtree couldn't be moved, transferred or deleted in the meantime
+//!    - `SubtreeHeight` upper bound keeps the lowest descendant within `MAX_TREE_DEPTH`
+//!
+//! 8. **`NodesByOwner`**: Index mapping `(AccountId, NodeId)` → `()`
+//!    - Answers which nodes account owns, paginated by `nodes_of`
+//!    - Queried together with tree lookups by `CpsApi` runtime API
+//!
+//! 9. **`PayloadHistory`**: Opt-in ring buffer of last payload versions
+//!    - Enabled per node by `set_payload_history`, up to `MAX_PAYLOAD_HISTORY` records
+//!    - Paid for by `ByteDeposit` per byte of each reserved record slot
+//!
+//! 10. **`PayloadExpiry`** and **`ExpiringPayloads`**: Payload time-to-live set by `set_payload`
+//!    - Expired payload is hidden from `live_node` right away
+//!    - Cleared block by block in `on_idle` from `ExpiryCursor`, up to
+//!      `MAX_EXPIRIES_PER_BLOCK` payloads expiring at the same block
+//!    - Cleared payload gets a new version, cursor is dropped once no payload expires
 //!
 //! ### Performance Characteristics
 //!
@@ -101,8 +138,8 @@
 //! // Get all children
 //! let children = NodesByParent::<T>::get(NodeId(0));
 //!
-//! // Get all root nodes
-//! let roots = RootNodes::<T>::get();
+//! // Get all root nodes of account
+//! let roots = Pallet::<T>::roots_of(&account);
 //!
 //! // Check if node is ancestor (O(1))
 //! let is_ancestor = node.path.contains(&NodeId(ancestor_id));
@@ -372,13 +409,21 @@
 //! The pallet maintains the following invariants:
 //!
 //! 1. **No Cycles**: The tree is acyclic (enforced by path checking)
-//! 2. **Ownership Consistency**: Children always have parent's owner
-//! 3. **Index Consistency**: `NodesByParent` and `RootNodes` stay synchronized
-//! 4. **Deletion Safety**: Cannot delete nodes with children
-//! 5. **Depth Limits**: Tree depth never exceeds `MAX_TREE_DEPTH`
-//! 6. **Proxy Delegation** (optional): When using `pallet-proxy`, access can be delegated
+//! 2. **Ownership Consistency**: Children always have parent's owner. Accepted transfer
+//!    reassigns the whole subtree and detaches it as a root node of the new owner
+//! 3. **Index Consistency**: `NodesByParent` and `RootsByOwner` stay synchronized
+//! 4. **Deletion Safety**: `delete_node` removes leaf nodes only, `delete_subtree` removes
+//!    descendants leaf-first across blocks. Subtree pending deletion can't get new nodes,
+//!    be moved or transferred
+//! 5. **Depth Limits**: Tree depth never exceeds `MAX_TREE_DEPTH`, subtree moves are
+//!    checked against tracked `SubtreeHeight`
+//! 6. **Delegated Access**: Granted roles edit metadata, payload or create children only.
+//!    They can't transfer, move or delete nodes and grow owner's deposits up to
+//!    `DepositAllowance` only
+//! 7. **Proxy Delegation** (optional): When using `pallet-proxy`, access can be delegated
 //!    while maintaining all ownership invariants. Proxies act on behalf of owners but
-//!    cannot transfer ownership or elevate privileges.
+//!    cannot elevate privileges. Ownership transfer is only possible when proxy type
+//!    admits `offer_transfer`, `CpsWrite` of the example filter above doesn't
 //!
 //! ## Testing
 //!
@@ -403,6 +448,7 @@
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod runtime_api;
 pub mod weights;
 
 #[cfg(test)]
@@ -515,12 +561,27 @@ pub const MAX_CHILDREN_PER_NODE: u32 = 100;
 
 pub const MAX_MOVABLE_SUBTREE_SIZE: u32 = 50;
 
-pub const MAX_ROOT_NODES: u32 = 100;
+pub const MAX_ROOTS_PER_OWNER: u32 = 100;
+
+pub const MAX_ROLES_PER_NODE: u32 = 16;
+
+pub const MAX_DELETION_BATCH: u32 = 50;
+
+pub const MAX_PENDING_MOVES: u32 = 100;
+
+pub const MAX_PATH_UPDATE_BATCH: u32 = 100;
+
+pub const MAX_PAYLOAD_HISTORY: u32 = 16;
+
+pub const MAX_EXPIRIES_PER_BLOCK: u32 = 100;
 
 pub type MaxDataSize = ConstU32<MAX_DATA_SIZE>;
 pub type MaxTreeDepth = ConstU32<MAX_TREE_DEPTH>;
 pub type MaxChildrenPerNode = ConstU32<MAX_CHILDREN_PER_NODE>;
-pub type MaxRootNodes = ConstU32<MAX_ROOT_NODES>;
+pub type MaxRolesPerNode = ConstU32<MAX_ROLES_PER_NODE>;
+pub type MaxPendingMoves = ConstU32<MAX_PENDING_MOVES>;
+pub type MaxPathCursor = ConstU32<{ MAX_TREE_DEPTH + 1 }>;
+pub type MaxExpiriesPerBlock = ConstU32<MAX_EXPIRIES_PER_BLOCK>;
 
 /// Type alias for node data - bounded vector of bytes.
 ///
@@ -690,11 +751,184 @@ where
     pub payload: Option<NodeData>,
 }
 
+/// Node payload version kept in payload history.
+///
+/// Nodes opted in with `set_payload_history` keep last `depth` records in a ring
+/// buffer, record of version `k` is stored in slot `k % depth`.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, MaxEncodedLen, Debug)]
+pub struct PayloadRecord<AccountId, BlockNumber, Moment> {
+    /// Payload version number
+    pub version: u32,
+    /// Block number when payload was set
+    pub block: BlockNumber,
+    /// Timestamp when payload was set
+    pub timestamp: Moment,
+    /// Account that set the payload, node owner when payload expired
+    pub setter: AccountId,
+    /// Payload data
+    pub payload: Option<NodeData>,
+}
+
+pub mod migration {
+    use super::*;
+    use frame_support::{
+        pallet_prelude::*,
+        traits::{fungible::MutateHold, Get, UncheckedOnRuntimeUpgrade},
+    };
+    use sp_runtime::traits::Zero;
+
+    /// Hold storage deposit for existing nodes, nodes of owners without enough
+    /// free balance are grandfathered and charged on the next data update.
+    pub type MigrationToV2<T> = frame_support::migrations::VersionedMigration<
+        1,
+        2,
+        UncheckedMigrationToV2<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+    pub struct UncheckedMigrationToV2<T>(PhantomData<T>);
+    impl<T: Config> UncheckedOnRuntimeUpgrade for UncheckedMigrationToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut nodes = 0u64;
+            let mut charged = 0u64;
+            for (node_id, node) in Nodes::<T>::iter() {
+                nodes += 1;
+                let deposit = Pallet::<T>::deposit_for(&node.meta, &node.payload);
+                if deposit.is_zero() {
+                    continue;
+                }
+                if T::Currency::hold(&HoldReason::CpsNode.into(), &node.owner, deposit).is_ok() {
+                    NodeDeposit::<T>::insert(node_id, deposit);
+                    charged += 1;
+                }
+            }
+            T::DbWeight::get().reads_writes(nodes * 2, charged * 3)
+        }
+    }
+
+    pub mod v2 {
+        use super::*;
+
+        /// Bound of global root node list, independent of current per owner limit.
+        const MAX_ROOT_NODES: u32 = 100;
+
+        #[frame_support::storage_alias]
+        pub type RootNodes<T: Config> =
+            StorageValue<Pallet<T>, BoundedVec<NodeId, ConstU32<MAX_ROOT_NODES>>, ValueQuery>;
+    }
+
+    /// Replace global root node list with per owner root index.
+    pub type MigrationToV3<T> = frame_support::migrations::VersionedMigration<
+        2,
+        3,
+        UncheckedMigrationToV3<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+    pub struct UncheckedMigrationToV3<T>(PhantomData<T>);
+    impl<T: Config> UncheckedOnRuntimeUpgrade for UncheckedMigrationToV3<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let roots = v2::RootNodes::<T>::take();
+            for node_id in roots.iter() {
+                if let Some(node) = Nodes::<T>::get(node_id) {
+                    RootsByOwner::<T>::insert(&node.owner, node_id, ());
+                    RootCount::<T>::mutate(&node.owner, |count| *count = count.saturating_add(1));
+                }
+            }
+            let indexed = roots.len() as u64;
+            T::DbWeight::get().reads_writes(indexed * 2 + 1, indexed * 2 + 1)
+        }
+    }
+
+    /// Index existing nodes by owner.
+    pub type MigrationToV4<T> = frame_support::migrations::VersionedMigration<
+        3,
+        4,
+        UncheckedMigrationToV4<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+    pub struct UncheckedMigrationToV4<T>(PhantomData<T>);
+    impl<T: Config> UncheckedOnRuntimeUpgrade for UncheckedMigrationToV4<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut indexed = 0u64;
+            for (node_id, node) in Nodes::<T>::iter() {
+                NodesByOwner::<T>::insert(&node.owner, node_id, ());
+                indexed += 1;
+            }
+            T::DbWeight::get().reads_writes(indexed, indexed)
+        }
+    }
+
+    /// Compute subtree heights of existing nodes.
+    pub type MigrationToV5<T> = frame_support::migrations::VersionedMigration<
+        4,
+        5,
+        UncheckedMigrationToV5<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+    pub struct UncheckedMigrationToV5<T>(PhantomData<T>);
+    impl<T: Config> UncheckedOnRuntimeUpgrade for UncheckedMigrationToV5<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut nodes = 0u64;
+            let mut ancestors = 0u64;
+            for (_, node) in Nodes::<T>::iter() {
+                nodes += 1;
+                let path = Pallet::<T>::resolved_path(&node);
+                let len = path.len() as u32;
+                for (depth, ancestor) in path.iter().enumerate() {
+                    SubtreeHeight::<T>::mutate(ancestor, |height| {
+                        *height = (*height).max(len - depth as u32)
+                    });
+                    ancestors += 1;
+                }
+            }
+            T::DbWeight::get().reads_writes(nodes + ancestors, ancestors)
+        }
+    }
+}
+
+/// Access role granted by node owner to another account.
+///
+/// Role granted on a node is also valid for all its descendants, so granting
+/// `Writer` on a building node lets a gateway update payload of every sensor below.
+/// Storage deposit of created or updated nodes is always held from the node owner,
+/// up to deposit allowance the owner gives to the grantee.
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    TypeInfo,
+    MaxEncodedLen,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Debug,
+)]
+pub enum Role {
+    /// Allowed to `set_payload`
+    Writer,
+    /// Allowed to `set_meta`
+    MetaEditor,
+    /// Allowed to `create_node` under the node
+    ChildCreator,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
-    use frame_support::pallet_prelude::*;
+    use frame_support::{
+        pallet_prelude::*,
+        traits::{
+            fungible::{self, MutateHold},
+            tokens::Precision,
+            Time,
+        },
+    };
     use frame_system::pallet_prelude::*;
+    use sp_runtime::traits::{CheckedSub, One, Saturating, Zero};
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
@@ -722,6 +956,24 @@ pub mod pallet {
         /// ```
         type OnPayloadSet: OnPayloadSet<Self::AccountId>;
 
+        /// The overarching hold reason.
+        type RuntimeHoldReason: From<HoldReason>;
+
+        /// Currency to hold node storage deposits.
+        type Currency: fungible::Mutate<Self::AccountId>
+            + fungible::MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+        /// Base storage deposit held from owner for each node.
+        #[pallet::constant]
+        type NodeDepositBase: Get<BalanceOf<Self>>;
+
+        /// Storage deposit held from owner for each byte of node metadata and payload.
+        #[pallet::constant]
+        type ByteDeposit: Get<BalanceOf<Self>>;
+
+        /// Time source for payload history records.
+        type Time: Time;
+
         /// Weight information for extrinsics
         type WeightInfo: WeightInfo;
     }
@@ -731,7 +983,25 @@ pub mod pallet {
     pub struct Pallet<T>(_);
 
     /// Storage version for migrations
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+
+    pub type BalanceOf<T> = <<T as Config>::Currency as fungible::Inspect<
+        <T as frame_system::Config>::AccountId,
+    >>::Balance;
+
+    pub type PayloadRecordOf<T> = PayloadRecord<
+        <T as frame_system::Config>::AccountId,
+        BlockNumberFor<T>,
+        <<T as Config>::Time as Time>::Moment,
+    >;
+
+    /// A reason for the pallet placing a hold on funds.
+    #[pallet::composite_enum]
+    pub enum HoldReason {
+        /// Storage deposit of CPS node.
+        #[codec(index = 0)]
+        CpsNode,
+    }
 
     /// Next node ID counter
     #[pallet::storage]
@@ -749,24 +1019,169 @@ pub mod pallet {
     pub type NodesByParent<T: Config> =
         StorageMap<_, Blake2_128Concat, NodeId, BoundedVec<NodeId, MaxChildrenPerNode>, ValueQuery>;
 
-    /// Root nodes (nodes without parents)
+    /// Root nodes (nodes without parents) by owner
+    #[pallet::storage]
+    pub type RootsByOwner<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, NodeId, ()>;
+
+    /// All nodes by owner
+    #[pallet::storage]
+    pub type NodesByOwner<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, NodeId, ()>;
+
+    /// Number of root nodes owned by account
     #[pallet::storage]
-    #[pallet::getter(fn root_nodes)]
-    pub type RootNodes<T: Config> = StorageValue<_, BoundedVec<NodeId, MaxRootNodes>, ValueQuery>;
+    #[pallet::getter(fn root_count)]
+    pub type RootCount<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    /// Roles granted by node owner: node -> (account, role) entries
+    #[pallet::storage]
+    #[pallet::getter(fn node_roles)]
+    pub type NodeRoles<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        NodeId,
+        BoundedVec<(T::AccountId, Role), MaxRolesPerNode>,
+        ValueQuery,
+    >;
+
+    /// Ownership transfer offers: node -> new owner
+    #[pallet::storage]
+    #[pallet::getter(fn pending_transfer)]
+    pub type PendingTransfers<T: Config> = StorageMap<_, Blake2_128Concat, NodeId, T::AccountId>;
+
+    /// Subtree roots pending deletion, processed round-robin on idle
+    #[pallet::storage]
+    pub type DeletionQueue<T: Config> = StorageMap<_, Blake2_128Concat, NodeId, ()>;
+
+    /// Subtree root of the last deletion batch, the next batch goes to the following root
+    #[pallet::storage]
+    #[pallet::getter(fn deletion_cursor)]
+    pub type DeletionCursor<T: Config> = StorageValue<_, NodeId>;
+
+    /// Moved subtree roots with descendant paths pending update, processed in order on idle
+    #[pallet::storage]
+    #[pallet::getter(fn move_queue)]
+    pub type MoveQueue<T: Config> =
+        StorageValue<_, BoundedVec<NodeId, MaxPendingMoves>, ValueQuery>;
+
+    /// Moved subtree roots of `MoveQueue` indexed for ancestor lookups
+    #[pallet::storage]
+    pub type MovingRoots<T: Config> = StorageMap<_, Blake2_128Concat, NodeId, ()>;
+
+    /// Depth-first traversal state of moved subtree: stack of (node, next child index)
+    #[pallet::storage]
+    #[pallet::getter(fn path_update_cursor)]
+    pub type PathUpdateCursor<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        NodeId,
+        BoundedVec<(NodeId, u32), MaxPathCursor>,
+        ValueQuery,
+    >;
+
+    /// Upper bound of descendant levels below node, zero for a leaf
+    #[pallet::storage]
+    #[pallet::getter(fn subtree_height)]
+    pub type SubtreeHeight<T: Config> = StorageMap<_, Blake2_128Concat, NodeId, u32, ValueQuery>;
+
+    /// Number of payload updates of node
+    #[pallet::storage]
+    #[pallet::getter(fn payload_version)]
+    pub type PayloadVersion<T: Config> = StorageMap<_, Blake2_128Concat, NodeId, u32, ValueQuery>;
+
+    /// Number of metadata updates of node
+    #[pallet::storage]
+    #[pallet::getter(fn meta_version)]
+    pub type MetaVersion<T: Config> = StorageMap<_, Blake2_128Concat, NodeId, u32, ValueQuery>;
+
+    /// Number of last payload versions kept in node history
+    #[pallet::storage]
+    #[pallet::getter(fn payload_history_depth)]
+    pub type PayloadHistoryDepth<T: Config> =
+        StorageMap<_, Blake2_128Concat, NodeId, u32, ValueQuery>;
+
+    /// Node payload history ring buffer: (node, slot) -> record
+    #[pallet::storage]
+    pub type PayloadHistory<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, NodeId, Twox64Concat, u32, PayloadRecordOf<T>>;
+
+    /// Block since which node payload is expired
+    #[pallet::storage]
+    #[pallet::getter(fn payload_expiry)]
+    pub type PayloadExpiry<T: Config> = StorageMap<_, Blake2_128Concat, NodeId, BlockNumberFor<T>>;
+
+    /// Nodes with payload expiring at block
+    #[pallet::storage]
+    pub type ExpiringPayloads<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedVec<NodeId, MaxExpiriesPerBlock>,
+        ValueQuery,
+    >;
+
+    /// Next block which expiring payloads aren't swept yet
+    #[pallet::storage]
+    #[pallet::getter(fn expiry_cursor)]
+    pub type ExpiryCursor<T: Config> = StorageValue<_, BlockNumberFor<T>>;
+
+    /// Storage deposit held from node owner
+    #[pallet::storage]
+    #[pallet::getter(fn node_deposit)]
+    pub type NodeDeposit<T: Config> =
+        StorageMap<_, Blake2_128Concat, NodeId, BalanceOf<T>, ValueQuery>;
+
+    /// Storage deposit grantee could still grow on nodes of owner [owner, grantee]
+    #[pallet::storage]
+    #[pallet::getter(fn deposit_allowance)]
+    pub type DepositAllowance<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
         /// Node created [node_id, parent_id, owner]
         NodeCreated(NodeId, Option<NodeId>, T::AccountId),
-        /// Node metadata set [node_id, owner]
-        MetaSet(NodeId, T::AccountId),
-        /// Node payload set [node_id, owner]
-        PayloadSet(NodeId, T::AccountId),
+        /// Node metadata set [node_id, owner, version]
+        MetaSet(NodeId, T::AccountId, u32),
+        /// Node payload set [node_id, owner, version]
+        PayloadSet(NodeId, T::AccountId, u32),
         /// Node moved [node_id, old_parent, new_parent, owner]
         NodeMoved(NodeId, Option<NodeId>, NodeId, T::AccountId),
         /// Node deleted [node_id, owner]
         NodeDeleted(NodeId, T::AccountId),
+        /// Role granted [node_id, account, role]
+        RoleGranted(NodeId, T::AccountId, Role),
+        /// Role revoked [node_id, account, role]
+        RoleRevoked(NodeId, T::AccountId, Role),
+        /// Node ownership transfer offered [node_id, owner, new_owner]
+        TransferOffered(NodeId, T::AccountId, T::AccountId),
+        /// Node subtree ownership transferred [node_id, old_owner, new_owner]
+        OwnershipTransferred(NodeId, T::AccountId, T::AccountId),
+        /// Node subtree scheduled for deletion [node_id, owner]
+        SubtreeDeletionScheduled(NodeId, T::AccountId),
+        /// Batch of subtree nodes deleted [node_id, deleted]
+        SubtreeDeletionProgress(NodeId, u32),
+        /// Node subtree completely deleted [node_id]
+        SubtreeDeleted(NodeId),
+        /// Batch of moved subtree paths updated [node_id, updated]
+        SubtreeMoveProgress(NodeId, u32),
+        /// Moved subtree paths completely updated [node_id]
+        SubtreeMoved(NodeId),
+        /// Node payload history depth set [node_id, depth]
+        PayloadHistorySet(NodeId, u32),
+        /// Node payload expired and cleared [node_id, version]
+        PayloadExpired(NodeId, u32),
+        /// Storage deposit allowance of grantee set [owner, grantee, amount]
+        DepositAllowanceSet(T::AccountId, T::AccountId, BalanceOf<T>),
     }
 
     #[pallet::error]
@@ -785,22 +1200,58 @@ pub mod pallet {
         MaxDepthExceeded,
         /// Too many children for node
         TooManyChildren,
-        /// Too many root nodes
+        /// Too many root nodes owned by account
         TooManyRootNodes,
         /// Node has children and cannot be deleted
         NodeHasChildren,
         /// The subtree is too large to move in a single operation
         SubtreeTooLarge,
+        /// Too many roles granted on node
+        TooManyRoles,
+        /// Role isn't granted to account
+        RoleNotFound,
+        /// No ownership transfer offered to sender
+        NoTransferOffer,
+        /// Node or its ancestor is pending deletion
+        NodePendingDeletion,
+        /// Node or its ancestor subtree is being moved
+        SubtreeMoving,
+        /// Too many moved subtrees pending path update
+        TooManyPendingMoves,
+        /// Payload history depth exceeds `MAX_PAYLOAD_HISTORY`
+        HistoryTooDeep,
+        /// Node data version differs from expected one
+        VersionMismatch,
+        /// Payload time-to-live is zero
+        InvalidTtl,
+        /// Too many payloads expire at the same block
+        TooManyExpiries,
+        /// Storage deposit growth exceeds allowance given by node owner
+        DepositAllowanceExceeded,
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let mut consumed = Self::process_deletions(remaining_weight);
+            consumed.saturating_accrue(Self::process_path_updates(
+                remaining_weight.saturating_sub(consumed),
+            ));
+            consumed.saturating_accrue(Self::process_expiries(
+                n,
+                remaining_weight.saturating_sub(consumed),
+            ));
+            consumed
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Create a new node
+        ///
+        /// Child creators spend deposit allowance given by the parent owner.
         #[pallet::call_index(0)]
-        #[pallet::weight(T::WeightInfo::create_node())]
+        #[pallet::weight(T::WeightInfo::create_node(MAX_TREE_DEPTH - 1))]
         pub fn create_node(
             origin: OriginFor<T>,
             parent_id: Option<NodeId>,
@@ -809,112 +1260,54 @@ pub mod pallet {
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
 
-            // Get new node ID
-            let node_id = <NextNodeId<T>>::get();
-            <NextNodeId<T>>::put(node_id.saturating_add(1));
-
-            // Build path based on parent
-            let path = if let Some(pid) = parent_id {
+            // Child nodes are owned by parent owner, child creators act on its behalf
+            let owner = if let Some(pid) = parent_id {
                 let parent = <Nodes<T>>::get(pid).ok_or(Error::<T>::ParentNotFound)?;
-                ensure!(parent.owner == sender, Error::<T>::OwnerMismatch);
-
-                // Check tree depth - path already includes all ancestors
                 ensure!(
-                    parent.path.len() < MAX_TREE_DEPTH as usize,
-                    Error::<T>::MaxDepthExceeded
+                    Self::has_role(pid, &parent, &sender, Role::ChildCreator),
+                    Error::<T>::OwnerMismatch
                 );
-
-                // Build new path by extending parent's path
-                let mut new_path = parent.path.clone();
-                new_path
-                    .try_push(pid)
-                    .map_err(|_| Error::<T>::MaxDepthExceeded)?;
-
-                // Add to parent's children index
-                <NodesByParent<T>>::try_mutate(pid, |children| {
-                    children
-                        .try_push(node_id)
-                        .map_err(|_| Error::<T>::TooManyChildren)
-                })?;
-
-                new_path
+                Self::use_deposit_allowance(
+                    &parent.owner,
+                    &sender,
+                    Zero::zero(),
+                    Self::deposit_for(&meta, &payload),
+                )?;
+                parent.owner
             } else {
-                // Root node has empty path
-                <RootNodes<T>>::try_mutate(|roots| {
-                    roots
-                        .try_push(node_id)
-                        .map_err(|_| Error::<T>::TooManyRootNodes)
-                })?;
-
-                BoundedVec::default()
-            };
-
-            // Create node
-            let node = Node {
-                parent: parent_id,
-                owner: sender.clone(),
-                path,
-                meta,
-                payload,
+                sender
             };
 
-            // Store node
-            <Nodes<T>>::insert(node_id, node);
-
-            Self::deposit_event(Event::NodeCreated(node_id, parent_id, sender));
+            Self::do_create_node(owner, parent_id, meta, payload)?;
             Ok(())
         }
 
         /// Set node metadata
         #[pallet::call_index(1)]
-        #[pallet::weight(T::WeightInfo::set_meta())]
+        #[pallet::weight(T::WeightInfo::set_meta(MAX_TREE_DEPTH))]
         pub fn set_meta(
             origin: OriginFor<T>,
             node_id: NodeId,
             meta: Option<NodeData>,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
-
-            // Update node
-            <Nodes<T>>::try_mutate(node_id, |node_opt| {
-                let node = node_opt.as_mut().ok_or(Error::<T>::NodeNotFound)?;
-                ensure!(node.owner == sender, Error::<T>::NotNodeOwner);
-                node.meta = meta;
-                Ok::<(), DispatchError>(())
-            })?;
-
-            Self::deposit_event(Event::MetaSet(node_id, sender));
-            Ok(())
+            Self::do_set_meta(sender, node_id, meta, None)
         }
 
         /// Set node payload
+        ///
+        /// With `ttl` given the payload is treated as expired `ttl` blocks later
+        /// and cleared by `on_idle` sweeper.
         #[pallet::call_index(2)]
-        #[pallet::weight(T::WeightInfo::set_payload())]
+        #[pallet::weight(T::WeightInfo::set_payload(MAX_TREE_DEPTH))]
         pub fn set_payload(
             origin: OriginFor<T>,
             node_id: NodeId,
             payload: Option<NodeData>,
+            ttl: Option<BlockNumberFor<T>>,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
-
-            // Capture metadata and new payload for callback
-            let (meta, new_payload) = <Nodes<T>>::try_mutate(node_id, |node_opt| {
-                let node = node_opt.as_mut().ok_or(Error::<T>::NodeNotFound)?;
-                ensure!(node.owner == sender, Error::<T>::NotNodeOwner);
-                let meta = node.meta.clone();
-                node.payload = payload;
-                Ok::<(Option<NodeData>, Option<NodeData>), DispatchError>((
-                    meta,
-                    node.payload.clone(),
-                ))
-            })?;
-
-            Self::deposit_event(Event::PayloadSet(node_id, sender));
-
-            // Invoke the callback after successful payload update
-            T::OnPayloadSet::on_payload_set(node_id, meta, new_payload);
-
-            Ok(())
+            Self::do_set_payload(sender, node_id, payload, ttl, None)
         }
 
         /// Move node to a new parent
@@ -934,24 +1327,32 @@ pub mod pallet {
             // Verify ownership
             ensure!(node.owner == sender, Error::<T>::NotNodeOwner);
             ensure!(new_parent.owner == sender, Error::<T>::OwnerMismatch);
-
-            // Check subtree size BEFORE attempting the move
-            let subtree_size = Self::count_descendants(node_id)?;
             ensure!(
-                subtree_size <= MAX_MOVABLE_SUBTREE_SIZE,
-                Error::<T>::SubtreeTooLarge
+                !Self::is_pending_deletion(node_id, &node)
+                    && !Self::is_pending_deletion(new_parent_id, &new_parent),
+                Error::<T>::NodePendingDeletion
             );
+            ensure!(
+                !Self::is_moving(node_id, &node) && !Self::is_moving(new_parent_id, &new_parent),
+                Error::<T>::SubtreeMoving
+            );
+
+            // Subtrees larger than MAX_MOVABLE_SUBTREE_SIZE get descendant paths updated on idle
+            let subtree_size = Self::count_descendants(node_id)?;
+            let is_large = subtree_size > MAX_MOVABLE_SUBTREE_SIZE;
+            let new_parent_path = Self::resolved_path(&new_parent);
 
             // Check for cycles - node_id cannot be an ancestor of new_parent
             // If node_id is in new_parent's path, moving node under new_parent would create a cycle
             ensure!(
-                !new_parent.path.contains(&node_id),
+                !new_parent_path.contains(&node_id),
                 Error::<T>::CycleDetected
             );
 
-            // Check tree depth after move
+            // Check tree depth of the lowest descendant after move
+            let height = <SubtreeHeight<T>>::get(node_id);
             ensure!(
-                new_parent.path.len() < MAX_TREE_DEPTH as usize,
+                (new_parent_path.len() as u32).saturating_add(height) < MAX_TREE_DEPTH,
                 Error::<T>::MaxDepthExceeded
             );
 
@@ -965,9 +1366,7 @@ pub mod pallet {
                 });
             } else {
                 // Remove from root nodes
-                <RootNodes<T>>::mutate(|roots| {
-                    roots.retain(|&id| id != node_id);
-                });
+                Self::remove_root(&node.owner, node_id);
             }
 
             // Add to new parent's children
@@ -978,10 +1377,11 @@ pub mod pallet {
             })?;
 
             // Build new path
-            let mut new_path = new_parent.path.clone();
+            let mut new_path = new_parent_path;
             new_path
                 .try_push(new_parent_id)
                 .map_err(|_| Error::<T>::MaxDepthExceeded)?;
+            Self::raise_subtree_heights(&new_path, height);
 
             // Update node's parent and path
             <Nodes<T>>::mutate(node_id, |node_opt| {
@@ -991,8 +1391,22 @@ pub mod pallet {
                 }
             });
 
-            // Recursively update all descendant paths
-            Self::update_descendant_paths(node_id, &new_path)?;
+            if is_large {
+                // Schedule descendant paths update
+                <MoveQueue<T>>::try_mutate(|queue| {
+                    queue
+                        .try_push(node_id)
+                        .map_err(|_| Error::<T>::TooManyPendingMoves)
+                })?;
+                <MovingRoots<T>>::insert(node_id, ());
+                <PathUpdateCursor<T>>::insert(
+                    node_id,
+                    BoundedVec::truncate_from([(node_id, 0)].to_vec()),
+                );
+            } else {
+                // Recursively update all descendant paths
+                Self::update_descendant_paths(node_id, &new_path)?;
+            }
 
             Self::deposit_event(Event::NodeMoved(node_id, old_parent, new_parent_id, sender));
             Ok(())
@@ -1013,31 +1427,968 @@ pub mod pallet {
             // Check if node has children
             let children = <NodesByParent<T>>::get(node_id);
             ensure!(children.is_empty(), Error::<T>::NodeHasChildren);
+            ensure!(!Self::is_moving(node_id, &node), Error::<T>::SubtreeMoving);
 
-            // Remove from parent's children index
+            Self::remove_node(node_id, &node)?;
+
+            Self::deposit_event(Event::NodeDeleted(node_id, sender));
+            Ok(())
+        }
+
+        /// Grant role on node and its descendants to account
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::grant_role())]
+        pub fn grant_role(
+            origin: OriginFor<T>,
+            node_id: NodeId,
+            who: T::AccountId,
+            role: Role,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let node = <Nodes<T>>::get(node_id).ok_or(Error::<T>::NodeNotFound)?;
+            ensure!(node.owner == sender, Error::<T>::NotNodeOwner);
+
+            <NodeRoles<T>>::try_mutate(node_id, |roles| {
+                if !roles.iter().any(|(a, r)| *a == who && *r == role) {
+                    roles
+                        .try_push((who.clone(), role))
+                        .map_err(|_| Error::<T>::TooManyRoles)?;
+                }
+                Ok::<(), Error<T>>(())
+            })?;
+
+            Self::deposit_event(Event::RoleGranted(node_id, who, role));
+            Ok(())
+        }
+
+        /// Revoke role on node from account
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::revoke_role())]
+        pub fn revoke_role(
+            origin: OriginFor<T>,
+            node_id: NodeId,
+            who: T::AccountId,
+            role: Role,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let node = <Nodes<T>>::get(node_id).ok_or(Error::<T>::NodeNotFound)?;
+            ensure!(node.owner == sender, Error::<T>::NotNodeOwner);
+
+            <NodeRoles<T>>::try_mutate(node_id, |roles| {
+                let index = roles
+                    .iter()
+                    .position(|(a, r)| *a == who && *r == role)
+                    .ok_or(Error::<T>::RoleNotFound)?;
+                roles.remove(index);
+                Ok::<(), Error<T>>(())
+            })?;
+
+            Self::deposit_event(Event::RoleRevoked(node_id, who, role));
+            Ok(())
+        }
+
+        /// Offer ownership of node and its subtree to another account
+        ///
+        /// New offer replaces the previous one, offer to the owner itself cancels it.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::offer_transfer())]
+        pub fn offer_transfer(
+            origin: OriginFor<T>,
+            node_id: NodeId,
+            to: T::AccountId,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let node = <Nodes<T>>::get(node_id).ok_or(Error::<T>::NodeNotFound)?;
+            ensure!(node.owner == sender, Error::<T>::NotNodeOwner);
+
+            if to == sender {
+                <PendingTransfers<T>>::remove(node_id);
+            } else {
+                <PendingTransfers<T>>::insert(node_id, to.clone());
+            }
+
+            Self::deposit_event(Event::TransferOffered(node_id, sender, to));
+            Ok(())
+        }
+
+        /// Accept ownership of node and its subtree
+        ///
+        /// Storage deposits of subtree nodes are released to the previous owner and
+        /// held from the new one, granted roles are cleared. Non-root node is detached
+        /// from its parent and becomes a new root node.
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::accept_transfer())]
+        pub fn accept_transfer(origin: OriginFor<T>, node_id: NodeId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let offer = <PendingTransfers<T>>::take(node_id);
+            ensure!(offer.as_ref() == Some(&sender), Error::<T>::NoTransferOffer);
+            let node = <Nodes<T>>::get(node_id).ok_or(Error::<T>::NodeNotFound)?;
+            let old_owner = node.owner.clone();
+            ensure!(
+                !Self::is_pending_deletion(node_id, &node),
+                Error::<T>::NodePendingDeletion
+            );
+            ensure!(!Self::is_moving(node_id, &node), Error::<T>::SubtreeMoving);
+
+            // Check subtree size BEFORE attempting the transfer
+            let subtree_size = Self::count_descendants(node_id)?;
+            ensure!(
+                subtree_size <= MAX_MOVABLE_SUBTREE_SIZE,
+                Error::<T>::SubtreeTooLarge
+            );
+
+            // Detach from old owner tree
             if let Some(parent_id) = node.parent {
                 <NodesByParent<T>>::mutate(parent_id, |children| {
                     children.retain(|&id| id != node_id);
                 });
-            } else {
-                // Remove from root nodes
-                <RootNodes<T>>::mutate(|roots| {
-                    roots.retain(|&id| id != node_id);
+                <Nodes<T>>::mutate(node_id, |node_opt| {
+                    if let Some(node) = node_opt {
+                        node.parent = None;
+                        node.path = BoundedVec::default();
+                    }
                 });
+                Self::update_descendant_paths(node_id, &BoundedVec::default())?;
+            } else {
+                Self::remove_root(&old_owner, node_id);
             }
+            Self::add_root(&sender, node_id)?;
 
-            // Remove the node's children index entry
-            <NodesByParent<T>>::remove(node_id);
-
-            // Remove the node itself
-            <Nodes<T>>::remove(node_id);
+            // Reassign subtree nodes with their deposits
+            let mut queue = sp_std::collections::vec_deque::VecDeque::new();
+            queue.push_back(node_id);
+            while let Some(id) = queue.pop_front() {
+                let deposit = <NodeDeposit<T>>::get(id);
+                if !deposit.is_zero() {
+                    let reason = HoldReason::CpsNode.into();
+                    T::Currency::release(&reason, &old_owner, deposit, Precision::BestEffort)?;
+                    T::Currency::hold(&reason, &sender, deposit)?;
+                }
+                <Nodes<T>>::mutate(id, |node_opt| {
+                    if let Some(node) = node_opt {
+                        node.owner = sender.clone();
+                    }
+                });
+                <NodesByOwner<T>>::remove(&old_owner, id);
+                <NodesByOwner<T>>::insert(&sender, id, ());
+                <NodeRoles<T>>::remove(id);
+                <PendingTransfers<T>>::remove(id);
+                queue.extend(<NodesByParent<T>>::get(id));
+            }
 
-            Self::deposit_event(Event::NodeDeleted(node_id, sender));
+            Self::deposit_event(Event::OwnershipTransferred(node_id, old_owner, sender));
             Ok(())
         }
-    }
 
-    impl<T: Config> Pallet<T> {
+        /// Delete node with all its descendants
+        ///
+        /// Nodes are removed leaf-first in batches on idle across blocks, storage deposits
+        /// are released to the owner. Subtree couldn't get new nodes in the meantime.
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::delete_subtree())]
+        pub fn delete_subtree(origin: OriginFor<T>, node_id: NodeId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let node = <Nodes<T>>::get(node_id).ok_or(Error::<T>::NodeNotFound)?;
+            ensure!(node.owner == sender, Error::<T>::NotNodeOwner);
+            ensure!(
+                !Self::is_pending_deletion(node_id, &node),
+                Error::<T>::NodePendingDeletion
+            );
+            ensure!(!Self::is_moving(node_id, &node), Error::<T>::SubtreeMoving);
+
+            <DeletionQueue<T>>::insert(node_id, ());
+
+            Self::deposit_event(Event::SubtreeDeletionScheduled(node_id, sender));
+            Ok(())
+        }
+
+        /// Keep last `depth` payload versions of node
+        ///
+        /// Storage deposit of the node grows by the size of `depth` payload records.
+        /// Changing depth clears kept history, zero depth disables it.
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::set_payload_history())]
+        pub fn set_payload_history(
+            origin: OriginFor<T>,
+            node_id: NodeId,
+            depth: u32,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(depth <= MAX_PAYLOAD_HISTORY, Error::<T>::HistoryTooDeep);
+
+            let node = <Nodes<T>>::get(node_id).ok_or(Error::<T>::NodeNotFound)?;
+            ensure!(node.owner == sender, Error::<T>::NotNodeOwner);
+
+            let deposit = Self::deposit_for(&node.meta, &node.payload)
+                .saturating_add(Self::history_deposit(depth));
+            Self::update_deposit(node_id, &node.owner, deposit)?;
+
+            let _ = <PayloadHistory<T>>::clear_prefix(node_id, MAX_PAYLOAD_HISTORY, None);
+            if depth > 0 {
+                <PayloadHistoryDepth<T>>::insert(node_id, depth);
+            } else {
+                <PayloadHistoryDepth<T>>::remove(node_id);
+            }
+
+            Self::deposit_event(Event::PayloadHistorySet(node_id, depth));
+            Ok(())
+        }
+
+        /// Set node payload if its current version is `expected_version`
+        ///
+        /// Lets concurrent writers detect lost updates: the call fails with
+        /// `VersionMismatch` when payload changed since `expected_version` was read.
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::set_payload(MAX_TREE_DEPTH))]
+        pub fn set_payload_if(
+            origin: OriginFor<T>,
+            node_id: NodeId,
+            expected_version: u32,
+            payload: Option<NodeData>,
+            ttl: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::do_set_payload(sender, node_id, payload, ttl, Some(expected_version))
+        }
+
+        /// Set node metadata if its current version is `expected_version`
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::set_meta(MAX_TREE_DEPTH))]
+        pub fn set_meta_if(
+            origin: OriginFor<T>,
+            node_id: NodeId,
+            expected_version: u32,
+            meta: Option<NodeData>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::do_set_meta(sender, node_id, meta, Some(expected_version))
+        }
+
+        /// Set storage deposit `grantee` could grow on nodes of sender
+        ///
+        /// Writers, meta editors and child creators hold node deposits from owner funds,
+        /// deposit they grow is taken from the allowance and deposit they release returns to it.
+        #[pallet::call_index(13)]
+        #[pallet::weight(T::WeightInfo::set_deposit_allowance())]
+        pub fn set_deposit_allowance(
+            origin: OriginFor<T>,
+            grantee: T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            if amount.is_zero() {
+                <DepositAllowance<T>>::remove(&sender, &grantee);
+            } else {
+                <DepositAllowance<T>>::insert(&sender, &grantee, amount);
+            }
+
+            Self::deposit_event(Event::DepositAllowanceSet(sender, grantee, amount));
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Set node metadata on behalf of `sender`, checking version when `expected_version` given.
+        fn do_set_meta(
+            sender: T::AccountId,
+            node_id: NodeId,
+            meta: Option<NodeData>,
+            expected_version: Option<u32>,
+        ) -> DispatchResult {
+            if let Some(expected) = expected_version {
+                ensure!(
+                    <MetaVersion<T>>::get(node_id) == expected,
+                    Error::<T>::VersionMismatch
+                );
+            }
+
+            // Update node
+            <Nodes<T>>::try_mutate(node_id, |node_opt| {
+                let node = node_opt.as_mut().ok_or(Error::<T>::NodeNotFound)?;
+                ensure!(
+                    Self::has_role(node_id, node, &sender, Role::MetaEditor),
+                    Error::<T>::NotNodeOwner
+                );
+                let deposit = Self::deposit_for(&meta, &node.payload).saturating_add(
+                    Self::history_deposit(<PayloadHistoryDepth<T>>::get(node_id)),
+                );
+                let held = <NodeDeposit<T>>::get(node_id);
+                Self::use_deposit_allowance(&node.owner, &sender, held, deposit)?;
+                Self::update_deposit(node_id, &node.owner, deposit)?;
+                node.meta = meta;
+                Ok::<(), DispatchError>(())
+            })?;
+
+            let version = <MetaVersion<T>>::mutate(node_id, |version| {
+                *version = version.saturating_add(1);
+                *version
+            });
+
+            Self::deposit_event(Event::MetaSet(node_id, sender, version));
+            Ok(())
+        }
+
+        /// Set node payload on behalf of `sender`, checking version when `expected_version` given.
+        fn do_set_payload(
+            sender: T::AccountId,
+            node_id: NodeId,
+            payload: Option<NodeData>,
+            ttl: Option<BlockNumberFor<T>>,
+            expected_version: Option<u32>,
+        ) -> DispatchResult {
+            if let Some(expected) = expected_version {
+                ensure!(
+                    <PayloadVersion<T>>::get(node_id) == expected,
+                    Error::<T>::VersionMismatch
+                );
+            }
+            if let Some(ttl) = ttl {
+                ensure!(!ttl.is_zero(), Error::<T>::InvalidTtl);
+            }
+
+            // Capture metadata and new payload for callback
+            let (meta, new_payload) = <Nodes<T>>::try_mutate(node_id, |node_opt| {
+                let node = node_opt.as_mut().ok_or(Error::<T>::NodeNotFound)?;
+                ensure!(
+                    Self::has_role(node_id, node, &sender, Role::Writer),
+                    Error::<T>::NotNodeOwner
+                );
+                let deposit = Self::deposit_for(&node.meta, &payload).saturating_add(
+                    Self::history_deposit(<PayloadHistoryDepth<T>>::get(node_id)),
+                );
+                let held = <NodeDeposit<T>>::get(node_id);
+                Self::use_deposit_allowance(&node.owner, &sender, held, deposit)?;
+                Self::update_deposit(node_id, &node.owner, deposit)?;
+                let meta = node.meta.clone();
+                node.payload = payload;
+                Ok::<(Option<NodeData>, Option<NodeData>), DispatchError>((
+                    meta,
+                    node.payload.clone(),
+                ))
+            })?;
+
+            // New payload replaces expiry of the previous one
+            Self::cancel_expiry(node_id);
+            if let (Some(ttl), Some(_)) = (ttl, &new_payload) {
+                let now = <frame_system::Pallet<T>>::block_number();
+                Self::schedule_expiry(node_id, now.saturating_add(ttl))?;
+            }
+
+            let version = Self::bump_payload_version(node_id, &sender, &new_payload);
+            Self::deposit_event(Event::PayloadSet(node_id, sender, version));
+
+            // Invoke the callback after successful payload update
+            T::OnPayloadSet::on_payload_set(node_id, meta, new_payload);
+
+            Ok(())
+        }
+
+        /// Increment payload version of node and keep the new version in history
+        /// when node opted in.
+        fn bump_payload_version(
+            node_id: NodeId,
+            setter: &T::AccountId,
+            payload: &Option<NodeData>,
+        ) -> u32 {
+            let version = <PayloadVersion<T>>::mutate(node_id, |version| {
+                *version = version.saturating_add(1);
+                *version
+            });
+
+            let depth = <PayloadHistoryDepth<T>>::get(node_id);
+            if depth > 0 {
+                let record = PayloadRecord {
+                    version,
+                    block: <frame_system::Pallet<T>>::block_number(),
+                    timestamp: T::Time::now(),
+                    setter: setter.clone(),
+                    payload: payload.clone(),
+                };
+                <PayloadHistory<T>>::insert(node_id, version % depth, record);
+            }
+            version
+        }
+
+        /// Create a new node owned by `owner` and return its ID.
+        ///
+        /// Performs the same checks as `create_node` extrinsic, so it could be used
+        /// by other pallets to build trees on behalf of an account.
+        pub fn do_create_node(
+            owner: T::AccountId,
+            parent_id: Option<NodeId>,
+            meta: Option<NodeData>,
+            payload: Option<NodeData>,
+        ) -> Result<NodeId, DispatchError> {
+            // Get new node ID
+            let node_id = <NextNodeId<T>>::get();
+            <NextNodeId<T>>::put(node_id.saturating_add(1));
+
+            // Build path based on parent
+            let path = if let Some(pid) = parent_id {
+                let parent = <Nodes<T>>::get(pid).ok_or(Error::<T>::ParentNotFound)?;
+                ensure!(parent.owner == owner, Error::<T>::OwnerMismatch);
+                ensure!(
+                    !Self::is_pending_deletion(pid, &parent),
+                    Error::<T>::NodePendingDeletion
+                );
+
+                // Check tree depth - path already includes all ancestors
+                let parent_path = Self::resolved_path(&parent);
+                ensure!(
+                    parent_path.len() < MAX_TREE_DEPTH as usize,
+                    Error::<T>::MaxDepthExceeded
+                );
+
+                // Build new path by extending parent's path
+                let mut new_path = parent_path;
+                new_path
+                    .try_push(pid)
+                    .map_err(|_| Error::<T>::MaxDepthExceeded)?;
+
+                // Add to parent's children index
+                <NodesByParent<T>>::try_mutate(pid, |children| {
+                    children
+                        .try_push(node_id)
+                        .map_err(|_| Error::<T>::TooManyChildren)
+                })?;
+                Self::raise_subtree_heights(&new_path, 0);
+
+                new_path
+            } else {
+                // Root node has empty path
+                Self::add_root(&owner, node_id)?;
+
+                BoundedVec::default()
+            };
+
+            // Hold storage deposit from owner
+            Self::update_deposit(node_id, &owner, Self::deposit_for(&meta, &payload))?;
+
+            // Create node
+            let node = Node {
+                parent: parent_id,
+                owner: owner.clone(),
+                path,
+                meta,
+                payload,
+            };
+
+            // Store node
+            <Nodes<T>>::insert(node_id, node);
+            <NodesByOwner<T>>::insert(&owner, node_id, ());
+
+            Self::deposit_event(Event::NodeCreated(node_id, parent_id, owner));
+            Ok(node_id)
+        }
+
+        /// Check if account owns the node or has role granted on it or any of its ancestors.
+        pub fn has_role(
+            node_id: NodeId,
+            node: &Node<T::AccountId>,
+            who: &T::AccountId,
+            role: Role,
+        ) -> bool {
+            node.owner == *who
+                || Self::resolved_path(node)
+                    .iter()
+                    .chain(core::iter::once(&node_id))
+                    .any(|id| {
+                        <NodeRoles<T>>::get(id)
+                            .iter()
+                            .any(|(a, r)| a == who && *r == role)
+                    })
+        }
+
+        /// Storage deposit required for node with given metadata and payload.
+        pub fn deposit_for(meta: &Option<NodeData>, payload: &Option<NodeData>) -> BalanceOf<T> {
+            let bytes =
+                meta.as_ref().map_or(0, |d| d.len()) + payload.as_ref().map_or(0, |d| d.len());
+            T::NodeDepositBase::get()
+                .saturating_add(T::ByteDeposit::get().saturating_mul((bytes as u32).into()))
+        }
+
+        /// Storage deposit required for payload history of `depth` records.
+        pub fn history_deposit(depth: u32) -> BalanceOf<T> {
+            let bytes = (PayloadRecordOf::<T>::max_encoded_len() as u32).saturating_mul(depth);
+            T::ByteDeposit::get().saturating_mul(bytes.into())
+        }
+
+        /// Payload record of node `version` if it's still kept in history.
+        pub fn payload_at(node_id: NodeId, version: u32) -> Option<PayloadRecordOf<T>> {
+            let depth = <PayloadHistoryDepth<T>>::get(node_id);
+            if depth == 0 {
+                return None;
+            }
+            <PayloadHistory<T>>::get(node_id, version % depth).filter(|r| r.version == version)
+        }
+
+        /// Hold or release node owner funds to make node deposit equal to `amount`.
+        fn update_deposit(
+            node_id: NodeId,
+            owner: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let held = <NodeDeposit<T>>::get(node_id);
+            let reason = HoldReason::CpsNode.into();
+            if amount > held {
+                T::Currency::hold(&reason, owner, amount.saturating_sub(held))?;
+            } else if held > amount {
+                T::Currency::release(
+                    &reason,
+                    owner,
+                    held.saturating_sub(amount),
+                    Precision::BestEffort,
+                )?;
+            }
+
+            if amount.is_zero() {
+                <NodeDeposit<T>>::remove(node_id);
+            } else {
+                <NodeDeposit<T>>::insert(node_id, amount);
+            }
+            Ok(())
+        }
+
+        /// Change deposit allowance of `grantee` acting on owner node with `held` deposit
+        /// that becomes `amount`.
+        fn use_deposit_allowance(
+            owner: &T::AccountId,
+            grantee: &T::AccountId,
+            held: BalanceOf<T>,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            if owner == grantee || amount == held {
+                return Ok(());
+            }
+            <DepositAllowance<T>>::try_mutate(owner, grantee, |allowance| {
+                *allowance = if amount > held {
+                    allowance
+                        .checked_sub(&amount.saturating_sub(held))
+                        .ok_or(Error::<T>::DepositAllowanceExceeded)?
+                } else {
+                    allowance.saturating_add(held.saturating_sub(amount))
+                };
+                Ok::<(), Error<T>>(())
+            })?;
+            Ok(())
+        }
+
+        /// Root nodes owned by account.
+        pub fn roots_of(owner: &T::AccountId) -> Vec<NodeId> {
+            <RootsByOwner<T>>::iter_key_prefix(owner).collect()
+        }
+
+        /// Nodes owned by account, up to `limit` nodes after `cursor` node.
+        ///
+        /// Nodes aren't ordered by ID, the last returned node is a cursor for the next page.
+        pub fn nodes_of(owner: &T::AccountId, cursor: Option<NodeId>, limit: u32) -> Vec<NodeId> {
+            let iter = match cursor {
+                Some(node_id) => <NodesByOwner<T>>::iter_key_prefix_from(
+                    owner,
+                    <NodesByOwner<T>>::hashed_key_for(owner, node_id),
+                ),
+                None => <NodesByOwner<T>>::iter_key_prefix(owner),
+            };
+            iter.take(limit as usize).collect()
+        }
+
+        /// Actual ancestors of the node from root to parent.
+        pub fn ancestors(node_id: NodeId) -> Vec<NodeId> {
+            <Nodes<T>>::get(node_id)
+                .map(|node| Self::resolved_path(&node).into_inner())
+                .unwrap_or_default()
+        }
+
+        /// Node with its descendants up to `max_depth` levels below, breadth-first.
+        ///
+        /// At most `limit` nodes are returned, parents always precede their children.
+        pub fn subtree(
+            node_id: NodeId,
+            max_depth: u32,
+            limit: u32,
+        ) -> Vec<(NodeId, Node<T::AccountId>)> {
+            let mut nodes = Vec::new();
+            let mut queue = sp_std::collections::vec_deque::VecDeque::new();
+            queue.push_back((node_id, 0u32));
+            while let Some((id, depth)) = queue.pop_front() {
+                if nodes.len() >= limit as usize {
+                    break;
+                }
+                let Some(node) = Self::live_node(id) else {
+                    continue;
+                };
+                if depth < max_depth {
+                    queue.extend(
+                        <NodesByParent<T>>::get(id)
+                            .into_iter()
+                            .map(|child| (child, depth.saturating_add(1))),
+                    );
+                }
+                nodes.push((id, node));
+            }
+            nodes
+        }
+
+        /// Index node as root of owner, bounded by `MAX_ROOTS_PER_OWNER`.
+        fn add_root(owner: &T::AccountId, node_id: NodeId) -> DispatchResult {
+            <RootCount<T>>::try_mutate(owner, |count| {
+                ensure!(*count < MAX_ROOTS_PER_OWNER, Error::<T>::TooManyRootNodes);
+                *count = count.saturating_add(1);
+                Ok::<_, DispatchError>(())
+            })?;
+            <RootsByOwner<T>>::insert(owner, node_id, ());
+            Ok(())
+        }
+
+        /// Remove node from root index of owner.
+        fn remove_root(owner: &T::AccountId, node_id: NodeId) {
+            if <RootsByOwner<T>>::take(owner, node_id).is_some() {
+                <RootCount<T>>::mutate_exists(owner, |count| {
+                    *count = count.map(|c| c.saturating_sub(1)).filter(|c| *c > 0);
+                });
+            }
+        }
+
+        /// Check if node or any of its ancestors is pending deletion.
+        pub fn is_pending_deletion(node_id: NodeId, node: &Node<T::AccountId>) -> bool {
+            <DeletionQueue<T>>::contains_key(node_id)
+                || Self::resolved_path(node)
+                    .iter()
+                    .any(|id| <DeletionQueue<T>>::contains_key(id))
+        }
+
+        /// Check if node belongs to a moved subtree with descendant paths pending update.
+        ///
+        /// Subtree structure isn't changed by the move, so outdated paths
+        /// still contain the moved subtree root.
+        pub fn is_moving(node_id: NodeId, node: &Node<T::AccountId>) -> bool {
+            <MovingRoots<T>>::contains_key(node_id)
+                || node
+                    .path
+                    .iter()
+                    .any(|id| <MovingRoots<T>>::contains_key(id))
+        }
+
+        /// Actual ancestor path of the node.
+        ///
+        /// Descendant paths of large moved subtrees are updated on idle, until then
+        /// the path is rebuilt from the path of the moved subtree root.
+        pub fn resolved_path(node: &Node<T::AccountId>) -> BoundedVec<NodeId, MaxTreeDepth> {
+            Self::resolve_path(&node.path)
+        }
+
+        fn resolve_path(
+            path: &BoundedVec<NodeId, MaxTreeDepth>,
+        ) -> BoundedVec<NodeId, MaxTreeDepth> {
+            // The closest moved ancestor has up to date path, except when it's
+            // inside another moved subtree
+            let Some(pos) = path
+                .iter()
+                .rposition(|id| <MovingRoots<T>>::contains_key(id))
+            else {
+                return path.clone();
+            };
+            let Some(root) = <Nodes<T>>::get(path[pos]) else {
+                return path.clone();
+            };
+            let mut resolved = Self::resolve_path(&root.path).into_inner();
+            resolved.extend_from_slice(&path[pos..]);
+            BoundedVec::try_from(resolved).unwrap_or_else(|_| path.clone())
+        }
+
+        /// Raise subtree height of ancestors in `path` to fit node of `height` below them.
+        ///
+        /// Heights aren't lowered when descendants are deleted or moved away,
+        /// so they stay an upper bound and walk stops at the first high enough ancestor.
+        fn raise_subtree_heights(path: &[NodeId], height: u32) {
+            let len = path.len() as u32;
+            for (depth, ancestor) in path.iter().enumerate().rev() {
+                let required = len.saturating_sub(depth as u32).saturating_add(height);
+                if <SubtreeHeight<T>>::get(ancestor) >= required {
+                    break;
+                }
+                <SubtreeHeight<T>>::insert(ancestor, required);
+            }
+        }
+
+        /// Remove node without children from the tree and release its deposit.
+        fn remove_node(node_id: NodeId, node: &Node<T::AccountId>) -> DispatchResult {
+            // Remove from parent's children index
+            if let Some(parent_id) = node.parent {
+                <NodesByParent<T>>::mutate(parent_id, |children| {
+                    children.retain(|&id| id != node_id);
+                });
+            } else {
+                // Remove from root nodes
+                Self::remove_root(&node.owner, node_id);
+            }
+
+            // Remove the node's children index, roles and transfer offer entries
+            <NodesByParent<T>>::remove(node_id);
+            <NodeRoles<T>>::remove(node_id);
+            <PendingTransfers<T>>::remove(node_id);
+
+            // Remove payload versions
+            let _ = <PayloadHistory<T>>::clear_prefix(node_id, MAX_PAYLOAD_HISTORY, None);
+            <PayloadHistoryDepth<T>>::remove(node_id);
+            <PayloadVersion<T>>::remove(node_id);
+            <MetaVersion<T>>::remove(node_id);
+            <SubtreeHeight<T>>::remove(node_id);
+            Self::cancel_expiry(node_id);
+
+            // Remove the node itself and release its deposit
+            <Nodes<T>>::remove(node_id);
+            <NodesByOwner<T>>::remove(&node.owner, node_id);
+            Self::update_deposit(node_id, &node.owner, Zero::zero())
+        }
+
+        /// Whether node payload is past its expiry block.
+        pub fn is_payload_expired(node_id: NodeId) -> bool {
+            <PayloadExpiry<T>>::get(node_id)
+                .is_some_and(|at| at <= <frame_system::Pallet<T>>::block_number())
+        }
+
+        /// Node as seen by clients: payload hidden once expired, even if not swept yet,
+        /// and path resolved while the node's subtree is moving.
+        pub fn live_node(node_id: NodeId) -> Option<Node<T::AccountId>> {
+            let mut node = <Nodes<T>>::get(node_id)?;
+            node.path = Self::resolved_path(&node);
+            if Self::is_payload_expired(node_id) {
+                node.payload = None;
+            }
+            Some(node)
+        }
+
+        /// Schedule node payload to expire at block `at`.
+        fn schedule_expiry(node_id: NodeId, at: BlockNumberFor<T>) -> DispatchResult {
+            <ExpiringPayloads<T>>::try_mutate(at, |nodes| nodes.try_push(node_id))
+                .map_err(|_| Error::<T>::TooManyExpiries)?;
+            <PayloadExpiry<T>>::insert(node_id, at);
+            if <ExpiryCursor<T>>::get().is_none() {
+                <ExpiryCursor<T>>::put(<frame_system::Pallet<T>>::block_number());
+            }
+            Ok(())
+        }
+
+        /// Drop scheduled expiry of node payload, if any.
+        fn cancel_expiry(node_id: NodeId) {
+            if let Some(at) = <PayloadExpiry<T>>::take(node_id) {
+                <ExpiringPayloads<T>>::mutate(at, |nodes| nodes.retain(|&id| id != node_id));
+            }
+        }
+
+        /// Clear expired payload of node and notify `OnPayloadSet` handler.
+        fn expire_payload(node_id: NodeId) {
+            <PayloadExpiry<T>>::remove(node_id);
+            let Some(mut node) = <Nodes<T>>::get(node_id) else {
+                return;
+            };
+            if node.payload.is_none() {
+                return;
+            }
+
+            node.payload = None;
+            let deposit = Self::deposit_for(&node.meta, &node.payload).saturating_add(
+                Self::history_deposit(<PayloadHistoryDepth<T>>::get(node_id)),
+            );
+            let _ = Self::update_deposit(node_id, &node.owner, deposit);
+            let meta = node.meta.clone();
+            let version = Self::bump_payload_version(node_id, &node.owner, &node.payload);
+            <Nodes<T>>::insert(node_id, node);
+
+            Self::deposit_event(Event::PayloadExpired(node_id, version));
+            T::OnPayloadSet::on_payload_set(node_id, meta, None);
+        }
+
+        /// Clear payloads expired up to block `now`, block by block from expiry cursor.
+        fn process_expiries(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let mut consumed = T::DbWeight::get().reads(1);
+            if remaining_weight.any_lt(consumed) {
+                return Weight::zero();
+            }
+
+            let Some(mut cursor) = <ExpiryCursor<T>>::get() else {
+                return consumed;
+            };
+            consumed.saturating_accrue(T::DbWeight::get().writes(1));
+
+            // Whole block of expiring payloads is cleared at once
+            let block_step = T::DbWeight::get().reads_writes(1, 1);
+            let node_step = T::WeightInfo::set_payload(0);
+            while cursor <= now
+                && consumed
+                    .saturating_add(block_step)
+                    .all_lte(remaining_weight)
+            {
+                let nodes = <ExpiringPayloads<T>>::get(cursor);
+                let step = block_step.saturating_add(node_step.saturating_mul(nodes.len() as u64));
+                if consumed.saturating_add(step).any_gt(remaining_weight) {
+                    consumed.saturating_accrue(T::DbWeight::get().reads(1));
+                    break;
+                }
+                consumed.saturating_accrue(step);
+
+                for node_id in nodes {
+                    if <PayloadExpiry<T>>::get(node_id) == Some(cursor) {
+                        Self::expire_payload(node_id);
+                    }
+                }
+                <ExpiringPayloads<T>>::remove(cursor);
+                cursor = cursor.saturating_add(One::one());
+            }
+
+            // Sweeper sleeps until the next payload with expiry is set
+            if cursor > now {
+                consumed.saturating_accrue(T::DbWeight::get().reads(1));
+                if <PayloadExpiry<T>>::iter_keys().next().is_none() {
+                    <ExpiryCursor<T>>::kill();
+                    return consumed;
+                }
+            }
+
+            <ExpiryCursor<T>>::put(cursor);
+            consumed
+        }
+
+        /// Remove batch of nodes of the subtree next to deletion cursor, so every
+        /// pending subtree makes progress regardless of others' size.
+        fn process_deletions(remaining_weight: Weight) -> Weight {
+            let mut consumed = T::DbWeight::get().reads(3);
+            if remaining_weight.any_lt(consumed) {
+                return Weight::zero();
+            }
+
+            let last = <DeletionCursor<T>>::get();
+            let next = last
+                .and_then(|last| {
+                    <DeletionQueue<T>>::iter_keys_from(<DeletionQueue<T>>::hashed_key_for(last))
+                        .next()
+                })
+                .or_else(|| <DeletionQueue<T>>::iter_keys().next());
+            let Some(root) = next else {
+                if last.is_some() {
+                    <DeletionCursor<T>>::kill();
+                    consumed.saturating_accrue(T::DbWeight::get().writes(1));
+                }
+                return consumed;
+            };
+            <DeletionCursor<T>>::put(root);
+            consumed.saturating_accrue(T::DbWeight::get().writes(1));
+
+            // Deleting a node takes descent to the leaf through the subtree
+            let step = T::WeightInfo::delete_node()
+                .saturating_add(T::DbWeight::get().reads(MAX_TREE_DEPTH.into()));
+            let mut deleted = 0u32;
+            let mut completed = false;
+            while deleted < MAX_DELETION_BATCH
+                && consumed.saturating_add(step).all_lte(remaining_weight)
+            {
+                consumed.saturating_accrue(step);
+
+                let mut leaf = root;
+                while let Some(&child) = <NodesByParent<T>>::get(leaf).first() {
+                    leaf = child;
+                }
+                let Some(node) = <Nodes<T>>::get(leaf) else {
+                    // Subtree root was already removed
+                    completed = true;
+                    break;
+                };
+                let _ = Self::remove_node(leaf, &node);
+                deleted = deleted.saturating_add(1);
+
+                if leaf == root {
+                    completed = true;
+                    break;
+                }
+            }
+
+            if completed {
+                <DeletionQueue<T>>::remove(root);
+                consumed.saturating_accrue(T::DbWeight::get().writes(1));
+                Self::deposit_event(Event::SubtreeDeleted(root));
+            } else if deleted > 0 {
+                Self::deposit_event(Event::SubtreeDeletionProgress(root, deleted));
+            }
+
+            consumed
+        }
+
+        /// Update paths of batch of nodes of the first subtree in move queue.
+        fn process_path_updates(remaining_weight: Weight) -> Weight {
+            let mut consumed = T::DbWeight::get().reads(1);
+            if remaining_weight.any_lt(consumed) {
+                return Weight::zero();
+            }
+
+            let mut queue = <MoveQueue<T>>::get();
+            let Some(&root) = queue.first() else {
+                return consumed;
+            };
+            let mut cursor = <PathUpdateCursor<T>>::get(root);
+            consumed.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+
+            // Updating a node path takes resolving the actual path of its parent
+            let step = T::DbWeight::get().reads_writes((MAX_TREE_DEPTH + 3).into(), 1);
+            let mut updated = 0u32;
+            let mut steps = 0u32;
+            while steps < MAX_PATH_UPDATE_BATCH
+                && consumed.saturating_add(step).all_lte(remaining_weight)
+            {
+                let Some(&(parent_id, index)) = cursor.last() else {
+                    break;
+                };
+                consumed.saturating_accrue(step);
+                steps = steps.saturating_add(1);
+
+                // Go back to the parent when all children are visited
+                let Some(&child_id) = <NodesByParent<T>>::get(parent_id).get(index as usize) else {
+                    cursor.pop();
+                    continue;
+                };
+                if let Some(top) = cursor.last_mut() {
+                    top.1 = index.saturating_add(1);
+                }
+
+                if let Some(parent) = <Nodes<T>>::get(parent_id) {
+                    let mut path = Self::resolve_path(&parent.path);
+                    if path.try_push(parent_id).is_ok() {
+                        <Nodes<T>>::mutate(child_id, |node_opt| {
+                            if let Some(node) = node_opt {
+                                node.path = path;
+                            }
+                        });
+                        updated = updated.saturating_add(1);
+                    }
+                }
+
+                // Cursor depth is bounded by the tree depth
+                let _ = cursor.try_push((child_id, 0));
+            }
+
+            if cursor.is_empty() {
+                queue.remove(0);
+                <MoveQueue<T>>::put(queue);
+                <MovingRoots<T>>::remove(root);
+                <PathUpdateCursor<T>>::remove(root);
+                consumed.saturating_accrue(T::DbWeight::get().writes(2));
+                Self::deposit_event(Event::SubtreeMoved(root));
+            } else {
+                <PathUpdateCursor<T>>::insert(root, cursor);
+                if updated > 0 {
+                    Self::deposit_event(Event::SubtreeMoveProgress(root, updated));
+                }
+            }
+
+            consumed
+        }
+
         /// Count total number of descendants for a given node
         ///
         /// Returns the count of all nodes in the subtree rooted at `node_id`,
@@ -1045,6 +2396,7 @@ pub mod pallet {
         /// descendant nodes that would need path updates during a move operation.
         ///
         /// Uses iterative breadth-first traversal to avoid stack overflow.
+        /// Fails when any visited descendant is scheduled for deletion.
         fn count_descendants(node_id: NodeId) -> Result<u32, Error<T>> {
             let mut count = 0u32;
             let mut queue = sp_std::collections::vec_deque::VecDeque::new();
@@ -1057,6 +2409,11 @@ pub mod pallet {
 
             // Iteratively process all nodes in the subtree (breadth-first)
             while let Some(current_id) = queue.pop_front() {
+                ensure!(
+                    !<DeletionQueue<T>>::contains_key(current_id),
+                    Error::<T>::NodePendingDeletion
+                );
+
                 // Count this node
                 count = count.saturating_add(1);
 
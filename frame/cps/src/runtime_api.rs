@@ -0,0 +1,58 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2026 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Runtime API definition for CPS pallet.
+
+use crate::{Node, NodeId, PayloadRecord};
+use core::fmt::Debug;
+use parity_scale_codec::{Codec, MaxEncodedLen};
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+    /// CPS tree and owner lookup.
+    pub trait CpsApi<AccountId, BlockNumber, Moment>
+    where
+        AccountId: Codec + MaxEncodedLen + Debug,
+        BlockNumber: Codec,
+        Moment: Codec,
+    {
+        /// Node by ID, with payload hidden once expired and actual path of moving subtree.
+        fn node(node_id: NodeId) -> Option<Node<AccountId>>;
+
+        /// Direct children of node.
+        fn children(node_id: NodeId) -> Vec<NodeId>;
+
+        /// Node with descendants up to `max_depth` levels below, breadth-first,
+        /// at most `limit` nodes.
+        fn subtree(node_id: NodeId, max_depth: u32, limit: u32) -> Vec<(NodeId, Node<AccountId>)>;
+
+        /// Node ancestors from root to parent.
+        fn ancestors(node_id: NodeId) -> Vec<NodeId>;
+
+        /// Nodes owned by `account`, up to `limit` nodes after `cursor` node.
+        fn nodes_by_owner(account: AccountId, cursor: Option<NodeId>, limit: u32) -> Vec<NodeId>;
+
+        /// Root nodes owned by `owner`.
+        fn roots(owner: AccountId) -> Vec<NodeId>;
+
+        /// Current payload and metadata versions of node.
+        fn versions(node_id: NodeId) -> (u32, u32);
+
+        /// Payload `version` of node if it's still kept in node history.
+        fn payload_at(node_id: NodeId, version: u32) -> Option<PayloadRecord<AccountId, BlockNumber, Moment>>;
+    }
+}
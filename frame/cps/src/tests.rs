@@ -18,12 +18,14 @@
 //! Tests for pallet-robonomics-cps
 
 use crate::{self as pallet_cps, *};
+use frame_support::traits::{fungible::InspectHold, Hooks};
 use frame_support::{
-    assert_noop, assert_ok, derive_impl, parameter_types, traits::InstanceFilter, BoundedVec,
+    assert_noop, assert_ok, derive_impl, parameter_types, traits::InstanceFilter, weights::Weight,
+    BoundedVec,
 };
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
-use sp_runtime::BuildStorage;
+use sp_runtime::{BuildStorage, TokenError};
 
 type Block = frame_system::mocking::MockBlock<Runtime>;
 type Balance = u64;
@@ -32,6 +34,7 @@ frame_support::construct_runtime!(
     pub enum Runtime {
         System: frame_system,
         Balances: pallet_balances,
+        Timestamp: pallet_timestamp,
         Proxy: pallet_proxy,
         Cps: pallet_cps,
     }
@@ -177,9 +180,26 @@ impl pallet_proxy::Config for Runtime {
     type BlockNumberProvider = System;
 }
 
+impl pallet_timestamp::Config for Runtime {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = ();
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub static NodeDepositBase: Balance = 0;
+    pub static ByteDeposit: Balance = 0;
+}
+
 impl pallet_cps::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type OnPayloadSet = ();
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type Currency = Balances;
+    type NodeDepositBase = NodeDepositBase;
+    type ByteDeposit = ByteDeposit;
+    type Time = Timestamp;
     type WeightInfo = weights::TestWeightInfo;
 }
 
@@ -218,7 +238,32 @@ fn create_root_node_works() {
         assert_eq!(node.owner, account);
 
         // Check indexes
-        assert_eq!(Cps::root_nodes().len(), 1);
+        assert_eq!(Cps::roots_of(&account), vec![NodeId(0)]);
+        assert_eq!(Cps::root_count(account), 1);
+    });
+}
+
+#[test]
+fn roots_are_bounded_per_owner() {
+    new_test_ext().execute_with(|| {
+        for _ in 0..MAX_ROOTS_PER_OWNER {
+            assert_ok!(Cps::create_node(RuntimeOrigin::signed(1), None, None, None));
+        }
+        assert_eq!(Cps::root_count(1), MAX_ROOTS_PER_OWNER);
+        assert_noop!(
+            Cps::create_node(RuntimeOrigin::signed(1), None, None, None),
+            Error::<Runtime>::TooManyRootNodes
+        );
+
+        // Other accounts aren't affected
+        assert_ok!(Cps::create_node(RuntimeOrigin::signed(2), None, None, None));
+        let node_id = NodeId(MAX_ROOTS_PER_OWNER as u64);
+        assert_eq!(Cps::roots_of(&2), vec![node_id]);
+
+        // Deleted root frees the slot
+        assert_ok!(Cps::delete_node(RuntimeOrigin::signed(1), NodeId(0)));
+        assert_eq!(Cps::root_count(1), MAX_ROOTS_PER_OWNER - 1);
+        assert_ok!(Cps::create_node(RuntimeOrigin::signed(1), None, None, None));
     });
 }
 
@@ -441,7 +486,8 @@ fn set_payload_works() {
         assert_ok!(Cps::set_payload(
             RuntimeOrigin::signed(account),
             NodeId(0),
-            payload.clone()
+            payload.clone(),
+            None
         ));
 
         let node = Cps::nodes(NodeId(0)).unwrap();
@@ -653,7 +699,7 @@ fn move_root_to_child_works() {
             None
         ));
 
-        assert_eq!(Cps::root_nodes().len(), 2);
+        assert_eq!(Cps::root_count(account), 2);
 
         // Move node 0 under node 1
         assert_ok!(Cps::move_node(
@@ -666,8 +712,8 @@ fn move_root_to_child_works() {
         assert_eq!(node.parent, Some(NodeId(1)));
 
         // Check root nodes updated
-        assert_eq!(Cps::root_nodes().len(), 1);
-        assert_eq!(Cps::root_nodes()[0], NodeId(1));
+        assert_eq!(Cps::root_count(account), 1);
+        assert_eq!(Cps::roots_of(&account), vec![NodeId(1)]);
     });
 }
 
@@ -697,6 +743,7 @@ fn clear_meta_and_payload_works() {
         assert_ok!(Cps::set_payload(
             RuntimeOrigin::signed(account),
             NodeId(0),
+            None,
             None
         ));
 
@@ -812,7 +859,7 @@ fn delete_root_node_works() {
             None
         ));
 
-        assert_eq!(Cps::root_nodes().len(), 1);
+        assert_eq!(Cps::root_count(account), 1);
 
         // Delete root node
         assert_ok!(Cps::delete_node(RuntimeOrigin::signed(account), NodeId(0)));
@@ -821,7 +868,8 @@ fn delete_root_node_works() {
         assert!(Cps::nodes(NodeId(0)).is_none());
 
         // Verify root nodes index is updated
-        assert_eq!(Cps::root_nodes().len(), 0);
+        assert_eq!(Cps::root_count(account), 0);
+        assert!(Cps::roots_of(&account).is_empty());
     });
 }
 
@@ -899,9 +947,8 @@ fn debug_formatting_works() {
     });
 }
 
-#[test]
-#[allow(unnameable_test_items)]
-fn on_payload_set_callback_invoked() {
+mod payload_callback {
+    use super::*;
     use std::cell::RefCell;
 
     // Thread-local storage to track callback invocations
@@ -920,15 +967,17 @@ fn on_payload_set_callback_invoked() {
         }
     }
 
-    // Create a separate test runtime with our callback handler.
+    // Separate test runtime with our callback handler.
     // We need a distinct runtime instance because the global `Runtime` at the top
     // of this file is configured with `OnPayloadSet = ()` (no-op), and we can't
-    // modify it for this single test without affecting other tests.
+    // modify it for these tests without affecting other tests.
     type TestBlock = frame_system::mocking::MockBlock<TestRuntime>;
 
     frame_support::construct_runtime!(
         pub enum TestRuntime {
             System: frame_system,
+            Balances: pallet_balances,
+            Timestamp: pallet_timestamp,
             Cps: pallet_cps,
         }
     );
@@ -936,81 +985,141 @@ fn on_payload_set_callback_invoked() {
     #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
     impl frame_system::Config for TestRuntime {
         type Block = TestBlock;
-        type AccountData = ();
+        type AccountData = pallet_balances::AccountData<Balance>;
+    }
+
+    #[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+    impl pallet_balances::Config for TestRuntime {
+        type AccountStore = System;
+    }
+
+    impl pallet_timestamp::Config for TestRuntime {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = ();
+        type WeightInfo = ();
     }
 
     impl pallet_cps::Config for TestRuntime {
         type RuntimeEvent = RuntimeEvent;
         type OnPayloadSet = TestPayloadHandler;
+        type RuntimeHoldReason = RuntimeHoldReason;
+        type Currency = Balances;
+        type NodeDepositBase = frame_support::traits::ConstU64<0>;
+        type ByteDeposit = frame_support::traits::ConstU64<0>;
+        type Time = Timestamp;
         type WeightInfo = weights::TestWeightInfo;
     }
 
-    let mut ext = {
+    fn new_test_ext() -> sp_io::TestExternalities {
         let t = frame_system::GenesisConfig::<TestRuntime>::default()
             .build_storage()
             .unwrap();
-        sp_io::TestExternalities::new(t)
-    };
+        let mut ext = sp_io::TestExternalities::new(t);
+        ext.execute_with(|| {
+            System::set_block_number(1);
+            // Reset callback tracker
+            CALLBACK_INVOKED.with(|cell| *cell.borrow_mut() = None);
+        });
+        ext
+    }
 
-    ext.execute_with(|| {
-        System::set_block_number(1);
-        let account = 1u64;
+    fn last_invocation() -> Option<(NodeId, Option<NodeData>, Option<NodeData>)> {
+        CALLBACK_INVOKED.with(|cell| cell.borrow().clone())
+    }
 
-        // Reset callback tracker
-        CALLBACK_INVOKED.with(|cell| *cell.borrow_mut() = None);
+    #[test]
+    fn on_payload_set_callback_invoked() {
+        new_test_ext().execute_with(|| {
+            let account = 1u64;
 
-        // Create a node with initial metadata
-        let meta = Some(BoundedVec::try_from(vec![1, 2, 3]).unwrap());
-        assert_ok!(Cps::create_node(
-            RuntimeOrigin::signed(account),
-            None,
-            meta.clone(),
-            None
-        ));
+            // Create a node with initial metadata
+            let meta = Some(BoundedVec::try_from(vec![1, 2, 3]).unwrap());
+            assert_ok!(Cps::create_node(
+                RuntimeOrigin::signed(account),
+                None,
+                meta.clone(),
+                None
+            ));
 
-        // Reset callback tracker (create_node doesn't trigger the callback)
-        CALLBACK_INVOKED.with(|cell| *cell.borrow_mut() = None);
+            // Reset callback tracker (create_node doesn't trigger the callback)
+            CALLBACK_INVOKED.with(|cell| *cell.borrow_mut() = None);
 
-        // Set payload - this should trigger the callback
-        let payload = Some(BoundedVec::try_from(vec![4, 5, 6]).unwrap());
-        assert_ok!(Cps::set_payload(
-            RuntimeOrigin::signed(account),
-            NodeId(0),
-            payload.clone()
-        ));
+            // Set payload - this should trigger the callback
+            let payload = Some(BoundedVec::try_from(vec![4, 5, 6]).unwrap());
+            assert_ok!(Cps::set_payload(
+                RuntimeOrigin::signed(account),
+                NodeId(0),
+                payload.clone(),
+                None
+            ));
 
-        // Verify callback was invoked with correct parameters
-        CALLBACK_INVOKED.with(|cell| {
-            let invocation = cell.borrow();
+            // Verify callback was invoked with correct parameters
+            let invocation = last_invocation();
             assert!(invocation.is_some(), "Callback was not invoked");
 
-            let (node_id, cb_meta, cb_payload) = invocation.as_ref().unwrap();
-            assert_eq!(*node_id, NodeId(0), "Callback received wrong node_id");
-            assert_eq!(*cb_meta, meta, "Callback received wrong metadata");
-            assert_eq!(*cb_payload, payload, "Callback received wrong payload");
-        });
+            let (node_id, cb_meta, cb_payload) = invocation.unwrap();
+            assert_eq!(node_id, NodeId(0), "Callback received wrong node_id");
+            assert_eq!(cb_meta, meta, "Callback received wrong metadata");
+            assert_eq!(cb_payload, payload, "Callback received wrong payload");
 
-        // Test clearing payload
-        CALLBACK_INVOKED.with(|cell| *cell.borrow_mut() = None);
+            // Test clearing payload
+            CALLBACK_INVOKED.with(|cell| *cell.borrow_mut() = None);
 
-        assert_ok!(Cps::set_payload(
-            RuntimeOrigin::signed(account),
-            NodeId(0),
-            None
-        ));
+            assert_ok!(Cps::set_payload(
+                RuntimeOrigin::signed(account),
+                NodeId(0),
+                None,
+                None
+            ));
 
-        // Verify callback was invoked with None payload
-        CALLBACK_INVOKED.with(|cell| {
-            let invocation = cell.borrow();
+            // Verify callback was invoked with None payload
+            let invocation = last_invocation();
             assert!(invocation.is_some(), "Callback was not invoked for clear");
 
-            let (_node_id, _cb_meta, cb_payload) = invocation.as_ref().unwrap();
+            let (_node_id, _cb_meta, cb_payload) = invocation.unwrap();
             assert_eq!(
-                *cb_payload, None,
+                cb_payload, None,
                 "Callback should receive None when payload is cleared"
             );
         });
-    });
+    }
+
+    #[test]
+    fn on_payload_set_callback_invoked_on_expiry() {
+        new_test_ext().execute_with(|| {
+            let account = 1u64;
+            let meta = Some(BoundedVec::try_from(vec![1, 2, 3]).unwrap());
+            let payload = Some(BoundedVec::try_from(vec![4, 5, 6]).unwrap());
+            assert_ok!(Cps::create_node(
+                RuntimeOrigin::signed(account),
+                None,
+                meta.clone(),
+                None
+            ));
+            assert_ok!(Cps::set_payload(
+                RuntimeOrigin::signed(account),
+                NodeId(0),
+                payload,
+                Some(2)
+            ));
+            CALLBACK_INVOKED.with(|cell| *cell.borrow_mut() = None);
+
+            // Not expired yet
+            System::set_block_number(2);
+            Cps::on_idle(2, Weight::MAX);
+            assert_eq!(last_invocation(), None);
+
+            // Expired payload is reported as cleared
+            System::set_block_number(3);
+            Cps::on_idle(3, Weight::MAX);
+            assert_eq!(
+                last_invocation(),
+                Some((NodeId(0), meta, None)),
+                "Callback should receive None when payload expires"
+            );
+        });
+    }
 }
 
 // ===== Proxy Integration Tests =====
@@ -1046,6 +1155,7 @@ fn proxy_can_update_cps_node_payload() {
             Box::new(RuntimeCall::Cps(pallet_cps::Call::set_payload {
                 node_id: NodeId(0),
                 payload: new_payload.clone(),
+                ttl: None,
             }))
         ));
 
@@ -1339,6 +1449,7 @@ fn proxy_type_any_allows_all_operations() {
             Box::new(RuntimeCall::Cps(pallet_cps::Call::set_payload {
                 node_id: NodeId(0),
                 payload: b"data".to_vec().try_into().ok(),
+                ttl: None,
             }))
         ));
 
@@ -1390,6 +1501,7 @@ fn proxy_ownership_validation_works() {
                 Box::new(RuntimeCall::Cps(pallet_cps::Call::set_payload {
                     node_id: NodeId(0),
                     payload: b"hacked".to_vec().try_into().ok(),
+                    ttl: None,
                 }))
             ),
             pallet_proxy::Error::<Runtime>::NotProxy
@@ -1404,6 +1516,7 @@ fn proxy_type_filter_works_correctly() {
         let cps_call = RuntimeCall::Cps(pallet_cps::Call::set_payload {
             node_id: NodeId(0),
             payload: None,
+            ttl: None,
         });
         assert!(
             ProxyType::CpsWrite(None).filter(&cps_call),
@@ -1469,6 +1582,7 @@ fn proxy_with_node_restriction_works() {
             Box::new(RuntimeCall::Cps(pallet_cps::Call::set_payload {
                 node_id: NodeId(0),
                 payload: b"updated_0".to_vec().try_into().ok(),
+                ttl: None,
             }))
         ));
 
@@ -1491,10 +1605,12 @@ fn proxy_node_restriction_filter_test() {
         let call_node_0 = RuntimeCall::Cps(pallet_cps::Call::set_payload {
             node_id: NodeId(0),
             payload: None,
+            ttl: None,
         });
         let call_node_1 = RuntimeCall::Cps(pallet_cps::Call::set_payload {
             node_id: NodeId(1),
             payload: None,
+            ttl: None,
         });
         assert!(
             unrestricted.filter(&call_node_0),
@@ -1577,7 +1693,7 @@ fn move_node_within_subtree_limit_works() {
 }
 
 #[test]
-fn move_node_exceeding_subtree_limit_fails() {
+fn move_node_exceeding_subtree_limit_deeper_works() {
     new_test_ext().execute_with(|| {
         let account = 1u64;
 
@@ -1608,11 +1724,14 @@ fn move_node_exceeding_subtree_limit_fails() {
             None
         )); // Node 52 (new parent)
 
-        // Attempt to move node 0 (with 51 descendants) under node 52 - should fail
-        assert_noop!(
-            Cps::move_node(RuntimeOrigin::signed(account), NodeId(0), NodeId(52)),
-            Error::<Runtime>::SubtreeTooLarge
-        );
+        // Move node 0 (with 51 descendants) deeper under node 52 - paths are updated on idle
+        assert_ok!(Cps::move_node(
+            RuntimeOrigin::signed(account),
+            NodeId(0),
+            NodeId(52)
+        ));
+        assert_eq!(Cps::move_queue().to_vec(), vec![NodeId(0)]);
+        assert_eq!(Cps::subtree_height(NodeId(52)), 2);
     });
 }
 
@@ -1661,7 +1780,7 @@ fn move_node_at_exact_subtree_limit_works() {
 }
 
 #[test]
-fn move_node_nested_subtree_exceeding_limit_fails() {
+fn move_node_nested_subtree_exceeding_limit_deeper_works() {
     new_test_ext().execute_with(|| {
         let account = 1u64;
 
@@ -1707,10 +1826,1200 @@ fn move_node_nested_subtree_exceeding_limit_fails() {
         // Get the ID of the new parent (should be after 1 + 10 + 50 = 61 nodes)
         let new_parent_id = NodeId(61);
 
-        // Attempt to move node 0 (with 60 descendants) under new parent - should fail
+        // Move node 0 (with 60 descendants) deeper under new parent - paths are updated on idle
+        assert_ok!(Cps::move_node(
+            RuntimeOrigin::signed(account),
+            NodeId(0),
+            new_parent_id
+        ));
+        assert_eq!(Cps::move_queue().to_vec(), vec![NodeId(0)]);
+
+        Cps::on_idle(1, Weight::MAX);
+        Cps::on_idle(2, Weight::MAX);
+        System::assert_last_event(Event::SubtreeMoved(NodeId(0)).into());
+        let grandchild = Cps::nodes(NodeId(2)).unwrap();
+        assert_eq!(
+            grandchild.path.to_vec(),
+            vec![new_parent_id, NodeId(0), NodeId(1)]
+        );
+    });
+}
+
+#[test]
+fn move_large_subtree_updates_paths_across_blocks() {
+    new_test_ext().execute_with(|| {
+        let owner = 1u64;
+        let sensor = 2u64;
+        let create = |parent: Option<u64>| {
+            assert_ok!(Cps::create_node(
+                RuntimeOrigin::signed(owner),
+                parent.map(NodeId),
+                None,
+                None
+            ));
+        };
+
+        // Tree: 0 -> 1 -> (2..=11) -> 5 children each (12..=61), 62 -> 63
+        create(None);
+        create(Some(0));
+        for _ in 2..=11 {
+            create(Some(1));
+        }
+        for parent in 2..=11 {
+            for _ in 0..5 {
+                create(Some(parent));
+            }
+        }
+        create(None);
+        create(Some(62));
+
+        assert_ok!(Cps::move_node(
+            RuntimeOrigin::signed(owner),
+            NodeId(1),
+            NodeId(62)
+        ));
+        System::assert_last_event(
+            Event::NodeMoved(NodeId(1), Some(NodeId(0)), NodeId(62), owner).into(),
+        );
+        assert_eq!(Cps::move_queue().to_vec(), vec![NodeId(1)]);
+        assert_eq!(
+            Cps::nodes(NodeId(1)).unwrap().path.to_vec(),
+            vec![NodeId(62)]
+        );
+
+        // Descendant path is outdated but resolves to the actual ancestry
+        let node = Cps::nodes(NodeId(12)).unwrap();
+        assert_eq!(node.path.to_vec(), vec![NodeId(0), NodeId(1), NodeId(2)]);
+        assert_eq!(
+            Cps::resolved_path(&node).to_vec(),
+            vec![NodeId(62), NodeId(1), NodeId(2)]
+        );
+        assert_eq!(
+            Cps::live_node(NodeId(12)).unwrap().path.to_vec(),
+            vec![NodeId(62), NodeId(1), NodeId(2)]
+        );
+        assert!(Cps::subtree(NodeId(2), 1, 100)
+            .into_iter()
+            .all(|(_, node)| node.path.first() == Some(&NodeId(62))));
+
+        // Roles are inherited from the new ancestors
+        assert_ok!(Cps::grant_role(
+            RuntimeOrigin::signed(owner),
+            NodeId(62),
+            sensor,
+            Role::Writer
+        ));
+        assert_ok!(Cps::set_payload(
+            RuntimeOrigin::signed(sensor),
+            NodeId(12),
+            Some(BoundedVec::try_from(vec![1]).unwrap()),
+            None
+        ));
+
+        // New nodes get the actual path
+        create(Some(12));
+        assert_eq!(
+            Cps::nodes(NodeId(64)).unwrap().path.to_vec(),
+            vec![NodeId(62), NodeId(1), NodeId(2), NodeId(12)]
+        );
+
+        // Moving subtree is protected from conflicting changes
         assert_noop!(
-            Cps::move_node(RuntimeOrigin::signed(account), NodeId(0), new_parent_id),
-            Error::<Runtime>::SubtreeTooLarge
+            Cps::move_node(RuntimeOrigin::signed(owner), NodeId(2), NodeId(63)),
+            Error::<Runtime>::SubtreeMoving
+        );
+        assert_noop!(
+            Cps::move_node(RuntimeOrigin::signed(owner), NodeId(63), NodeId(5)),
+            Error::<Runtime>::SubtreeMoving
+        );
+        assert_noop!(
+            Cps::delete_node(RuntimeOrigin::signed(owner), NodeId(64)),
+            Error::<Runtime>::SubtreeMoving
+        );
+        assert_noop!(
+            Cps::delete_subtree(RuntimeOrigin::signed(owner), NodeId(1)),
+            Error::<Runtime>::SubtreeMoving
+        );
+
+        // First batch
+        Cps::on_idle(1, Weight::MAX);
+        assert!(matches!(
+            System::events().last().map(|r| r.event.clone()),
+            Some(RuntimeEvent::Cps(Event::SubtreeMoveProgress(NodeId(1), _)))
+        ));
+        assert_eq!(Cps::move_queue().to_vec(), vec![NodeId(1)]);
+        assert!(MovingRoots::<Runtime>::contains_key(NodeId(1)));
+
+        // Rest of subtree
+        Cps::on_idle(2, Weight::MAX);
+        System::assert_last_event(Event::SubtreeMoved(NodeId(1)).into());
+        assert!(Cps::move_queue().is_empty());
+        assert!(!MovingRoots::<Runtime>::contains_key(NodeId(1)));
+        assert!(Cps::path_update_cursor(NodeId(1)).is_empty());
+        for id in (2..=61).chain(64..=64) {
+            let node = Cps::nodes(NodeId(id)).unwrap();
+            assert_eq!(node.path, Cps::resolved_path(&node));
+            assert_eq!(node.path.first(), Some(&NodeId(62)));
+        }
+        assert_eq!(
+            Cps::nodes(NodeId(12)).unwrap().path.to_vec(),
+            vec![NodeId(62), NodeId(1), NodeId(2)]
+        );
+
+        // Subtree could be changed again
+        assert_ok!(Cps::move_node(
+            RuntimeOrigin::signed(owner),
+            NodeId(2),
+            NodeId(63)
+        ));
+        assert_eq!(
+            Cps::nodes(NodeId(64)).unwrap().path.to_vec(),
+            vec![NodeId(62), NodeId(63), NodeId(2), NodeId(12)]
+        );
+    });
+}
+
+#[test]
+fn move_node_keeps_subtree_within_max_depth() {
+    new_test_ext().execute_with(|| {
+        let owner = 1u64;
+        let create = |parent: Option<u64>| {
+            assert_ok!(Cps::create_node(
+                RuntimeOrigin::signed(owner),
+                parent.map(NodeId),
+                None,
+                None
+            ));
+        };
+
+        // Large subtree of height 1: 0 -> (1..=51)
+        create(None);
+        for _ in 1..=51 {
+            create(Some(0));
+        }
+        assert_eq!(Cps::subtree_height(NodeId(0)), 1);
+        assert_eq!(Cps::subtree_height(NodeId(1)), 0);
+
+        // Chain 52 -> 53 -> ... -> 83 with the lowest node at MAX_TREE_DEPTH - 1
+        create(None);
+        for parent in 52..83 {
+            create(Some(parent));
+        }
+        assert_eq!(
+            Cps::nodes(NodeId(83)).unwrap().path.len(),
+            MAX_TREE_DEPTH as usize - 1
+        );
+        assert_eq!(Cps::subtree_height(NodeId(52)), MAX_TREE_DEPTH - 1);
+
+        // Children of node 0 wouldn't fit under the lowest chain node
+        assert_noop!(
+            Cps::move_node(RuntimeOrigin::signed(owner), NodeId(0), NodeId(83)),
+            Error::<Runtime>::MaxDepthExceeded
+        );
+
+        // One level up the whole subtree fits
+        assert_ok!(Cps::move_node(
+            RuntimeOrigin::signed(owner),
+            NodeId(0),
+            NodeId(82)
+        ));
+        assert_eq!(Cps::subtree_height(NodeId(82)), 2);
+        assert_eq!(Cps::subtree_height(NodeId(52)), MAX_TREE_DEPTH);
+
+        Cps::on_idle(1, Weight::MAX);
+        Cps::on_idle(2, Weight::MAX);
+        System::assert_last_event(Event::SubtreeMoved(NodeId(0)).into());
+        assert_eq!(
+            Cps::nodes(NodeId(1)).unwrap().path.len(),
+            MAX_TREE_DEPTH as usize
+        );
+
+        // Chain couldn't get under the moved subtree anymore
+        assert_noop!(
+            Cps::move_node(RuntimeOrigin::signed(owner), NodeId(83), NodeId(1)),
+            Error::<Runtime>::MaxDepthExceeded
+        );
+    });
+}
+
+#[test]
+fn node_deposit_follows_data_size() {
+    new_test_ext().execute_with(|| {
+        NodeDepositBase::set(100);
+        ByteDeposit::set(10);
+        let account = 1u64;
+        let reason: RuntimeHoldReason = HoldReason::CpsNode.into();
+        let held = || Balances::balance_on_hold(&reason, &account);
+
+        // Base deposit plus 3 bytes of metadata
+        assert_ok!(Cps::create_node(
+            RuntimeOrigin::signed(account),
+            None,
+            Some(BoundedVec::try_from(vec![1, 2, 3]).unwrap()),
+            None
+        ));
+        assert_eq!(Cps::node_deposit(NodeId(0)), 130);
+        assert_eq!(held(), 130);
+
+        // Payload grows deposit
+        assert_ok!(Cps::set_payload(
+            RuntimeOrigin::signed(account),
+            NodeId(0),
+            Some(BoundedVec::try_from(vec![0; 10]).unwrap()),
+            None
+        ));
+        assert_eq!(Cps::node_deposit(NodeId(0)), 230);
+        assert_eq!(held(), 230);
+
+        // Metadata cleared shrinks deposit
+        assert_ok!(Cps::set_meta(
+            RuntimeOrigin::signed(account),
+            NodeId(0),
+            None
+        ));
+        assert_eq!(Cps::node_deposit(NodeId(0)), 200);
+        assert_eq!(held(), 200);
+
+        // Deletion releases whole deposit
+        assert_ok!(Cps::delete_node(RuntimeOrigin::signed(account), NodeId(0)));
+        assert_eq!(Cps::node_deposit(NodeId(0)), 0);
+        assert_eq!(held(), 0);
+        assert_eq!(Balances::free_balance(account), 10000);
+    });
+}
+
+#[test]
+fn node_deposit_requires_funds() {
+    new_test_ext().execute_with(|| {
+        NodeDepositBase::set(20000);
+        ByteDeposit::set(10);
+        let account = 1u64;
+
+        assert_noop!(
+            Cps::create_node(RuntimeOrigin::signed(account), None, None, None),
+            TokenError::FundsUnavailable
+        );
+
+        NodeDepositBase::set(100);
+        assert_ok!(Cps::create_node(
+            RuntimeOrigin::signed(account),
+            None,
+            None,
+            None
+        ));
+        // 2048 bytes of payload costs more than the account has
+        assert_noop!(
+            Cps::set_payload(
+                RuntimeOrigin::signed(account),
+                NodeId(0),
+                Some(BoundedVec::try_from(vec![0; 2048]).unwrap()),
+                None
+            ),
+            TokenError::FundsUnavailable
+        );
+        assert_eq!(Cps::nodes(NodeId(0)).unwrap().payload, None);
+        assert_eq!(Cps::node_deposit(NodeId(0)), 100);
+    });
+}
+
+#[test]
+fn roles_are_inherited_by_subtree() {
+    new_test_ext().execute_with(|| {
+        let owner = 1u64;
+        let sensor = 2u64;
+        let data = || Some(BoundedVec::try_from(vec![1, 2, 3]).unwrap());
+
+        // Tree: 0 -> 1 -> 2
+        for parent in [None, Some(NodeId(0)), Some(NodeId(1))] {
+            assert_ok!(Cps::create_node(
+                RuntimeOrigin::signed(owner),
+                parent,
+                None,
+                None
+            ));
+        }
+
+        assert_noop!(
+            Cps::set_payload(RuntimeOrigin::signed(sensor), NodeId(2), data(), None),
+            Error::<Runtime>::NotNodeOwner
+        );
+
+        assert_ok!(Cps::grant_role(
+            RuntimeOrigin::signed(owner),
+            NodeId(1),
+            sensor,
+            Role::Writer
+        ));
+        System::assert_last_event(Event::RoleGranted(NodeId(1), sensor, Role::Writer).into());
+
+        // Writer role works on the node and its descendants only
+        assert_ok!(Cps::set_payload(
+            RuntimeOrigin::signed(sensor),
+            NodeId(1),
+            data(),
+            None
+        ));
+        assert_ok!(Cps::set_payload(
+            RuntimeOrigin::signed(sensor),
+            NodeId(2),
+            data(),
+            None
+        ));
+        assert_noop!(
+            Cps::set_payload(RuntimeOrigin::signed(sensor), NodeId(0), data(), None),
+            Error::<Runtime>::NotNodeOwner
+        );
+
+        // Writer isn't meta editor nor child creator
+        assert_noop!(
+            Cps::set_meta(RuntimeOrigin::signed(sensor), NodeId(2), data()),
+            Error::<Runtime>::NotNodeOwner
         );
+        assert_noop!(
+            Cps::create_node(RuntimeOrigin::signed(sensor), Some(NodeId(2)), None, None),
+            Error::<Runtime>::OwnerMismatch
+        );
+
+        assert_ok!(Cps::revoke_role(
+            RuntimeOrigin::signed(owner),
+            NodeId(1),
+            sensor,
+            Role::Writer
+        ));
+        assert_noop!(
+            Cps::set_payload(RuntimeOrigin::signed(sensor), NodeId(2), data(), None),
+            Error::<Runtime>::NotNodeOwner
+        );
+        assert_noop!(
+            Cps::revoke_role(
+                RuntimeOrigin::signed(owner),
+                NodeId(1),
+                sensor,
+                Role::Writer
+            ),
+            Error::<Runtime>::RoleNotFound
+        );
+    });
+}
+
+#[test]
+fn meta_editor_and_child_creator_roles_work() {
+    new_test_ext().execute_with(|| {
+        NodeDepositBase::set(100);
+        let owner = 1u64;
+        let gateway = 2u64;
+        let meta = Some(BoundedVec::try_from(vec![1, 2, 3]).unwrap());
+
+        assert_ok!(Cps::create_node(
+            RuntimeOrigin::signed(owner),
+            None,
+            None,
+            None
+        ));
+        assert_ok!(Cps::grant_role(
+            RuntimeOrigin::signed(owner),
+            NodeId(0),
+            gateway,
+            Role::MetaEditor
+        ));
+        assert_ok!(Cps::grant_role(
+            RuntimeOrigin::signed(owner),
+            NodeId(0),
+            gateway,
+            Role::ChildCreator
+        ));
+
+        // Owner opts in to pay deposits of nodes created by gateway
+        assert_noop!(
+            Cps::create_node(RuntimeOrigin::signed(gateway), Some(NodeId(0)), None, None),
+            Error::<Runtime>::DepositAllowanceExceeded
+        );
+        assert_ok!(Cps::set_deposit_allowance(
+            RuntimeOrigin::signed(owner),
+            gateway,
+            100
+        ));
+        System::assert_last_event(Event::DepositAllowanceSet(owner, gateway, 100).into());
+
+        // Child created by gateway belongs to the parent owner who pays the deposit
+        assert_ok!(Cps::create_node(
+            RuntimeOrigin::signed(gateway),
+            Some(NodeId(0)),
+            None,
+            None
+        ));
+        assert_eq!(Cps::nodes(NodeId(1)).unwrap().owner, owner);
+        assert_eq!(Balances::free_balance(owner), 10000 - 200);
+        assert_eq!(Balances::free_balance(gateway), 10000);
+        assert_eq!(Cps::deposit_allowance(owner, gateway), 0);
+
+        assert_ok!(Cps::set_meta(
+            RuntimeOrigin::signed(gateway),
+            NodeId(1),
+            meta.clone()
+        ));
+        assert_eq!(Cps::nodes(NodeId(1)).unwrap().meta, meta);
+
+        // Only owner manages roles, deletes and moves nodes
+        assert_noop!(
+            Cps::grant_role(RuntimeOrigin::signed(gateway), NodeId(1), 3, Role::Writer),
+            Error::<Runtime>::NotNodeOwner
+        );
+        assert_noop!(
+            Cps::delete_node(RuntimeOrigin::signed(gateway), NodeId(1)),
+            Error::<Runtime>::NotNodeOwner
+        );
+
+        assert_ok!(Cps::delete_node(RuntimeOrigin::signed(owner), NodeId(1)));
+        assert_ok!(Cps::delete_node(RuntimeOrigin::signed(owner), NodeId(0)));
+        assert!(Cps::node_roles(NodeId(0)).is_empty());
+    });
+}
+
+#[test]
+fn grantee_deposit_growth_limited_by_allowance() {
+    new_test_ext().execute_with(|| {
+        ByteDeposit::set(1);
+        let owner = 1u64;
+        let sensor = 2u64;
+        let reason: RuntimeHoldReason = HoldReason::CpsNode.into();
+        let data = |len: usize| Some(BoundedVec::try_from(vec![1; len]).unwrap());
+
+        assert_ok!(Cps::create_node(
+            RuntimeOrigin::signed(owner),
+            None,
+            None,
+            None
+        ));
+        assert_ok!(Cps::grant_role(
+            RuntimeOrigin::signed(owner),
+            NodeId(0),
+            sensor,
+            Role::Writer
+        ));
+
+        // Writer couldn't grow owner deposit without allowance
+        assert_noop!(
+            Cps::set_payload(RuntimeOrigin::signed(sensor), NodeId(0), data(4), None),
+            Error::<Runtime>::DepositAllowanceExceeded
+        );
+
+        assert_ok!(Cps::set_deposit_allowance(
+            RuntimeOrigin::signed(owner),
+            sensor,
+            6
+        ));
+        assert_ok!(Cps::set_payload(
+            RuntimeOrigin::signed(sensor),
+            NodeId(0),
+            data(4),
+            None
+        ));
+        assert_eq!(Cps::deposit_allowance(owner, sensor), 2);
+        assert_eq!(Balances::balance_on_hold(&reason, &owner), 4);
+
+        // Same size payload doesn't spend allowance, larger one is capped
+        assert_ok!(Cps::set_payload(
+            RuntimeOrigin::signed(sensor),
+            NodeId(0),
+            data(4),
+            None
+        ));
+        assert_eq!(Cps::deposit_allowance(owner, sensor), 2);
+        assert_noop!(
+            Cps::set_payload(RuntimeOrigin::signed(sensor), NodeId(0), data(8), None),
+            Error::<Runtime>::DepositAllowanceExceeded
+        );
+
+        // Released deposit returns to allowance
+        assert_ok!(Cps::set_payload(
+            RuntimeOrigin::signed(sensor),
+            NodeId(0),
+            None,
+            None
+        ));
+        assert_eq!(Cps::deposit_allowance(owner, sensor), 6);
+        assert_eq!(Balances::balance_on_hold(&reason, &owner), 0);
+
+        // Owner updates don't touch allowance
+        assert_ok!(Cps::set_payload(
+            RuntimeOrigin::signed(owner),
+            NodeId(0),
+            data(8),
+            None
+        ));
+        assert_eq!(Cps::deposit_allowance(owner, sensor), 6);
+
+        assert_ok!(Cps::set_deposit_allowance(
+            RuntimeOrigin::signed(owner),
+            sensor,
+            0
+        ));
+        assert!(!DepositAllowance::<Runtime>::contains_key(owner, sensor));
+    });
+}
+
+#[test]
+fn too_many_roles_fails() {
+    new_test_ext().execute_with(|| {
+        let owner = 1u64;
+        assert_ok!(Cps::create_node(
+            RuntimeOrigin::signed(owner),
+            None,
+            None,
+            None
+        ));
+        for who in 0..MAX_ROLES_PER_NODE as u64 {
+            assert_ok!(Cps::grant_role(
+                RuntimeOrigin::signed(owner),
+                NodeId(0),
+                100 + who,
+                Role::Writer
+            ));
+        }
+        // Granting the same role again is a no-op
+        assert_ok!(Cps::grant_role(
+            RuntimeOrigin::signed(owner),
+            NodeId(0),
+            100,
+            Role::Writer
+        ));
+        assert_noop!(
+            Cps::grant_role(
+                RuntimeOrigin::signed(owner),
+                NodeId(0),
+                100,
+                Role::MetaEditor
+            ),
+            Error::<Runtime>::TooManyRoles
+        );
+    });
+}
+
+#[test]
+fn transfer_ownership_of_subtree() {
+    new_test_ext().execute_with(|| {
+        NodeDepositBase::set(100);
+        let owner = 1u64;
+        let buyer = 2u64;
+        let sensor = 3u64;
+        let reason: RuntimeHoldReason = HoldReason::CpsNode.into();
+
+        // Tree: 0 -> 1 -> 2
+        for parent in [None, Some(NodeId(0)), Some(NodeId(1))] {
+            assert_ok!(Cps::create_node(
+                RuntimeOrigin::signed(owner),
+                parent,
+                None,
+                None
+            ));
+        }
+        assert_ok!(Cps::grant_role(
+            RuntimeOrigin::signed(owner),
+            NodeId(2),
+            sensor,
+            Role::Writer
+        ));
+
+        assert_noop!(
+            Cps::offer_transfer(RuntimeOrigin::signed(buyer), NodeId(1), buyer),
+            Error::<Runtime>::NotNodeOwner
+        );
+        assert_noop!(
+            Cps::accept_transfer(RuntimeOrigin::signed(buyer), NodeId(1)),
+            Error::<Runtime>::NoTransferOffer
+        );
+
+        assert_ok!(Cps::offer_transfer(
+            RuntimeOrigin::signed(owner),
+            NodeId(1),
+            buyer
+        ));
+        System::assert_last_event(Event::TransferOffered(NodeId(1), owner, buyer).into());
+        assert_noop!(
+            Cps::accept_transfer(RuntimeOrigin::signed(sensor), NodeId(1)),
+            Error::<Runtime>::NoTransferOffer
+        );
+
+        assert_ok!(Cps::accept_transfer(
+            RuntimeOrigin::signed(buyer),
+            NodeId(1)
+        ));
+        System::assert_last_event(Event::OwnershipTransferred(NodeId(1), owner, buyer).into());
+        assert_eq!(Cps::pending_transfer(NodeId(1)), None);
+
+        // Subtree is owned by buyer and detached as a new root
+        let node = Cps::nodes(NodeId(1)).unwrap();
+        assert_eq!(node.owner, buyer);
+        assert_eq!(node.parent, None);
+        assert!(node.path.is_empty());
+        let child = Cps::nodes(NodeId(2)).unwrap();
+        assert_eq!(child.owner, buyer);
+        assert_eq!(child.path.to_vec(), vec![NodeId(1)]);
+        assert!(Cps::nodes_by_parent(NodeId(0)).is_empty());
+        assert_eq!(Cps::roots_of(&owner), vec![NodeId(0)]);
+        assert_eq!(Cps::roots_of(&buyer), vec![NodeId(1)]);
+        assert_eq!(Cps::nodes(NodeId(0)).unwrap().owner, owner);
+
+        // Deposits moved to the new owner, roles are cleared
+        assert_eq!(Balances::balance_on_hold(&reason, &owner), 100);
+        assert_eq!(Balances::balance_on_hold(&reason, &buyer), 200);
+        assert!(Cps::node_roles(NodeId(2)).is_empty());
+        assert_noop!(
+            Cps::set_payload(RuntimeOrigin::signed(sensor), NodeId(2), None, None),
+            Error::<Runtime>::NotNodeOwner
+        );
+        assert_ok!(Cps::set_payload(
+            RuntimeOrigin::signed(buyer),
+            NodeId(2),
+            None,
+            None
+        ));
+    });
+}
+
+#[test]
+fn transfer_offer_could_be_cancelled() {
+    new_test_ext().execute_with(|| {
+        let owner = 1u64;
+        let buyer = 2u64;
+        assert_ok!(Cps::create_node(
+            RuntimeOrigin::signed(owner),
+            None,
+            None,
+            None
+        ));
+        assert_ok!(Cps::offer_transfer(
+            RuntimeOrigin::signed(owner),
+            NodeId(0),
+            buyer
+        ));
+        assert_ok!(Cps::offer_transfer(
+            RuntimeOrigin::signed(owner),
+            NodeId(0),
+            owner
+        ));
+        assert_noop!(
+            Cps::accept_transfer(RuntimeOrigin::signed(buyer), NodeId(0)),
+            Error::<Runtime>::NoTransferOffer
+        );
+        assert_eq!(Cps::nodes(NodeId(0)).unwrap().owner, owner);
+    });
+}
+
+#[test]
+fn transfer_of_subtree_pending_deletion_fails() {
+    new_test_ext().execute_with(|| {
+        let owner = 1u64;
+        let buyer = 2u64;
+
+        // Tree: 0 -> 1 -> 2
+        for parent in [None, Some(NodeId(0)), Some(NodeId(1))] {
+            assert_ok!(Cps::create_node(
+                RuntimeOrigin::signed(owner),
+                parent,
+                None,
+                None
+            ));
+        }
+        assert_ok!(Cps::offer_transfer(
+            RuntimeOrigin::signed(owner),
+            NodeId(0),
+            buyer
+        ));
+        assert_ok!(Cps::delete_subtree(RuntimeOrigin::signed(owner), NodeId(2)));
+
+        // Descendant deletion would release deposits from the new owner
+        assert_noop!(
+            Cps::accept_transfer(RuntimeOrigin::signed(buyer), NodeId(0)),
+            Error::<Runtime>::NodePendingDeletion
+        );
+        assert_eq!(Cps::nodes(NodeId(0)).unwrap().owner, owner);
+        assert_eq!(Cps::pending_transfer(NodeId(0)), Some(buyer));
+
+        Cps::on_idle(1, Weight::MAX);
+        assert_ok!(Cps::accept_transfer(
+            RuntimeOrigin::signed(buyer),
+            NodeId(0)
+        ));
+        assert_eq!(Cps::nodes(NodeId(1)).unwrap().owner, buyer);
+        assert_eq!(Cps::nodes(NodeId(2)), None);
+    });
+}
+
+#[test]
+fn delete_subtree_works_across_blocks() {
+    new_test_ext().execute_with(|| {
+        NodeDepositBase::set(10);
+        let owner = 1u64;
+        let reason: RuntimeHoldReason = HoldReason::CpsNode.into();
+
+        // Tree: 0 -> 1 -> (2..=30), 0 -> (31..=60) and separate root 61
+        assert_ok!(Cps::create_node(
+            RuntimeOrigin::signed(owner),
+            None,
+            None,
+            None
+        ));
+        assert_ok!(Cps::create_node(
+            RuntimeOrigin::signed(owner),
+            Some(NodeId(0)),
+            None,
+            None
+        ));
+        for _ in 2..=30 {
+            assert_ok!(Cps::create_node(
+                RuntimeOrigin::signed(owner),
+                Some(NodeId(1)),
+                None,
+                None
+            ));
+        }
+        for _ in 31..=60 {
+            assert_ok!(Cps::create_node(
+                RuntimeOrigin::signed(owner),
+                Some(NodeId(0)),
+                None,
+                None
+            ));
+        }
+        assert_ok!(Cps::create_node(
+            RuntimeOrigin::signed(owner),
+            None,
+            None,
+            None
+        ));
+        assert_eq!(Balances::balance_on_hold(&reason, &owner), 620);
+
+        assert_noop!(
+            Cps::delete_subtree(RuntimeOrigin::signed(2), NodeId(0)),
+            Error::<Runtime>::NotNodeOwner
+        );
+        assert_ok!(Cps::delete_subtree(RuntimeOrigin::signed(owner), NodeId(0)));
+        System::assert_last_event(Event::SubtreeDeletionScheduled(NodeId(0), owner).into());
+        assert!(DeletionQueue::<Runtime>::contains_key(NodeId(0)));
+
+        // Subtree pending deletion couldn't grow or be scheduled twice
+        assert_noop!(
+            Cps::create_node(RuntimeOrigin::signed(owner), Some(NodeId(5)), None, None),
+            Error::<Runtime>::NodePendingDeletion
+        );
+        assert_noop!(
+            Cps::move_node(RuntimeOrigin::signed(owner), NodeId(61), NodeId(1)),
+            Error::<Runtime>::NodePendingDeletion
+        );
+        assert_noop!(
+            Cps::delete_subtree(RuntimeOrigin::signed(owner), NodeId(1)),
+            Error::<Runtime>::NodePendingDeletion
+        );
+
+        // First batch
+        Cps::on_idle(1, Weight::MAX);
+        System::assert_last_event(
+            Event::SubtreeDeletionProgress(NodeId(0), MAX_DELETION_BATCH).into(),
+        );
+        assert_eq!(Cps::nodes(NodeId(1)), None);
+        assert!(Cps::nodes(NodeId(0)).is_some());
+
+        // Rest of subtree
+        Cps::on_idle(2, Weight::MAX);
+        System::assert_last_event(Event::SubtreeDeleted(NodeId(0)).into());
+        assert_eq!(DeletionQueue::<Runtime>::iter_keys().count(), 0);
+        for id in 0..=60 {
+            assert_eq!(Cps::nodes(NodeId(id)), None);
+        }
+        assert_eq!(Cps::roots_of(&owner), vec![NodeId(61)]);
+        assert_eq!(Balances::balance_on_hold(&reason, &owner), 10);
+
+        // Nothing to do anymore
+        Cps::on_idle(3, Weight::MAX);
+        assert!(Cps::nodes(NodeId(61)).is_some());
+        assert_eq!(Cps::deletion_cursor(), None);
+    });
+}
+
+#[test]
+fn pending_deletions_take_turns() {
+    new_test_ext().execute_with(|| {
+        let owner = 1u64;
+        let other = 2u64;
+        let create = |account: u64, parent: Option<u64>| {
+            assert_ok!(Cps::create_node(
+                RuntimeOrigin::signed(account),
+                parent.map(NodeId),
+                None,
+                None
+            ));
+        };
+
+        // Subtree larger than deletion batch and small subtree of another account
+        create(owner, None);
+        for _ in 1..=60 {
+            create(owner, Some(0));
+        }
+        create(other, None);
+        create(other, Some(61));
+        assert_ok!(Cps::delete_subtree(RuntimeOrigin::signed(owner), NodeId(0)));
+        assert_ok!(Cps::delete_subtree(
+            RuntimeOrigin::signed(other),
+            NodeId(61)
+        ));
+
+        // Small subtree doesn't wait for the large one
+        Cps::on_idle(1, Weight::MAX);
+        Cps::on_idle(2, Weight::MAX);
+        assert_eq!(Cps::nodes(NodeId(61)), None);
+        assert_eq!(Cps::nodes(NodeId(62)), None);
+
+        Cps::on_idle(3, Weight::MAX);
+        for id in 0..=60 {
+            assert_eq!(Cps::nodes(NodeId(id)), None);
+        }
+        assert_eq!(DeletionQueue::<Runtime>::iter_keys().count(), 0);
+        Cps::on_idle(4, Weight::MAX);
+        assert_eq!(Cps::deletion_cursor(), None);
+
+        // Pending deletions are bounded only by nodes of the account
+        create(owner, None);
+        for _ in 64..=163 {
+            create(owner, Some(63));
+        }
+        create(owner, None);
+        for _ in 165..=184 {
+            create(owner, Some(164));
+        }
+        for id in (64..=163).chain(165..=184) {
+            assert_ok!(Cps::delete_subtree(
+                RuntimeOrigin::signed(owner),
+                NodeId(id)
+            ));
+        }
+        assert_eq!(DeletionQueue::<Runtime>::iter_keys().count(), 120);
+    });
+}
+
+#[test]
+fn owner_index_and_tree_queries_work() {
+    new_test_ext().execute_with(|| {
+        let owner = 1u64;
+        let buyer = 2u64;
+        let sorted = |mut nodes: Vec<NodeId>| {
+            nodes.sort();
+            nodes
+        };
+        let ids = |nodes: Vec<(NodeId, Node<u64>)>| {
+            nodes.into_iter().map(|(id, _)| id).collect::<Vec<_>>()
+        };
+
+        // Tree: 0 -> (1 -> 3, 2)
+        for parent in [None, Some(NodeId(0)), Some(NodeId(0)), Some(NodeId(1))] {
+            assert_ok!(Cps::create_node(
+                RuntimeOrigin::signed(owner),
+                parent,
+                None,
+                None
+            ));
+        }
+        assert_eq!(
+            sorted(Cps::nodes_of(&owner, None, 10)),
+            vec![NodeId(0), NodeId(1), NodeId(2), NodeId(3)]
+        );
+        assert!(Cps::nodes_of(&buyer, None, 10).is_empty());
+
+        // Owned nodes are paginated by cursor
+        let first = Cps::nodes_of(&owner, None, 3);
+        assert_eq!(first.len(), 3);
+        let rest = Cps::nodes_of(&owner, first.last().copied(), 3);
+        assert_eq!(rest.len(), 1);
+        assert!(!first.contains(&rest[0]));
+
+        // Subtree is breadth-first and bounded by depth and limit
+        assert_eq!(
+            ids(Cps::subtree(NodeId(0), MAX_TREE_DEPTH, 100)),
+            vec![NodeId(0), NodeId(1), NodeId(2), NodeId(3)]
+        );
+        assert_eq!(
+            ids(Cps::subtree(NodeId(0), 1, 100)),
+            vec![NodeId(0), NodeId(1), NodeId(2)]
+        );
+        assert_eq!(
+            ids(Cps::subtree(NodeId(0), MAX_TREE_DEPTH, 2)),
+            vec![NodeId(0), NodeId(1)]
+        );
+        assert!(Cps::subtree(NodeId(9), MAX_TREE_DEPTH, 100).is_empty());
+        assert_eq!(Cps::ancestors(NodeId(3)), vec![NodeId(0), NodeId(1)]);
+        assert!(Cps::ancestors(NodeId(9)).is_empty());
+
+        // Owner index follows transfer and deletion
+        assert_ok!(Cps::offer_transfer(
+            RuntimeOrigin::signed(owner),
+            NodeId(1),
+            buyer
+        ));
+        assert_ok!(Cps::accept_transfer(
+            RuntimeOrigin::signed(buyer),
+            NodeId(1)
+        ));
+        assert_eq!(
+            sorted(Cps::nodes_of(&owner, None, 10)),
+            vec![NodeId(0), NodeId(2)]
+        );
+        assert_eq!(
+            sorted(Cps::nodes_of(&buyer, None, 10)),
+            vec![NodeId(1), NodeId(3)]
+        );
+
+        assert_ok!(Cps::delete_node(RuntimeOrigin::signed(buyer), NodeId(3)));
+        assert_eq!(Cps::nodes_of(&buyer, None, 10), vec![NodeId(1)]);
+    });
+}
+
+#[test]
+fn payload_history_keeps_last_versions() {
+    new_test_ext().execute_with(|| {
+        ByteDeposit::set(1);
+        let owner = 1u64;
+        let payload = |byte: u8| Some(BoundedVec::try_from(vec![byte]).unwrap());
+        let record_size = PayloadRecordOf::<Runtime>::max_encoded_len() as u64;
+
+        assert_ok!(Cps::create_node(
+            RuntimeOrigin::signed(owner),
+            None,
+            None,
+            None
+        ));
+
+        // Only owner enables bounded history
+        assert_noop!(
+            Cps::set_payload_history(RuntimeOrigin::signed(2), NodeId(0), 2),
+            Error::<Runtime>::NotNodeOwner
+        );
+        assert_noop!(
+            Cps::set_payload_history(
+                RuntimeOrigin::signed(owner),
+                NodeId(0),
+                MAX_PAYLOAD_HISTORY + 1
+            ),
+            Error::<Runtime>::HistoryTooDeep
+        );
+        assert_ok!(Cps::set_payload_history(
+            RuntimeOrigin::signed(owner),
+            NodeId(0),
+            2
+        ));
+        System::assert_last_event(Event::PayloadHistorySet(NodeId(0), 2).into());
+        assert_eq!(Cps::payload_history_depth(NodeId(0)), 2);
+        assert_eq!(Cps::node_deposit(NodeId(0)), 2 * record_size);
+
+        // Every payload update gets next version
+        for (block, byte) in [(2, 1u8), (3, 2), (4, 3)] {
+            System::set_block_number(block);
+            Timestamp::set_timestamp(block * 1000);
+            assert_ok!(Cps::set_payload(
+                RuntimeOrigin::signed(owner),
+                NodeId(0),
+                payload(byte),
+                None
+            ));
+            System::assert_last_event(Event::PayloadSet(NodeId(0), owner, byte as u32).into());
+        }
+        assert_eq!(Cps::payload_version(NodeId(0)), 3);
+        assert_eq!(Cps::node_deposit(NodeId(0)), 2 * record_size + 1);
+
+        // Oldest version is overwritten in ring buffer
+        assert!(Cps::payload_at(NodeId(0), 1).is_none());
+        assert_eq!(
+            Cps::payload_at(NodeId(0), 2),
+            Some(PayloadRecord {
+                version: 2,
+                block: 3,
+                timestamp: 3000,
+                setter: owner,
+                payload: payload(2),
+            })
+        );
+        assert_eq!(Cps::payload_at(NodeId(0), 3).unwrap().payload, payload(3));
+        assert!(Cps::payload_at(NodeId(0), 4).is_none());
+
+        // Disabling history drops records and releases their deposit
+        assert_ok!(Cps::set_payload_history(
+            RuntimeOrigin::signed(owner),
+            NodeId(0),
+            0
+        ));
+        assert!(Cps::payload_at(NodeId(0), 3).is_none());
+        assert_eq!(Cps::node_deposit(NodeId(0)), 1);
+
+        // Versions keep growing without history
+        assert_ok!(Cps::set_payload(
+            RuntimeOrigin::signed(owner),
+            NodeId(0),
+            None,
+            None
+        ));
+        assert_eq!(Cps::payload_version(NodeId(0)), 4);
+    });
+}
+
+#[test]
+fn conditional_updates_detect_conflicts() {
+    new_test_ext().execute_with(|| {
+        let owner = 1u64;
+        let data = |byte: u8| Some(BoundedVec::try_from(vec![byte]).unwrap());
+
+        assert_ok!(Cps::create_node(
+            RuntimeOrigin::signed(owner),
+            None,
+            None,
+            None
+        ));
+        assert_eq!(Cps::payload_version(NodeId(0)), 0);
+        assert_eq!(Cps::meta_version(NodeId(0)), 0);
+
+        // Both writers read version 0, first one wins
+        assert_ok!(Cps::set_payload_if(
+            RuntimeOrigin::signed(owner),
+            NodeId(0),
+            0,
+            data(1),
+            None
+        ));
+        System::assert_last_event(Event::PayloadSet(NodeId(0), owner, 1).into());
+        assert_noop!(
+            Cps::set_payload_if(RuntimeOrigin::signed(owner), NodeId(0), 0, data(2), None),
+            Error::<Runtime>::VersionMismatch
+        );
+        assert_eq!(Cps::nodes(NodeId(0)).unwrap().payload, data(1));
+
+        // Unconditional update still bumps version
+        assert_ok!(Cps::set_payload(
+            RuntimeOrigin::signed(owner),
+            NodeId(0),
+            data(3),
+            None
+        ));
+        assert_ok!(Cps::set_payload_if(
+            RuntimeOrigin::signed(owner),
+            NodeId(0),
+            2,
+            data(4),
+            None
+        ));
+        assert_eq!(Cps::payload_version(NodeId(0)), 3);
+
+        // Metadata has its own version
+        assert_ok!(Cps::set_meta(
+            RuntimeOrigin::signed(owner),
+            NodeId(0),
+            data(5)
+        ));
+        System::assert_last_event(Event::MetaSet(NodeId(0), owner, 1).into());
+        assert_noop!(
+            Cps::set_meta_if(RuntimeOrigin::signed(owner), NodeId(0), 0, data(6)),
+            Error::<Runtime>::VersionMismatch
+        );
+        assert_ok!(Cps::set_meta_if(
+            RuntimeOrigin::signed(owner),
+            NodeId(0),
+            1,
+            data(6)
+        ));
+        assert_eq!(Cps::meta_version(NodeId(0)), 2);
+        assert_eq!(Cps::payload_version(NodeId(0)), 3);
+
+        // Version check doesn't bypass access control
+        assert_noop!(
+            Cps::set_payload_if(RuntimeOrigin::signed(2), NodeId(0), 3, data(7), None),
+            Error::<Runtime>::NotNodeOwner
+        );
+    });
+}
+
+#[test]
+fn payload_expires_after_ttl() {
+    new_test_ext().execute_with(|| {
+        ByteDeposit::set(1);
+        let owner = 1u64;
+        let data = |byte: u8| Some(BoundedVec::try_from(vec![byte; 4]).unwrap());
+
+        for _ in 0..2 {
+            assert_ok!(Cps::create_node(
+                RuntimeOrigin::signed(owner),
+                None,
+                None,
+                None
+            ));
+        }
+        assert_noop!(
+            Cps::set_payload(RuntimeOrigin::signed(owner), NodeId(0), data(1), Some(0)),
+            Error::<Runtime>::InvalidTtl
+        );
+
+        // Both payloads expire at block 4
+        assert_ok!(Cps::set_payload(
+            RuntimeOrigin::signed(owner),
+            NodeId(0),
+            data(1),
+            Some(3)
+        ));
+        assert_ok!(Cps::set_payload(
+            RuntimeOrigin::signed(owner),
+            NodeId(1),
+            data(2),
+            Some(3)
+        ));
+        assert_eq!(Cps::payload_expiry(NodeId(0)), Some(4));
+        assert_eq!(ExpiringPayloads::<Runtime>::get(4).len(), 2);
+
+        // Rewriting payload drops its expiry
+        assert_ok!(Cps::set_payload(
+            RuntimeOrigin::signed(owner),
+            NodeId(1),
+            data(3),
+            None
+        ));
+        assert_eq!(Cps::payload_expiry(NodeId(1)), None);
+        assert_eq!(
+            ExpiringPayloads::<Runtime>::get(4).into_inner(),
+            vec![NodeId(0)]
+        );
+
+        // Still valid before deadline
+        System::set_block_number(3);
+        Cps::on_idle(3, Weight::MAX);
+        assert_eq!(Cps::live_node(NodeId(0)).unwrap().payload, data(1));
+        assert_eq!(Cps::expiry_cursor(), Some(4));
+
+        // Expired payload is hidden on read before sweeper clears it
+        System::set_block_number(5);
+        assert!(Cps::is_payload_expired(NodeId(0)));
+        assert_eq!(Cps::live_node(NodeId(0)).unwrap().payload, None);
+        assert_eq!(Cps::nodes(NodeId(0)).unwrap().payload, data(1));
+        assert_eq!(Cps::node_deposit(NodeId(0)), 4);
+
+        // Sweeper catches up skipped blocks and releases payload deposit
+        Cps::on_idle(5, Weight::MAX);
+        System::assert_has_event(Event::PayloadExpired(NodeId(0), 2).into());
+        assert_eq!(Cps::nodes(NodeId(0)).unwrap().payload, None);
+        assert_eq!(Cps::payload_version(NodeId(0)), 2);
+        assert_eq!(Cps::node_deposit(NodeId(0)), 0);
+        assert_eq!(Cps::payload_expiry(NodeId(0)), None);
+        assert!(ExpiringPayloads::<Runtime>::get(4).is_empty());
+        assert_eq!(Cps::nodes(NodeId(1)).unwrap().payload, data(3));
+
+        // Sweeper sleeps while no payload waits for expiry
+        assert_eq!(Cps::expiry_cursor(), None);
+
+        // Deleted node leaves no expiry behind
+        assert_ok!(Cps::set_payload(
+            RuntimeOrigin::signed(owner),
+            NodeId(1),
+            data(4),
+            Some(1)
+        ));
+        assert_eq!(Cps::expiry_cursor(), Some(5));
+        assert_ok!(Cps::delete_node(RuntimeOrigin::signed(owner), NodeId(1)));
+        assert_eq!(Cps::payload_expiry(NodeId(1)), None);
+        assert!(ExpiringPayloads::<Runtime>::get(6).is_empty());
     });
 }
@@ -23,11 +23,18 @@ use frame_support::weights::Weight;
 ///
 /// Provides benchmark-derived weights for each extrinsic in the pallet.
 pub trait WeightInfo {
-    fn create_node() -> Weight;
-    fn set_meta() -> Weight;
-    fn set_payload() -> Weight;
+    fn create_node(d: u32) -> Weight;
+    fn set_meta(d: u32) -> Weight;
+    fn set_payload(d: u32) -> Weight;
     fn move_node() -> Weight;
     fn delete_node() -> Weight;
+    fn grant_role() -> Weight;
+    fn revoke_role() -> Weight;
+    fn offer_transfer() -> Weight;
+    fn accept_transfer() -> Weight;
+    fn delete_subtree() -> Weight;
+    fn set_payload_history() -> Weight;
+    fn set_deposit_allowance() -> Weight;
 }
 
 /// Test weight implementation that returns zero weight for all operations.
@@ -35,13 +42,13 @@ pub trait WeightInfo {
 /// Used in testing environments where actual weight calculations are not needed.
 pub struct TestWeightInfo;
 impl WeightInfo for TestWeightInfo {
-    fn create_node() -> Weight {
+    fn create_node(_d: u32) -> Weight {
         Weight::zero()
     }
-    fn set_meta() -> Weight {
+    fn set_meta(_d: u32) -> Weight {
         Weight::zero()
     }
-    fn set_payload() -> Weight {
+    fn set_payload(_d: u32) -> Weight {
         Weight::zero()
     }
     fn move_node() -> Weight {
@@ -50,4 +57,25 @@ impl WeightInfo for TestWeightInfo {
     fn delete_node() -> Weight {
         Weight::zero()
     }
+    fn grant_role() -> Weight {
+        Weight::zero()
+    }
+    fn revoke_role() -> Weight {
+        Weight::zero()
+    }
+    fn offer_transfer() -> Weight {
+        Weight::zero()
+    }
+    fn accept_transfer() -> Weight {
+        Weight::zero()
+    }
+    fn delete_subtree() -> Weight {
+        Weight::zero()
+    }
+    fn set_payload_history() -> Weight {
+        Weight::zero()
+    }
+    fn set_deposit_allowance() -> Weight {
+        Weight::zero()
+    }
 }
@@ -21,6 +21,7 @@
 
 use super::{Pallet as DigitalTwin, *};
 use frame_benchmarking::v2::*;
+use frame_support::traits::Get;
 use frame_system::RawOrigin;
 use sp_core::H256;
 use sp_std::prelude::*;
@@ -90,6 +91,81 @@ mod benchmarks {
         Ok(())
     }
 
+    #[benchmark]
+    fn transfer() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        let id: u32 = 0;
+        let new_owner: T::AccountId = account("owner", 1, SEED);
+        DigitalTwin::<T>::create(RawOrigin::Signed(caller.clone()).into())?;
+
+        #[extrinsic_call]
+        transfer(RawOrigin::Signed(caller), id, new_owner);
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn destroy() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        let id: u32 = 0;
+        DigitalTwin::<T>::create(RawOrigin::Signed(caller.clone()).into())?;
+        for i in 0..T::MaxTopics::get() {
+            let topic = H256::from_low_u64_be(i as u64);
+            let source: T::AccountId = account("source", i, SEED);
+            DigitalTwin::<T>::set_source(
+                RawOrigin::Signed(caller.clone()).into(),
+                id,
+                topic,
+                source.clone(),
+            )?;
+            DigitalTwin::<T>::publish(RawOrigin::Signed(source).into(), id, topic, topic)?;
+        }
+
+        #[extrinsic_call]
+        destroy(RawOrigin::Signed(caller), id);
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn migrate_to_cps(t: Linear<0, { T::MaxTopics::get() }>) -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        let id: u32 = 0;
+        DigitalTwin::<T>::create(RawOrigin::Signed(caller.clone()).into())?;
+        for i in 0..t {
+            let topic = H256::from_low_u64_be(i as u64);
+            let source: T::AccountId = account("source", i, SEED);
+            DigitalTwin::<T>::set_source(
+                RawOrigin::Signed(caller.clone()).into(),
+                id,
+                topic,
+                source.clone(),
+            )?;
+            DigitalTwin::<T>::publish(RawOrigin::Signed(source).into(), id, topic, topic)?;
+        }
+
+        #[extrinsic_call]
+        migrate_to_cps(RawOrigin::Signed(caller), id);
+
+        assert!(DigitalTwin::<T>::migrated_to(id).is_some());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn publish() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        let id: u32 = 0;
+        let topic: H256 = Default::default();
+        let source: T::AccountId = account("source", 2, SEED);
+        DigitalTwin::<T>::create(RawOrigin::Signed(caller.clone()).into())?;
+        DigitalTwin::<T>::set_source(RawOrigin::Signed(caller).into(), id, topic, source.clone())?;
+
+        #[extrinsic_call]
+        publish(RawOrigin::Signed(source), id, topic, H256::repeat_byte(1));
+
+        Ok(())
+    }
+
     impl_benchmark_test_suite!(
         DigitalTwin,
         crate::tests::new_test_ext(),
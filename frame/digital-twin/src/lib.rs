@@ -25,24 +25,132 @@ pub mod weights;
 pub use pallet::*;
 pub use weights::WeightInfo;
 
+pub mod migration {
+    use super::*;
+    use frame_support::{
+        pallet_prelude::*,
+        traits::{Get, UncheckedOnRuntimeUpgrade},
+    };
+    use sp_core::H256;
+    use sp_std::collections::btree_map::BTreeMap;
+
+    /// Storage layout of digital twin topics in storage version 1: topic -> single source.
+    pub mod v1 {
+        use super::*;
+
+        #[frame_support::storage_alias]
+        pub type DigitalTwin<T: Config> = StorageMap<
+            Pallet<T>,
+            Twox64Concat,
+            u32,
+            BoundedBTreeMap<H256, <T as frame_system::Config>::AccountId, <T as Config>::MaxTopics>,
+        >;
+    }
+
+    /// Bound digital twin topics by `T::MaxTopics`, topics above the limit are dropped.
+    pub type MigrationToV1<T> = frame_support::migrations::VersionedMigration<
+        0,
+        1,
+        UncheckedMigrationToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+    pub struct UncheckedMigrationToV1<T>(PhantomData<T>);
+    impl<T: Config> UncheckedOnRuntimeUpgrade for UncheckedMigrationToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            v1::DigitalTwin::<T>::translate::<BTreeMap<H256, T::AccountId>, _>(|_, old| {
+                translated += 1;
+                let mut topics = BoundedBTreeMap::new();
+                for (topic, source) in old {
+                    if topics.try_insert(topic, source).is_err() {
+                        break;
+                    }
+                }
+                Some(topics)
+            });
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+    }
+
+    /// Convert single topic source into the set of topic sources.
+    pub type MigrationToV2<T> = frame_support::migrations::VersionedMigration<
+        1,
+        2,
+        UncheckedMigrationToV2<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+    pub struct UncheckedMigrationToV2<T>(PhantomData<T>);
+    impl<T: Config> UncheckedOnRuntimeUpgrade for UncheckedMigrationToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            DigitalTwin::<T>::translate::<BoundedBTreeMap<H256, T::AccountId, T::MaxTopics>, _>(
+                |_, old| {
+                    translated += 1;
+                    let mut topics = BoundedBTreeMap::new();
+                    for (topic, source) in old {
+                        let mut sources = BoundedBTreeSet::new();
+                        // Set of sources bound is never zero, so first insert always succeeds
+                        let _ = sources.try_insert(source);
+                        // Topics count is bounded by the same limit as before
+                        let _ = topics.try_insert(topic, sources);
+                    }
+                    Some(topics)
+                },
+            );
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+    }
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
+    use pallet_robonomics_cps::{NodeData, NodeId, MAX_CHILDREN_PER_NODE};
     use sp_core::H256;
-    use sp_std::collections::btree_map::BTreeMap;
 
     use super::*;
 
+    /// Set of data source accounts of digital twin topic.
+    pub type SourcesOf<T> =
+        BoundedBTreeSet<<T as frame_system::Config>::AccountId, <T as Config>::MaxSources>;
+
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
     #[pallet::config]
-    pub trait Config: frame_system::Config {
+    pub trait Config: frame_system::Config + pallet_robonomics_cps::Config {
         /// The overarching event type.
         #[allow(deprecated)]
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// Maximum number of topics per digital twin, should fit CPS node children limit.
+        #[pallet::constant]
+        type MaxTopics: Get<u32>;
+        /// Maximum number of data sources per digital twin topic.
+        #[pallet::constant]
+        type MaxSources: Get<u32>;
         /// Extrinsic weights
         type WeightInfo: WeightInfo;
     }
 
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Digital twin with given id doesn't exist.
+        TwinNotFound,
+        /// Sender should be a twin owner.
+        NotOwner,
+        /// Digital twin topics limit reached.
+        TooManyTopics,
+        /// Digital twin topic sources limit reached.
+        TooManySources,
+        /// Sender isn't registered as a topic source.
+        NotSource,
+        /// Digital twin topic doesn't fit CPS node metadata.
+        TopicTooBig,
+    }
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -50,10 +158,29 @@ pub mod pallet {
         NewDigitalTwin(T::AccountId, u32),
         /// Digital twin topic was changed: [sender, id, topic, source]
         TopicChanged(T::AccountId, u32, H256, T::AccountId),
+        /// Digital twin was transferred: [sender, id, new owner]
+        OwnerChanged(T::AccountId, u32, T::AccountId),
+        /// Digital twin was destroyed: [sender, id]
+        DigitalTwinDestroyed(T::AccountId, u32),
+        /// Digital twin was migrated into CPS tree: [sender, id, root node]
+        MigratedToCps(T::AccountId, u32, NodeId),
+        /// Digital twin topic value was published: [source, id, topic, value hash]
+        TopicUpdated(T::AccountId, u32, H256, H256),
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn integrity_test() {
+            assert!(
+                T::MaxTopics::get() <= MAX_CHILDREN_PER_NODE,
+                "digital twin topics should fit CPS node children limit"
+            );
+            assert!(
+                T::MaxSources::get() > 0,
+                "digital twin topic should accept at least one source"
+            );
+        }
+    }
 
     #[pallet::storage]
     #[pallet::getter(fn total)]
@@ -68,19 +195,30 @@ pub mod pallet {
 
     #[pallet::storage]
     #[pallet::getter(fn digital_twin)]
-    /// Get internal structure of difital twin in format: topic hash -> source account.
-    pub(super) type DigitalTwin<T> =
-        StorageMap<_, Twox64Concat, u32, BTreeMap<H256, <T as frame_system::Config>::AccountId>>;
+    /// Get internal structure of difital twin in format: topic hash -> source accounts.
+    pub(super) type DigitalTwin<T: Config> =
+        StorageMap<_, Twox64Concat, u32, BoundedBTreeMap<H256, SourcesOf<T>, T::MaxTopics>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn topic_value)]
+    /// Latest published value hash of digital twin topic.
+    pub(super) type TopicValue<T> =
+        StorageDoubleMap<_, Twox64Concat, u32, Blake2_128Concat, H256, H256>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn migrated_to)]
+    /// Redirect of digital twin migrated into CPS: twin id -> CPS root node.
+    pub(super) type MigratedTo<T> = StorageMap<_, Twox64Concat, u32, NodeId>;
 
     #[pallet::pallet]
-    #[pallet::without_storage_info]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Create new digital twin.
         #[pallet::call_index(0)]
-        #[pallet::weight(T::WeightInfo::create())]
+        #[pallet::weight(<T as Config>::WeightInfo::create())]
         pub fn create(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
             let sender = ensure_signed(origin)?;
             let id = <Total<T>>::get().unwrap_or(0);
@@ -92,7 +230,7 @@ pub mod pallet {
 
         /// Set data source account for difital twin.
         #[pallet::call_index(1)]
-        #[pallet::weight(T::WeightInfo::set_source())]
+        #[pallet::weight(<T as Config>::WeightInfo::set_source())]
         pub fn set_source(
             origin: OriginFor<T>,
             id: u32,
@@ -100,27 +238,31 @@ pub mod pallet {
             source: T::AccountId,
         ) -> DispatchResultWithPostInfo {
             let sender = ensure_signed(origin)?;
-            ensure!(
-                <Owner<T>>::get(id) == Some(sender.clone()),
-                "sender should be a twin owner"
-            );
-            Self::deposit_event(Event::TopicChanged(sender, id, topic, source.clone()));
-            <DigitalTwin<T>>::mutate(id, |m| match m {
-                None => {
-                    let mut map = BTreeMap::new();
-                    map.insert(topic, source);
-                    *m = Some(map);
+            Self::ensure_owner(id, &sender)?;
+            <DigitalTwin<T>>::try_mutate(id, |m| {
+                let topics = m.get_or_insert_with(Default::default);
+                if let Some(sources) = topics.get_mut(&topic) {
+                    sources
+                        .try_insert(source.clone())
+                        .map_err(|_| Error::<T>::TooManySources)?;
+                } else {
+                    let mut sources = SourcesOf::<T>::new();
+                    sources
+                        .try_insert(source.clone())
+                        .map_err(|_| Error::<T>::TooManySources)?;
+                    topics
+                        .try_insert(topic, sources)
+                        .map_err(|_| Error::<T>::TooManyTopics)?;
                 }
-                Some(map) => {
-                    map.insert(topic, source);
-                }
-            });
+                Ok::<_, Error<T>>(())
+            })?;
+            Self::deposit_event(Event::TopicChanged(sender, id, topic, source));
             Ok(().into())
         }
 
         /// Remove data source account for digital twin.
         #[pallet::call_index(2)]
-        #[pallet::weight(T::WeightInfo::remove_source())]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_source())]
         pub fn remove_source(
             origin: OriginFor<T>,
             id: u32,
@@ -128,18 +270,118 @@ pub mod pallet {
             source: T::AccountId,
         ) -> DispatchResultWithPostInfo {
             let sender = ensure_signed(origin)?;
-            ensure!(
-                <Owner<T>>::get(id) == Some(sender.clone()),
-                "sender should be a twin owner"
-            );
+            Self::ensure_owner(id, &sender)?;
             Self::deposit_event(Event::TopicChanged(sender, id, topic, source.clone()));
             <DigitalTwin<T>>::mutate(id, |m| {
                 if let Some(map) = m {
-                    map.remove(&topic);
+                    if let Some(sources) = map.get_mut(&topic) {
+                        sources.remove(&source);
+                        if sources.is_empty() {
+                            map.remove(&topic);
+                            <TopicValue<T>>::remove(id, topic);
+                        }
+                    }
                 }
             });
             Ok(().into())
         }
+
+        /// Transfer digital twin ownership to another account.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::transfer())]
+        pub fn transfer(
+            origin: OriginFor<T>,
+            id: u32,
+            new_owner: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_owner(id, &sender)?;
+            <Owner<T>>::insert(id, new_owner.clone());
+            Self::deposit_event(Event::OwnerChanged(sender, id, new_owner));
+            Ok(().into())
+        }
+
+        /// Destroy digital twin with all its topics.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::destroy())]
+        pub fn destroy(origin: OriginFor<T>, id: u32) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_owner(id, &sender)?;
+            <Owner<T>>::remove(id);
+            <DigitalTwin<T>>::remove(id);
+            let _ = <TopicValue<T>>::clear_prefix(id, T::MaxTopics::get(), None);
+            Self::deposit_event(Event::DigitalTwinDestroyed(sender, id));
+            Ok(().into())
+        }
+
+        /// Move digital twin into CPS tree.
+        ///
+        /// Creates CPS root node owned by twin owner with one child per topic,
+        /// child metadata contains SCALE encoded `(topic, sources)` pair and
+        /// payload contains latest published topic value.
+        /// Twin is removed and redirect record points to created root node.
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::migrate_to_cps(T::MaxTopics::get()))]
+        pub fn migrate_to_cps(origin: OriginFor<T>, id: u32) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_owner(id, &sender)?;
+
+            let topics = <DigitalTwin<T>>::take(id).unwrap_or_default();
+            let root = pallet_robonomics_cps::Pallet::<T>::do_create_node(
+                sender.clone(),
+                None,
+                None,
+                None,
+            )?;
+            for (topic, sources) in topics.iter() {
+                let meta = NodeData::try_from((topic, sources).encode())
+                    .map_err(|_| Error::<T>::TopicTooBig)?;
+                let payload = <TopicValue<T>>::take(id, topic)
+                    .map(|value| NodeData::truncate_from(value.encode()));
+                pallet_robonomics_cps::Pallet::<T>::do_create_node(
+                    sender.clone(),
+                    Some(root),
+                    Some(meta),
+                    payload,
+                )?;
+            }
+
+            <Owner<T>>::remove(id);
+            <MigratedTo<T>>::insert(id, root);
+            Self::deposit_event(Event::MigratedToCps(sender, id, root));
+            Ok(Some(<T as Config>::WeightInfo::migrate_to_cps(
+                topics.len() as u32
+            ))
+            .into())
+        }
+
+        /// Publish new value of digital twin topic, only topic sources are allowed.
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::publish())]
+        pub fn publish(
+            origin: OriginFor<T>,
+            id: u32,
+            topic: H256,
+            value_hash: H256,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            ensure!(<Owner<T>>::contains_key(id), Error::<T>::TwinNotFound);
+            let is_source = <DigitalTwin<T>>::get(id)
+                .and_then(|topics| topics.get(&topic).map(|s| s.contains(&sender)))
+                .unwrap_or(false);
+            ensure!(is_source, Error::<T>::NotSource);
+            <TopicValue<T>>::insert(id, topic, value_hash);
+            Self::deposit_event(Event::TopicUpdated(sender, id, topic, value_hash));
+            Ok(().into())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        fn ensure_owner(id: u32, sender: &T::AccountId) -> DispatchResult {
+            let owner = <Owner<T>>::get(id).ok_or(Error::<T>::TwinNotFound)?;
+            ensure!(&owner == sender, Error::<T>::NotOwner);
+            Ok(())
+        }
     }
 }
 
@@ -147,7 +389,12 @@ pub mod pallet {
 mod tests {
     use crate::{self as digital_twin, *};
 
-    use frame_support::{assert_err, assert_ok, derive_impl, parameter_types};
+    use frame_support::{
+        assert_err, assert_ok, derive_impl, parameter_types,
+        traits::{ConstU32, ConstU64},
+    };
+    use parity_scale_codec::Encode;
+    use sp_core::H256;
     use sp_runtime::{BuildStorage, DispatchError};
 
     type Block = frame_system::mocking::MockBlock<Runtime>;
@@ -155,6 +402,9 @@ mod tests {
     frame_support::construct_runtime!(
         pub enum Runtime {
             System: frame_system,
+            Balances: pallet_balances,
+            Timestamp: pallet_timestamp,
+            Cps: pallet_robonomics_cps,
             DigitalTwin: digital_twin,
         }
     );
@@ -166,10 +416,36 @@ mod tests {
     #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
     impl frame_system::Config for Runtime {
         type Block = Block;
+        type AccountData = pallet_balances::AccountData<u64>;
+    }
+
+    #[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+    impl pallet_balances::Config for Runtime {
+        type AccountStore = System;
+    }
+
+    impl pallet_timestamp::Config for Runtime {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = ();
+        type WeightInfo = ();
+    }
+
+    impl pallet_robonomics_cps::Config for Runtime {
+        type RuntimeEvent = RuntimeEvent;
+        type OnPayloadSet = ();
+        type RuntimeHoldReason = RuntimeHoldReason;
+        type Currency = Balances;
+        type NodeDepositBase = ConstU64<0>;
+        type ByteDeposit = ConstU64<0>;
+        type Time = Timestamp;
+        type WeightInfo = pallet_robonomics_cps::weights::TestWeightInfo;
     }
 
     impl Config for Runtime {
         type RuntimeEvent = RuntimeEvent;
+        type MaxTopics = ConstU32<2>;
+        type MaxSources = ConstU32<2>;
         type WeightInfo = weights::TestWeightInfo;
     }
 
@@ -204,7 +480,7 @@ mod tests {
                     Default::default(),
                     bad_sender
                 ),
-                DispatchError::Other("sender should be a twin owner")
+                Error::<Runtime>::NotOwner
             );
             assert_ok!(DigitalTwin::set_source(
                 RuntimeOrigin::signed(sender),
@@ -235,7 +511,7 @@ mod tests {
                     Default::default(),
                     source
                 ),
-                DispatchError::Other("sender should be a twin owner")
+                Error::<Runtime>::NotOwner
             );
             assert_ok!(DigitalTwin::remove_source(
                 RuntimeOrigin::signed(sender),
@@ -246,6 +522,250 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_topic_sources() {
+        new_test_ext().execute_with(|| {
+            let sender = 1;
+            let topic = H256::repeat_byte(1);
+            assert_ok!(DigitalTwin::create(RuntimeOrigin::signed(sender)));
+            for source in [2, 3] {
+                assert_ok!(DigitalTwin::set_source(
+                    RuntimeOrigin::signed(sender),
+                    0,
+                    topic,
+                    source
+                ));
+            }
+            assert_err!(
+                DigitalTwin::set_source(RuntimeOrigin::signed(sender), 0, topic, 4),
+                Error::<Runtime>::TooManySources
+            );
+            let sources = |id| {
+                DigitalTwin::digital_twin(id)
+                    .and_then(|t| t.get(&topic).map(|s| s.iter().cloned().collect::<Vec<_>>()))
+            };
+            assert_eq!(sources(0), Some(vec![2, 3]));
+
+            assert_ok!(DigitalTwin::remove_source(
+                RuntimeOrigin::signed(sender),
+                0,
+                topic,
+                2
+            ));
+            assert_eq!(sources(0), Some(vec![3]));
+            assert_ok!(DigitalTwin::remove_source(
+                RuntimeOrigin::signed(sender),
+                0,
+                topic,
+                3
+            ));
+            assert_eq!(sources(0), None);
+        })
+    }
+
+    #[test]
+    fn test_publish() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let sender = 1;
+            let source = 2;
+            let topic = H256::repeat_byte(1);
+            let value = H256::repeat_byte(42);
+            assert_err!(
+                DigitalTwin::publish(RuntimeOrigin::signed(source), 0, topic, value),
+                Error::<Runtime>::TwinNotFound
+            );
+            assert_ok!(DigitalTwin::create(RuntimeOrigin::signed(sender)));
+            assert_ok!(DigitalTwin::set_source(
+                RuntimeOrigin::signed(sender),
+                0,
+                topic,
+                source
+            ));
+            assert_err!(
+                DigitalTwin::publish(RuntimeOrigin::signed(sender), 0, topic, value),
+                Error::<Runtime>::NotSource
+            );
+            assert_err!(
+                DigitalTwin::publish(RuntimeOrigin::signed(source), 0, H256::zero(), value),
+                Error::<Runtime>::NotSource
+            );
+            assert_ok!(DigitalTwin::publish(
+                RuntimeOrigin::signed(source),
+                0,
+                topic,
+                value
+            ));
+            assert_eq!(DigitalTwin::topic_value(0, topic), Some(value));
+            System::assert_last_event(Event::TopicUpdated(source, 0, topic, value).into());
+
+            // Value is dropped with the last topic source
+            assert_ok!(DigitalTwin::remove_source(
+                RuntimeOrigin::signed(sender),
+                0,
+                topic,
+                source
+            ));
+            assert_eq!(DigitalTwin::topic_value(0, topic), None);
+        })
+    }
+
+    #[test]
+    fn test_too_many_topics() {
+        new_test_ext().execute_with(|| {
+            let sender = 1;
+            let source = 2;
+            assert_ok!(DigitalTwin::create(RuntimeOrigin::signed(sender)));
+            for topic in 0..2 {
+                assert_ok!(DigitalTwin::set_source(
+                    RuntimeOrigin::signed(sender),
+                    0,
+                    H256::repeat_byte(topic),
+                    source
+                ));
+            }
+            assert_err!(
+                DigitalTwin::set_source(
+                    RuntimeOrigin::signed(sender),
+                    0,
+                    H256::repeat_byte(2),
+                    source
+                ),
+                Error::<Runtime>::TooManyTopics
+            );
+            // Existing topic could be updated
+            assert_ok!(DigitalTwin::set_source(
+                RuntimeOrigin::signed(sender),
+                0,
+                H256::repeat_byte(1),
+                sender
+            ));
+            assert_eq!(DigitalTwin::digital_twin(0).unwrap().len(), 2);
+        })
+    }
+
+    #[test]
+    fn test_transfer() {
+        new_test_ext().execute_with(|| {
+            let sender = 1;
+            let new_owner = 2;
+            assert_err!(
+                DigitalTwin::transfer(RuntimeOrigin::signed(sender), 0, new_owner),
+                Error::<Runtime>::TwinNotFound
+            );
+            assert_ok!(DigitalTwin::create(RuntimeOrigin::signed(sender)));
+            assert_err!(
+                DigitalTwin::transfer(RuntimeOrigin::signed(new_owner), 0, new_owner),
+                Error::<Runtime>::NotOwner
+            );
+            assert_ok!(DigitalTwin::transfer(
+                RuntimeOrigin::signed(sender),
+                0,
+                new_owner
+            ));
+            assert_eq!(DigitalTwin::owner(0), Some(new_owner));
+            assert_err!(
+                DigitalTwin::set_source(
+                    RuntimeOrigin::signed(sender),
+                    0,
+                    Default::default(),
+                    sender
+                ),
+                Error::<Runtime>::NotOwner
+            );
+            assert_ok!(DigitalTwin::set_source(
+                RuntimeOrigin::signed(new_owner),
+                0,
+                Default::default(),
+                sender
+            ));
+        })
+    }
+
+    #[test]
+    fn test_destroy() {
+        new_test_ext().execute_with(|| {
+            let sender = 1;
+            let bad_sender = 2;
+            assert_ok!(DigitalTwin::create(RuntimeOrigin::signed(sender)));
+            assert_ok!(DigitalTwin::set_source(
+                RuntimeOrigin::signed(sender),
+                0,
+                Default::default(),
+                bad_sender
+            ));
+            assert_err!(
+                DigitalTwin::destroy(RuntimeOrigin::signed(bad_sender), 0),
+                Error::<Runtime>::NotOwner
+            );
+            assert_ok!(DigitalTwin::destroy(RuntimeOrigin::signed(sender), 0));
+            assert_eq!(DigitalTwin::owner(0), None);
+            assert_eq!(DigitalTwin::digital_twin(0), None);
+            assert_eq!(DigitalTwin::total(), Some(1));
+            assert_err!(
+                DigitalTwin::destroy(RuntimeOrigin::signed(sender), 0),
+                Error::<Runtime>::TwinNotFound
+            );
+        })
+    }
+
+    #[test]
+    fn test_migrate_to_cps() {
+        new_test_ext().execute_with(|| {
+            let sender = 1;
+            let bad_sender = 2;
+            let topics = [(H256::repeat_byte(1), 3), (H256::repeat_byte(2), 4)];
+            assert_ok!(DigitalTwin::create(RuntimeOrigin::signed(sender)));
+            for (topic, source) in topics {
+                assert_ok!(DigitalTwin::set_source(
+                    RuntimeOrigin::signed(sender),
+                    0,
+                    topic,
+                    source
+                ));
+            }
+            let value = H256::repeat_byte(42);
+            assert_ok!(DigitalTwin::publish(
+                RuntimeOrigin::signed(3),
+                0,
+                topics[0].0,
+                value
+            ));
+            assert_err!(
+                DigitalTwin::migrate_to_cps(RuntimeOrigin::signed(bad_sender), 0),
+                Error::<Runtime>::NotOwner
+            );
+            assert_ok!(DigitalTwin::migrate_to_cps(
+                RuntimeOrigin::signed(sender),
+                0
+            ));
+
+            let root = DigitalTwin::migrated_to(0).unwrap();
+            assert_eq!(DigitalTwin::owner(0), None);
+            assert_eq!(DigitalTwin::digital_twin(0), None);
+
+            let node = Cps::nodes(root).unwrap();
+            assert_eq!(node.owner, sender);
+            assert_eq!(node.parent, None);
+            let children = Cps::nodes_by_parent(root);
+            assert_eq!(children.len(), topics.len());
+            for (child, (topic, source)) in children.iter().zip(topics) {
+                let node = Cps::nodes(child).unwrap();
+                assert_eq!(node.owner, sender);
+                assert_eq!(node.meta.unwrap().to_vec(), (topic, vec![source]).encode());
+            }
+            let payload = Cps::nodes(children[0]).unwrap().payload;
+            assert_eq!(payload.unwrap().to_vec(), value.encode());
+            assert_eq!(Cps::nodes(children[1]).unwrap().payload, None);
+            assert_eq!(DigitalTwin::topic_value(0, topics[0].0), None);
+
+            assert_err!(
+                DigitalTwin::migrate_to_cps(RuntimeOrigin::signed(sender), 0),
+                Error::<Runtime>::TwinNotFound
+            );
+        })
+    }
+
     #[test]
     fn test_bad_origin() {
         new_test_ext().execute_with(|| {
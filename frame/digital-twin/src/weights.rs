@@ -26,6 +26,10 @@ pub trait WeightInfo {
     fn create() -> Weight;
     fn set_source() -> Weight;
     fn remove_source() -> Weight;
+    fn transfer() -> Weight;
+    fn destroy() -> Weight;
+    fn migrate_to_cps(t: u32) -> Weight;
+    fn publish() -> Weight;
 }
 
 /// Test weight implementation that returns zero weight for all operations.
@@ -42,4 +46,16 @@ impl WeightInfo for TestWeightInfo {
     fn remove_source() -> Weight {
         Weight::zero()
     }
+    fn transfer() -> Weight {
+        Weight::zero()
+    }
+    fn destroy() -> Weight {
+        Weight::zero()
+    }
+    fn migrate_to_cps(_t: u32) -> Weight {
+        Weight::zero()
+    }
+    fn publish() -> Weight {
+        Weight::zero()
+    }
 }
@@ -99,6 +99,12 @@ mod benchmark_runtime {
         pub const MaxLocks: u32 = 50;
         pub const MaxReserves: u32 = 50;
         pub const ExistentialDeposit: Balance = 10;
+        pub const ChallengePeriod: u32 = 10;
+        pub const DisputeBond: Balance = 100;
+        pub const ResolutionPeriod: u32 = 10;
+        pub const OrderDeposit: Balance = 10;
+        pub const StorageDeposit: Balance = 100;
+        pub const RetentionPeriod: u32 = 10;
     }
 
     impl pallet_balances::Config for BenchmarkRuntime {
@@ -113,7 +119,7 @@ mod benchmark_runtime {
         type WeightInfo = ();
         type FreezeIdentifier = ();
         type MaxFreezes = ();
-        type RuntimeHoldReason = ();
+        type RuntimeHoldReason = RuntimeHoldReason;
         type RuntimeFreezeReason = RuntimeFreezeReason;
         type DoneSlashHandler = ();
     }
@@ -132,6 +138,18 @@ mod benchmark_runtime {
             sp_runtime::MultiSignature,
             crate::technics::IPFS,
         >;
+        type RuntimeHoldReason = RuntimeHoldReason;
+        type Currency = Balances;
+        type Evidence = crate::technics::IPFS;
+        type ChallengePeriod = ChallengePeriod;
+        type DisputeBond = DisputeBond;
+        type ArbiterOrigin = frame_system::EnsureRoot<Self::AccountId>;
+        type ResolutionPeriod = ResolutionPeriod;
+        type OrderSignature = sp_runtime::MultiSignature;
+        type MaxOrders = frame_support::traits::ConstU32<16>;
+        type OrderDeposit = OrderDeposit;
+        type StorageDeposit = StorageDeposit;
+        type RetentionPeriod = RetentionPeriod;
         type WeightInfo = weights::TestWeightInfo;
     }
 }
@@ -140,26 +158,39 @@ mod benchmark_runtime {
 mod benchmarks {
     use super::*;
     use crate::economics::SimpleMarket;
-    use crate::signed::{SignedAgreement, SignedReport};
-    use crate::technics::IPFS;
-    use frame_support::traits::Currency;
+    use crate::signed::{SignedAgreement, SignedReport, ValidatedReport};
+    use crate::technics::{Technics, IPFS};
+    use frame_support::traits::{
+        fungible::{Inspect, Mutate, MutateHold},
+        Currency, Get,
+    };
+    use frame_system::pallet_prelude::BlockNumberFor;
     use parity_scale_codec::{Decode, Encode};
     use sp_core::sr25519;
+    use sp_runtime::traits::{One, Saturating, Zero};
     use sp_runtime::{AccountId32, MultiSignature};
 
     fn dummy_signature() -> MultiSignature {
         MultiSignature::Sr25519(sr25519::Signature::from_raw([0u8; 64]))
     }
 
-    fn create_test_agreement() -> SignedAgreement<
-        IPFS,
+    // Benchmark technics of runtime type: IPFS hash or its content identifier
+    fn test_technics<T: Config>() -> TechnicsFor<T> {
+        let ipfs = IPFS {
+            hash: IPFS_HASH.into(),
+        };
+        TechnicsFor::<T>::decode(&mut &ipfs.encode()[..])
+            .or_else(|_| TechnicsFor::<T>::decode(&mut &Technics::from(ipfs).encode()[..]))
+            .expect("Failed to decode technics")
+    }
+
+    fn create_test_agreement<T: Config>() -> SignedAgreement<
+        TechnicsFor<T>,
         SimpleMarket<AccountId32, benchmark_runtime::Balances>,
         AccountId32,
         MultiSignature,
     > {
-        let technics = IPFS {
-            hash: IPFS_HASH.into(),
-        };
+        let technics = test_technics::<T>();
         let economics = SimpleMarket::<AccountId32, benchmark_runtime::Balances> { price: 10 };
         let promisee: AccountId32 = account("alice", 0, 0);
         let promisor: AccountId32 = account("bob", 0, 0);
@@ -172,11 +203,41 @@ mod benchmarks {
             economics,
             promisee,
             promisor,
+            validator: None,
+            nonce: 0,
             promisee_signature: signature.clone(),
             promisor_signature: signature,
         }
     }
 
+    // Benchmark agreement of runtime type: price in native currency as simple market
+    // or as native variant of economics that could be paid in assets
+    fn runtime_agreement<T: Config>(
+        agreement: &SignedAgreement<
+            TechnicsFor<T>,
+            SimpleMarket<AccountId32, benchmark_runtime::Balances>,
+            AccountId32,
+            MultiSignature,
+        >,
+    ) -> T::Agreement {
+        T::Agreement::decode(&mut &agreement.encode()[..])
+            .or_else(|_| {
+                // Native variant index is encoded before its price
+                let native = SignedAgreement {
+                    technics: agreement.technics.clone(),
+                    economics: (0u8, agreement.economics.clone()),
+                    promisee: agreement.promisee.clone(),
+                    promisor: agreement.promisor.clone(),
+                    validator: agreement.validator.clone(),
+                    nonce: agreement.nonce,
+                    promisee_signature: agreement.promisee_signature.clone(),
+                    promisor_signature: agreement.promisor_signature.clone(),
+                };
+                T::Agreement::decode(&mut &native.encode()[..])
+            })
+            .expect("Failed to decode agreement")
+    }
+
     fn create_test_report() -> SignedReport<u32, AccountId32, MultiSignature, IPFS> {
         SignedReport {
             index: 0,
@@ -188,13 +249,96 @@ mod benchmarks {
         }
     }
 
+    // Caller account able to pay liability storage deposit
+    fn funded_caller<T: Config>() -> T::AccountId {
+        let caller: T::AccountId = whitelisted_caller();
+        let balance = T::StorageDeposit::get()
+            .saturating_add(T::OrderDeposit::get())
+            .saturating_mul(10u32.into())
+            .saturating_add(T::Currency::minimum_balance());
+        let _ = T::Currency::set_balance(&caller, balance);
+        caller
+    }
+
+    // Create liability and put its verdict on hold, returns promisee account
+    fn create_test_settlement<T: Config>(
+        deadline: BlockNumberFor<T>,
+        disputed: bool,
+    ) -> T::AccountId {
+        let caller = funded_caller::<T>();
+        let agreement = create_test_agreement::<T>();
+        let promisee = T::AccountId::decode(&mut &agreement.promisee.encode()[..])
+            .expect("Failed to decode promisee account");
+        let agreement_typed = runtime_agreement::<T>(&agreement);
+        let _ = Liability::<T>::create(RawOrigin::Signed(caller).into(), agreement_typed);
+
+        let bond = T::DisputeBond::get();
+        let _ = T::Currency::set_balance(&promisee, bond.saturating_mul(10u32.into()));
+        let dispute = if disputed {
+            let _ = T::Currency::hold(&HoldReason::DisputeBond.into(), &promisee, bond);
+            let evidence = T::Evidence::decode(
+                &mut &IPFS {
+                    hash: IPFS_HASH.into(),
+                }
+                .encode()[..],
+            )
+            .expect("Failed to decode evidence");
+            Some(Dispute { bond, evidence })
+        } else {
+            None
+        };
+        <SettlementOf<T>>::insert(
+            0,
+            Settlement {
+                verdict: true,
+                deadline,
+                dispute,
+            },
+        );
+        promisee
+    }
+
+    // Make market order of benchmark technics
+    fn create_test_order<T: Config>(
+        side: OrderSide,
+        owner: T::AccountId,
+        nonce: AgreementNonce,
+    ) -> OrderFor<T> {
+        // Demand owner pays storage deposit of matched liability
+        let balance = T::StorageDeposit::get()
+            .saturating_mul(10u32.into())
+            .saturating_add(10000u32.into());
+        let _ = T::Currency::set_balance(&owner, balance);
+        let technics = test_technics::<T>();
+        let signature = T::OrderSignature::decode(&mut &dummy_signature().encode()[..])
+            .expect("Failed to decode order signature");
+
+        Order {
+            side,
+            technics,
+            price: 10u32.into(),
+            owner,
+            expires: frame_system::Pallet::<T>::block_number().saturating_add(100u32.into()),
+            nonce,
+            signature,
+        }
+    }
+
+    // Place market order of benchmark technics, returns order index
+    fn place_test_order<T: Config>(side: OrderSide, owner: T::AccountId) -> OrderIndex {
+        let caller = funded_caller::<T>();
+        let order = create_test_order::<T>(side, owner, 0);
+        let index = Liability::<T>::next_order_index();
+        let _ = Liability::<T>::place_order(RawOrigin::Signed(caller).into(), order);
+        index
+    }
+
     #[benchmark]
     fn create() {
-        let caller: T::AccountId = whitelisted_caller();
-        let signed_agreement = create_test_agreement();
+        let caller = funded_caller::<T>();
+        let signed_agreement = create_test_agreement::<T>();
 
-        let agreement = T::Agreement::decode(&mut &signed_agreement.encode()[..])
-            .expect("Failed to decode agreement for benchmarking");
+        let agreement = runtime_agreement::<T>(&signed_agreement);
 
         #[extrinsic_call]
         _(RawOrigin::Signed(caller), agreement);
@@ -202,12 +346,11 @@ mod benchmarks {
 
     #[benchmark]
     fn finalize() {
-        let caller: T::AccountId = whitelisted_caller();
+        let caller = funded_caller::<T>();
 
         // Create a liability first
-        let agreement = create_test_agreement();
-        let agreement_typed =
-            T::Agreement::decode(&mut &agreement.encode()[..]).expect("Failed to decode agreement");
+        let agreement = create_test_agreement::<T>();
+        let agreement_typed = runtime_agreement::<T>(&agreement);
         let _ = Liability::<T>::create(RawOrigin::Signed(caller.clone()).into(), agreement_typed);
 
         // Create report to finalize the liability
@@ -219,9 +362,196 @@ mod benchmarks {
         _(RawOrigin::Signed(caller), report);
     }
 
+    #[benchmark]
+    fn confirm() -> Result<(), BenchmarkError> {
+        let caller = funded_caller::<T>();
+        let validator = AccountId32::decode(&mut &caller.encode()[..])
+            .expect("Failed to decode validator account");
+
+        // Runtime report type could not be confirmed by validator
+        if !<ReportFor<T> as RealWorldOracle>::VALIDATED {
+            return Err(BenchmarkError::Skip);
+        }
+
+        // Create a liability with the caller as validator
+        let mut agreement = create_test_agreement::<T>();
+        agreement.validator = Some(validator);
+        let agreement_typed = runtime_agreement::<T>(&agreement);
+        let _ = Liability::<T>::create(RawOrigin::Signed(caller.clone()).into(), agreement_typed);
+
+        // Put pending report to be confirmed by validator
+        let validated_report = ValidatedReport::<u32, AccountId32, MultiSignature, IPFS> {
+            index: 0,
+            sender: account("bob", 0, 0),
+            payload: IPFS {
+                hash: IPFS_HASH.into(),
+            },
+            signature: dummy_signature(),
+            verdict: None,
+        };
+        let report = ReportFor::<T>::decode(&mut &validated_report.encode()[..])
+            .expect("Failed to decode report");
+        <ReportOf<T>>::insert(0, report);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), 0, true);
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn dispute() {
+        let deadline = frame_system::Pallet::<T>::block_number().saturating_add(One::one());
+        let promisee = create_test_settlement::<T>(deadline, false);
+        let evidence = T::Evidence::decode(
+            &mut &IPFS {
+                hash: IPFS_HASH.into(),
+            }
+            .encode()[..],
+        )
+        .expect("Failed to decode evidence");
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(promisee), 0, evidence);
+    }
+
+    #[benchmark]
+    fn resolve() -> Result<(), BenchmarkError> {
+        let deadline = frame_system::Pallet::<T>::block_number().saturating_add(One::one());
+        let _ = create_test_settlement::<T>(deadline, true);
+        let origin =
+            T::ArbiterOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, 0, true);
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn settle() {
+        let caller: T::AccountId = whitelisted_caller();
+        // Worst case is disputed liability that wasn't resolved in time
+        let _ = create_test_settlement::<T>(Zero::zero(), true);
+        frame_system::Pallet::<T>::set_block_number(
+            T::ResolutionPeriod::get().saturating_add(One::one()),
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), 0);
+    }
+
+    #[benchmark]
+    fn expire() -> Result<(), BenchmarkError> {
+        let caller = funded_caller::<T>();
+        let agreement = runtime_agreement::<T>(&create_test_agreement::<T>());
+        // Runtime agreement type could not be expired
+        let deadline = agreement.expires_at().ok_or(BenchmarkError::Skip)?;
+        let _ = Liability::<T>::create(RawOrigin::Signed(caller.clone()).into(), agreement);
+        frame_system::Pallet::<T>::set_block_number(deadline.saturating_add(One::one()));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), 0);
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn report_milestone() -> Result<(), BenchmarkError> {
+        let caller = funded_caller::<T>();
+        let agreement = runtime_agreement::<T>(&create_test_agreement::<T>());
+        // Runtime agreement type has no milestones
+        if agreement.milestones() == 0 {
+            return Err(BenchmarkError::Skip);
+        }
+        let promisor = agreement.promisor();
+        let _ = Liability::<T>::create(RawOrigin::Signed(caller).into(), agreement);
+        let payload = ReportMessageFor::<T>::decode(
+            &mut &IPFS {
+                hash: IPFS_HASH.into(),
+            }
+            .encode()[..],
+        )
+        .expect("Failed to decode payload");
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(promisor), 0, 0, payload);
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn place_order() {
+        let caller = funded_caller::<T>();
+        let order = create_test_order::<T>(OrderSide::Demand, account("alice", 0, 0), 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), order);
+    }
+
+    #[benchmark]
+    fn cancel_order() {
+        let caller = funded_caller::<T>();
+        let index = place_test_order::<T>(OrderSide::Offer, caller.clone());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), index);
+    }
+
+    #[benchmark]
+    fn match_orders() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        let demand = place_test_order::<T>(OrderSide::Demand, account("alice", 0, 0));
+        let offer = place_test_order::<T>(OrderSide::Offer, account("bob", 0, 0));
+        let demand_order = Liability::<T>::order_of(demand).ok_or(BenchmarkError::Weightless)?;
+        let offer_order = Liability::<T>::order_of(offer).ok_or(BenchmarkError::Weightless)?;
+        // Runtime agreement type couldn't be made of orders
+        if T::Agreement::from_orders(
+            demand_order.technics,
+            offer_order.price,
+            (demand_order.owner, demand_order.signature),
+            (offer_order.owner, offer_order.signature),
+            demand_order.nonce,
+        )
+        .is_none()
+        {
+            return Err(BenchmarkError::Skip);
+        }
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), demand, offer);
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn prune() {
+        let caller = funded_caller::<T>();
+        let agreement = runtime_agreement::<T>(&create_test_agreement::<T>());
+        let promisee = agreement.promisee();
+        let promisor = agreement.promisor();
+        let _ = Liability::<T>::create(RawOrigin::Signed(caller.clone()).into(), agreement);
+
+        // Put reported and finalized liability out of retention period
+        let report = ReportFor::<T>::decode(&mut &create_test_report().encode()[..])
+            .expect("Failed to decode report");
+        <ReportOf<T>>::insert(0, report);
+        <PromiseeLiabilities<T>>::insert(&promisee, 0, LiabilityStatus::Finalized);
+        <PromisorLiabilities<T>>::insert(&promisor, 0, LiabilityStatus::Finalized);
+        let now = frame_system::Pallet::<T>::block_number();
+        <FinalizedAt<T>>::insert(0, now);
+        frame_system::Pallet::<T>::set_block_number(
+            now.saturating_add(T::RetentionPeriod::get())
+                .saturating_add(One::one()),
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), 0);
+    }
+
     impl_benchmark_test_suite!(
         Liability,
-        crate::tests::new_test_ext(),
-        crate::tests::Runtime,
+        crate::mock::simple_market::new_test_ext(),
+        crate::mock::simple_market::Runtime,
     );
 }
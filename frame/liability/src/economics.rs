@@ -17,9 +17,17 @@
 ///////////////////////////////////////////////////////////////////////////////
 //! Set of approaches to handle economical aspects of agreement.
 
-use frame_support::traits::Currency;
+use frame_support::{
+    traits::{fungibles, tokens::ConversionFromAssetBalance, ConstU32, Currency},
+    BoundedVec,
+};
 use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::marker::PhantomData;
+
+use crate::pallet::Reputation;
+use crate::traits::{Expiration, MinReputation, Valuation};
 
 /// Simple market as approach: liability has a price of execution.
 #[derive(
@@ -30,3 +38,230 @@ pub struct SimpleMarket<AccountId, C: Currency<AccountId>> {
     #[codec(compact)]
     pub price: C::Balance,
 }
+
+impl<A, C: Currency<A>, B> Expiration<B> for SimpleMarket<A, C> {
+    fn expires_at(&self) -> Option<B> {
+        None
+    }
+}
+
+impl<A, C: Currency<A>> Valuation<C::Balance> for SimpleMarket<A, C> {
+    fn value(&self) -> C::Balance {
+        self.price
+    }
+}
+
+impl<A, C: Currency<A>, R> MinReputation<R> for SimpleMarket<A, C> {
+    fn is_acceptable(&self, _reputation: &R) -> bool {
+        true
+    }
+}
+
+/// Reputable market: liability has a price and could be made only with promisor
+/// that has enough completed liabilities of enough value and not too many failed ones.
+#[derive(
+    Encode, Decode, PartialEq, Clone, Eq, TypeInfo, Debug, MaxEncodedLen, DecodeWithMemTracking,
+)]
+#[scale_info(skip_type_params(C))]
+pub struct ReputableMarket<AccountId, C: Currency<AccountId>> {
+    #[codec(compact)]
+    pub price: C::Balance,
+    /// Minimal number of completed promisor liabilities.
+    pub min_completed: u32,
+    /// Maximal number of failed and expired promisor liabilities.
+    pub max_failed: u32,
+    /// Minimal total value of completed promisor liabilities.
+    #[codec(compact)]
+    pub min_settled: C::Balance,
+}
+
+impl<A, C: Currency<A>, B> Expiration<B> for ReputableMarket<A, C> {
+    fn expires_at(&self) -> Option<B> {
+        None
+    }
+}
+
+impl<A, C: Currency<A>> Valuation<C::Balance> for ReputableMarket<A, C> {
+    fn value(&self) -> C::Balance {
+        self.price
+    }
+}
+
+impl<A, C: Currency<A>> MinReputation<Reputation<C::Balance>> for ReputableMarket<A, C> {
+    fn is_acceptable(&self, reputation: &Reputation<C::Balance>) -> bool {
+        reputation.completed >= self.min_completed
+            && reputation.settled >= self.min_settled
+            && reputation.failed.saturating_add(reputation.expired) <= self.max_failed
+    }
+}
+
+/// Multi-asset market: liability price paid in fungible asset, e.g. bridged by XCM.
+/// Asset amount is valued in native currency by converter `V`.
+#[derive(
+    Encode, Decode, PartialEq, Clone, Eq, TypeInfo, Debug, MaxEncodedLen, DecodeWithMemTracking,
+)]
+#[scale_info(skip_type_params(F, V))]
+pub struct MultiAssetMarket<AccountId, F: fungibles::Inspect<AccountId>, V> {
+    pub asset_id: F::AssetId,
+    #[codec(compact)]
+    pub amount: F::Balance,
+    #[codec(skip)]
+    pub valuation: PhantomData<V>,
+}
+
+impl<A, F: fungibles::Inspect<A>, V, B> Expiration<B> for MultiAssetMarket<A, F, V> {
+    fn expires_at(&self) -> Option<B> {
+        None
+    }
+}
+
+// Asset without conversion rate has no value in native currency.
+impl<A, F, V, B> Valuation<B> for MultiAssetMarket<A, F, V>
+where
+    F: fungibles::Inspect<A>,
+    V: ConversionFromAssetBalance<F::Balance, F::AssetId, B>,
+    B: Zero,
+{
+    fn value(&self) -> B {
+        V::from_asset_balance(self.amount, self.asset_id.clone()).unwrap_or_else(|_| Zero::zero())
+    }
+}
+
+impl<A, F: fungibles::Inspect<A>, V, R> MinReputation<R> for MultiAssetMarket<A, F, V> {
+    fn is_acceptable(&self, _reputation: &R) -> bool {
+        true
+    }
+}
+
+/// Native or asset market: liability price paid either in native currency
+/// or in fungible asset, parties choose one when making agreement.
+#[derive(
+    Encode, Decode, PartialEq, Clone, Eq, TypeInfo, Debug, MaxEncodedLen, DecodeWithMemTracking,
+)]
+#[scale_info(skip_type_params(C, F, V))]
+pub enum NativeOrAssetMarket<AccountId, C: Currency<AccountId>, F: fungibles::Inspect<AccountId>, V>
+{
+    #[codec(index = 0)]
+    Native(SimpleMarket<AccountId, C>),
+    #[codec(index = 1)]
+    Asset(MultiAssetMarket<AccountId, F, V>),
+}
+
+// Agreements made before asset payments were supported are paid in native currency.
+impl<A, C: Currency<A>, F: fungibles::Inspect<A>, V> From<SimpleMarket<A, C>>
+    for NativeOrAssetMarket<A, C, F, V>
+{
+    fn from(market: SimpleMarket<A, C>) -> Self {
+        Self::Native(market)
+    }
+}
+
+impl<A, C: Currency<A>, F: fungibles::Inspect<A>, V, B> Expiration<B>
+    for NativeOrAssetMarket<A, C, F, V>
+{
+    fn expires_at(&self) -> Option<B> {
+        None
+    }
+}
+
+impl<A, C, F, V> Valuation<C::Balance> for NativeOrAssetMarket<A, C, F, V>
+where
+    C: Currency<A>,
+    F: fungibles::Inspect<A>,
+    V: ConversionFromAssetBalance<F::Balance, F::AssetId, C::Balance>,
+{
+    fn value(&self) -> C::Balance {
+        match self {
+            Self::Native(market) => market.value(),
+            Self::Asset(market) => market.value(),
+        }
+    }
+}
+
+impl<A, C: Currency<A>, F: fungibles::Inspect<A>, V, R> MinReputation<R>
+    for NativeOrAssetMarket<A, C, F, V>
+{
+    fn is_acceptable(&self, _reputation: &R) -> bool {
+        true
+    }
+}
+
+/// Maximal number of milestones in agreement.
+pub const MAX_MILESTONES: u32 = 16;
+
+/// Part of liability execution that paid separately.
+#[derive(
+    Encode, Decode, PartialEq, Clone, Eq, TypeInfo, Debug, MaxEncodedLen, DecodeWithMemTracking,
+)]
+pub struct Milestone<Balance, Technics> {
+    #[codec(compact)]
+    pub amount: Balance,
+    /// Milestone task description.
+    pub technics: Technics,
+}
+
+/// Milestone market: liability price paid by tranches as milestones reached.
+#[derive(
+    Encode, Decode, PartialEq, Clone, Eq, TypeInfo, Debug, MaxEncodedLen, DecodeWithMemTracking,
+)]
+#[scale_info(skip_type_params(C))]
+pub struct MilestoneMarket<AccountId, C: Currency<AccountId>, Technics, BlockNumber> {
+    pub milestones: BoundedVec<Milestone<C::Balance, Technics>, ConstU32<MAX_MILESTONES>>,
+    /// Last block when milestone report could be published.
+    pub deadline: BlockNumber,
+}
+
+impl<A, C: Currency<A>, T, B: Clone> Expiration<B> for MilestoneMarket<A, C, T, B> {
+    fn expires_at(&self) -> Option<B> {
+        Some(self.deadline.clone())
+    }
+}
+
+impl<A, C: Currency<A>, T, B> Valuation<C::Balance> for MilestoneMarket<A, C, T, B> {
+    fn value(&self) -> C::Balance {
+        self.milestones
+            .iter()
+            .fold(Zero::zero(), |sum: C::Balance, m| {
+                sum.saturating_add(m.amount)
+            })
+    }
+}
+
+impl<A, C: Currency<A>, T, B, R> MinReputation<R> for MilestoneMarket<A, C, T, B> {
+    fn is_acceptable(&self, _reputation: &R) -> bool {
+        true
+    }
+}
+
+/// Collateralized market: promisor pledges collateral that goes to promisee
+/// when liability fails or expires.
+#[derive(
+    Encode, Decode, PartialEq, Clone, Eq, TypeInfo, Debug, MaxEncodedLen, DecodeWithMemTracking,
+)]
+#[scale_info(skip_type_params(C))]
+pub struct CollateralizedMarket<AccountId, C: Currency<AccountId>, BlockNumber> {
+    #[codec(compact)]
+    pub price: C::Balance,
+    #[codec(compact)]
+    pub collateral: C::Balance,
+    /// Last block when liability report could be published.
+    pub deadline: BlockNumber,
+}
+
+impl<A, C: Currency<A>, B: Clone> Expiration<B> for CollateralizedMarket<A, C, B> {
+    fn expires_at(&self) -> Option<B> {
+        Some(self.deadline.clone())
+    }
+}
+
+impl<A, C: Currency<A>, B> Valuation<C::Balance> for CollateralizedMarket<A, C, B> {
+    fn value(&self) -> C::Balance {
+        self.price
+    }
+}
+
+impl<A, C: Currency<A>, B, R> MinReputation<R> for CollateralizedMarket<A, C, B> {
+    fn is_acceptable(&self, _reputation: &R) -> bool {
+        true
+    }
+}
@@ -19,12 +19,15 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod economics;
+pub mod runtime_api;
 pub mod signed;
 pub mod technics;
 pub mod traits;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+#[cfg(test)]
+mod mock;
 pub mod weights;
 
 pub use pallet::*;
@@ -32,28 +35,316 @@ pub use signed::*;
 pub use traits::*;
 pub use weights::WeightInfo;
 
+pub mod migration {
+    use super::*;
+    use frame_support::{
+        pallet_prelude::*,
+        storage::{migration::storage_key_iter, unhashed, StoragePrefixedMap},
+        traits::{Get, UncheckedOnRuntimeUpgrade},
+    };
+    use sp_runtime::traits::{Hash, Saturating, Zero};
+
+    /// Stored agreements decoded with layout `A` of previous storage version.
+    fn stored_agreements<T: Config, A: Decode>() -> impl Iterator<Item = (Index, A)> {
+        storage_key_iter::<Index, A, Twox64Concat>(
+            AgreementOf::<T>::pallet_prefix(),
+            AgreementOf::<T>::storage_prefix(),
+        )
+    }
+
+    /// Signed agreement layout before replay protection nonce was introduced.
+    #[derive(Encode, Decode)]
+    pub struct OldSignedAgreement<T, E, AccountId, Signature> {
+        pub technics: T,
+        pub economics: E,
+        pub promisee: AccountId,
+        pub promisor: AccountId,
+        pub promisee_signature: Signature,
+        pub promisor_signature: Signature,
+    }
+
+    impl<T, U: From<T>, E, A, S> From<OldSignedAgreement<T, E, A, S>> for SignedAgreement<U, E, A, S> {
+        fn from(old: OldSignedAgreement<T, E, A, S>) -> Self {
+            Self {
+                technics: old.technics.into(),
+                economics: old.economics,
+                promisee: old.promisee,
+                promisor: old.promisor,
+                validator: None,
+                nonce: 0,
+                promisee_signature: old.promisee_signature,
+                promisor_signature: old.promisor_signature,
+            }
+        }
+    }
+
+    /// Translate stored agreements from `Old` layout into `New` layout of storage version 1.
+    ///
+    /// `New` is the agreement layout stored until the next layout migration, it isn't
+    /// `T::Agreement` when later versions change agreement type again.
+    pub type MigrationToV1<T, Old, New> = frame_support::migrations::VersionedMigration<
+        0,
+        1,
+        UncheckedMigrationToV1<T, Old, New>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+    pub struct UncheckedMigrationToV1<T, Old, New>(PhantomData<(T, Old, New)>);
+    impl<T: Config, Old: Decode + Into<New>, New: Encode> UncheckedOnRuntimeUpgrade
+        for UncheckedMigrationToV1<T, Old, New>
+    {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            for (index, old) in stored_agreements::<T, Old>() {
+                let new: New = old.into();
+                unhashed::put(&AgreementOf::<T>::hashed_key_for(index), &new);
+                translated += 1;
+            }
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+    }
+
+    /// Build promisee and promisor liability indexes and promisor reputation
+    /// of stored agreements, `A` is agreement layout of storage version 1.
+    /// Retention period of liabilities finished before upgrade starts at upgrade block.
+    pub type MigrationToV2<T, A> = frame_support::migrations::VersionedMigration<
+        1,
+        2,
+        UncheckedMigrationToV2<T, A>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+    pub struct UncheckedMigrationToV2<T, A>(PhantomData<(T, A)>);
+    impl<T, A> UncheckedOnRuntimeUpgrade for UncheckedMigrationToV2<T, A>
+    where
+        T: Config,
+        A: Decode + Agreement<T::AccountId> + Milestones + Valuation<BalanceOf<T>>,
+    {
+        fn on_runtime_upgrade() -> Weight {
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut indexed = 0u64;
+            for (index, agreement) in stored_agreements::<T, A>() {
+                // Liability is finished when it's expired, reported and settled,
+                // or all its milestones are paid
+                let milestones = agreement.milestones();
+                let settlement = SettlementOf::<T>::get(index);
+                let outcome = if ExpiredOf::<T>::contains_key(index) {
+                    Some(None)
+                } else if milestones > 0 {
+                    (MilestonesPaid::<T>::get(index) == milestones).then_some(Some(true))
+                } else if settlement.is_none() {
+                    ReportOf::<T>::get(index)
+                        .and_then(|report| report.is_confirmed())
+                        .map(Some)
+                } else {
+                    None
+                };
+                // Retention period of finished liabilities starts at upgrade
+                let status = if outcome.is_some() {
+                    FinalizedAt::<T>::insert(index, now);
+                    LiabilityStatus::Finalized
+                } else {
+                    LiabilityStatus::Open
+                };
+
+                let promisor = agreement.promisor();
+                PromiseeLiabilities::<T>::insert(agreement.promisee(), index, status);
+                PromisorLiabilities::<T>::insert(&promisor, index, status);
+                indexed += 1;
+                // Self-dealing and zero-value liabilities don't make reputation
+                if agreement.promisee() == promisor || agreement.value().is_zero() {
+                    continue;
+                }
+                ReputationOf::<T>::mutate(&promisor, |r| {
+                    r.created = r.created.saturating_add(1);
+                    if settlement.map_or(false, |s| s.dispute.is_some()) {
+                        r.disputed = r.disputed.saturating_add(1);
+                    }
+                    match outcome {
+                        Some(Some(true)) => {
+                            r.completed = r.completed.saturating_add(1);
+                            r.settled = r.settled.saturating_add(agreement.value());
+                        }
+                        Some(Some(false)) => r.failed = r.failed.saturating_add(1),
+                        Some(None) => r.expired = r.expired.saturating_add(1),
+                        None => (),
+                    }
+                });
+            }
+            T::DbWeight::get().reads_writes(indexed * 6 + 1, indexed * 4)
+        }
+    }
+
+    /// Stored value with technics of previous type.
+    #[derive(Encode, Decode)]
+    pub struct OldTechnics<V>(pub V);
+
+    impl<T, U: From<T>, E, F: From<E>, A, S> From<OldTechnics<SignedAgreement<T, E, A, S>>>
+        for SignedAgreement<U, F, A, S>
+    {
+        fn from(old: OldTechnics<SignedAgreement<T, E, A, S>>) -> Self {
+            let old = old.0;
+            Self {
+                technics: old.technics.into(),
+                economics: old.economics.into(),
+                promisee: old.promisee,
+                promisor: old.promisor,
+                validator: old.validator,
+                nonce: old.nonce,
+                promisee_signature: old.promisee_signature,
+                promisor_signature: old.promisor_signature,
+            }
+        }
+    }
+
+    impl<T, U: From<T>, P, A, B, S> From<OldTechnics<Order<T, P, A, B, S>>> for Order<U, P, A, B, S> {
+        fn from(old: OldTechnics<Order<T, P, A, B, S>>) -> Self {
+            let old = old.0;
+            Self {
+                side: old.side,
+                technics: old.technics.into(),
+                price: old.price,
+                owner: old.owner,
+                expires: old.expires,
+                nonce: old.nonce,
+                signature: old.signature,
+            }
+        }
+    }
+
+    /// Translate technics of stored agreements and market orders, e.g. from
+    /// [`crate::technics::IPFS`] into [`crate::technics::Technics`].
+    /// Agreement economics could be translated too, e.g. from
+    /// [`crate::economics::SimpleMarket`] into [`crate::economics::NativeOrAssetMarket`].
+    pub type MigrationToV3<T, OldAgreement, OldOrder> =
+        frame_support::migrations::VersionedMigration<
+            2,
+            3,
+            UncheckedMigrationToV3<T, OldAgreement, OldOrder>,
+            Pallet<T>,
+            <T as frame_system::Config>::DbWeight,
+        >;
+    pub struct UncheckedMigrationToV3<T, OldAgreement, OldOrder>(
+        PhantomData<(T, OldAgreement, OldOrder)>,
+    );
+    impl<T, OldAgreement, OldOrder> UncheckedOnRuntimeUpgrade
+        for UncheckedMigrationToV3<T, OldAgreement, OldOrder>
+    where
+        T: Config,
+        OldAgreement: Decode + Into<T::Agreement>,
+        OldOrder: Decode + Into<OrderFor<T>>,
+    {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            AgreementOf::<T>::translate::<OldAgreement, _>(|_, old| {
+                translated += 1;
+                Some(old.into())
+            });
+
+            // Order book is keyed by technics hash
+            let _ = OrderBook::<T>::clear(u32::MAX, None);
+            OrderOf::<T>::translate::<OldOrder, _>(|index, old| {
+                let order: OrderFor<T> = old.into();
+                let _ = OrderBook::<T>::try_mutate(T::Hashing::hash_of(&order.technics), |book| {
+                    book.try_push(index)
+                });
+                translated += 2;
+                Some(order)
+            });
+
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+    }
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::traits::*;
     use super::*;
-    use frame_support::{dispatch, pallet_prelude::*};
+    use frame_support::{
+        dispatch,
+        pallet_prelude::*,
+        traits::{
+            fungible::{self, MutateHold},
+            tokens::{Fortitude, Precision, Restriction},
+        },
+    };
     use frame_system::pallet_prelude::*;
+    use parity_scale_codec::DecodeWithMemTracking;
+    use sp_runtime::traits::{Hash, IdentifyAccount, Saturating, Verify, Zero};
     use sp_std::prelude::*;
 
     /// Agreement indexing parameter.
     pub type Index = u32;
 
+    /// Market order indexing parameter.
+    pub type OrderIndex = u32;
+
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
         /// How to make and process agreement between two parties.
         type Agreement: dispatch::Parameter
             + Processing
+            + Milestones
             + Agreement<Self::AccountId>
+            + Expiration<BlockNumberFor<Self>>
+            + FromOrders<BalanceOf<Self>, Self::AccountId, Self::OrderSignature>
+            + Valuation<BalanceOf<Self>>
+            + MinReputation<ReputationFor<Self>>
             + MaxEncodedLen;
 
         /// How to report of agreement execution.
         type Report: dispatch::Parameter + Report<Index, Self::AccountId> + MaxEncodedLen;
 
+        /// The overarching hold reason.
+        type RuntimeHoldReason: From<HoldReason>;
+
+        /// Currency to hold dispute bonds and storage deposits.
+        type Currency: fungible::Mutate<Self::AccountId>
+            + fungible::MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+        /// Evidence attached by promisee to report dispute.
+        type Evidence: dispatch::Parameter + MaxEncodedLen;
+
+        /// Period after report when promisee could dispute it, zero disables disputes.
+        #[pallet::constant]
+        type ChallengePeriod: Get<BlockNumberFor<Self>>;
+
+        /// Amount held from promisee to open a dispute.
+        #[pallet::constant]
+        type DisputeBond: Get<BalanceOf<Self>>;
+
+        /// Origin that resolves disputes.
+        type ArbiterOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Period after challenge period when arbiter should resolve dispute, then liability
+        /// could be settled with report verdict and dispute bond returned to promisee.
+        #[pallet::constant]
+        type ResolutionPeriod: Get<BlockNumberFor<Self>>;
+
+        /// Signature of market order made by its owner.
+        type OrderSignature: dispatch::Parameter
+            + MaxEncodedLen
+            + Verify<Signer: IdentifyAccount<AccountId = Self::AccountId>>;
+
+        /// Maximal number of open orders for the same technics.
+        #[pallet::constant]
+        type MaxOrders: Get<u32>;
+
+        /// Amount held from order submitter until order matched, cancelled or expired.
+        #[pallet::constant]
+        type OrderDeposit: Get<BalanceOf<Self>>;
+
+        /// Amount held from liability submitter for agreement and report storage.
+        #[pallet::constant]
+        type StorageDeposit: Get<BalanceOf<Self>>;
+
+        /// Period after liability finalization when it couldn't be pruned.
+        #[pallet::constant]
+        type RetentionPeriod: Get<BlockNumberFor<Self>>;
+
         /// The overarching event type.
         #[allow(deprecated)]
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
@@ -67,6 +358,149 @@ pub mod pallet {
     pub type EconomicsFor<T> =
         <<T as Config>::Agreement as Agreement<<T as frame_system::Config>::AccountId>>::Economical;
     pub type ReportFor<T> = <T as Config>::Report;
+    pub type ReportMessageFor<T> =
+        <<T as Config>::Report as Report<Index, <T as frame_system::Config>::AccountId>>::Message;
+    pub type BalanceOf<T> = <<T as Config>::Currency as fungible::Inspect<
+        <T as frame_system::Config>::AccountId,
+    >>::Balance;
+    pub type ReputationFor<T> = Reputation<BalanceOf<T>>;
+    pub type SettlementFor<T> =
+        Settlement<BlockNumberFor<T>, BalanceOf<T>, <T as Config>::Evidence>;
+    pub type OrderFor<T> = Order<
+        TechnicsFor<T>,
+        BalanceOf<T>,
+        <T as frame_system::Config>::AccountId,
+        BlockNumberFor<T>,
+        <T as Config>::OrderSignature,
+    >;
+
+    /// Report dispute opened by promisee.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Dispute<Balance, Evidence> {
+        /// Amount held from promisee.
+        pub bond: Balance,
+        /// Promisee evidence against report.
+        pub evidence: Evidence,
+    }
+
+    /// Liability verdict waiting for the end of challenge period.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Settlement<BlockNumber, Balance, Evidence> {
+        /// Report verdict that will be applied when no dispute opened.
+        pub verdict: bool,
+        /// Last block when report could be disputed.
+        pub deadline: BlockNumber,
+        /// Opened dispute, if any.
+        pub dispute: Option<Dispute<Balance, Evidence>>,
+    }
+
+    /// Liability outcomes of promisor account.
+    #[derive(
+        Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+    )]
+    pub struct Reputation<Balance> {
+        /// Number of started liabilities.
+        pub created: u32,
+        /// Number of successfully finished liabilities.
+        pub completed: u32,
+        /// Number of failed liabilities.
+        pub failed: u32,
+        /// Number of liabilities expired without report.
+        pub expired: u32,
+        /// Number of disputed liability reports.
+        pub disputed: u32,
+        /// Total value of successfully finished liabilities.
+        pub settled: Balance,
+    }
+
+    /// Liability lifecycle status.
+    #[derive(
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    pub enum LiabilityStatus {
+        /// Liability is started and waits for report or settlement.
+        Open,
+        /// Liability is settled, expired or all its milestones are paid.
+        Finalized,
+    }
+
+    /// Account role in liability.
+    #[derive(
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    pub enum LiabilityRole {
+        /// The client account.
+        Promisee,
+        /// The executive account.
+        Promisor,
+    }
+
+    /// Side of market order.
+    #[derive(
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    pub enum OrderSide {
+        /// Promisee order, price is the maximal liability price.
+        Demand,
+        /// Promisor order, price is the minimal liability price.
+        Offer,
+    }
+
+    /// Market order signed by its owner, could be published by anyone.
+    #[derive(
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        Clone,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    pub struct Order<Technics, Balance, AccountId, BlockNumber, Signature> {
+        /// Demand or offer.
+        pub side: OrderSide,
+        /// Liability technical parameter.
+        pub technics: Technics,
+        /// Price limit of the order.
+        pub price: Balance,
+        /// Order owner account.
+        pub owner: AccountId,
+        /// Last block when order could be matched.
+        pub expires: BlockNumber,
+        /// Owner agreement nonce, protects order from replay.
+        pub nonce: AgreementNonce,
+        /// Owner signature of order parameters.
+        pub signature: Signature,
+    }
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -82,6 +516,51 @@ pub mod pallet {
 
         /// Liability report published.
         NewReport(Index, ReportFor<T>),
+
+        /// Liability report confirmed by validator: [index, validator, verdict].
+        ReportConfirmed(Index, T::AccountId, bool),
+
+        /// Liability verdict waits for challenge period: [index, verdict, deadline].
+        SettlementScheduled(Index, bool, BlockNumberFor<T>),
+
+        /// Liability report disputed by promisee: [index, promisee, evidence].
+        Disputed(Index, T::AccountId, T::Evidence),
+
+        /// Liability dispute resolved by arbiter: [index, verdict].
+        DisputeResolved(Index, bool),
+
+        /// Liability settled after challenge period: [index, verdict].
+        Settled(Index, bool),
+
+        /// Liability expired without report: [index].
+        Expired(Index),
+
+        /// Liability milestone reported by promisor: [index, milestone, payload].
+        MilestoneReported(Index, u32, ReportMessageFor<T>),
+
+        /// Liability milestone reached and paid: [index, milestone].
+        MilestonePaid(Index, u32),
+
+        /// Liability milestone report rejected, it could be reported again: [index, milestone].
+        MilestoneRejected(Index, u32),
+
+        /// All liability milestones paid: [index].
+        MilestonesCompleted(Index),
+
+        /// Market order placed: [order, side, technics hash, owner].
+        OrderPlaced(OrderIndex, OrderSide, T::Hash, T::AccountId),
+
+        /// Market order cancelled by owner: [order].
+        OrderCancelled(OrderIndex),
+
+        /// Market order removed after expiration: [order].
+        OrderExpired(OrderIndex),
+
+        /// Demand and offer matched into liability: [demand, offer, index].
+        OrdersMatched(OrderIndex, OrderIndex, Index),
+
+        /// Finalized liability pruned from storage: [index, agreement hash, report hash].
+        Pruned(Index, T::Hash, Option<T::Hash>),
     }
 
     #[pallet::error]
@@ -98,6 +577,68 @@ pub mod pallet {
         OracleIsNotReady,
         /// Unable to load agreement from storage.
         AgreementNotFound,
+        /// Agreement nonce already used by promisee.
+        AgreementNonceUsed,
+        /// Report of agreement with validator can't be confirmed by promisor.
+        ValidatorVerdictRequired,
+        /// Wrong validator account.
+        BadValidator,
+        /// Unable to load report from storage.
+        ReportNotFound,
+        /// Liability has no verdict waiting for settlement.
+        SettlementNotFound,
+        /// Only promisee could dispute liability report.
+        BadDisputant,
+        /// Liability report already disputed.
+        AlreadyDisputed,
+        /// Liability report isn't disputed.
+        NotDisputed,
+        /// Challenge period of liability report is over.
+        ChallengePeriodExpired,
+        /// Challenge period of liability report isn't over yet.
+        ChallengePeriodNotExpired,
+        /// Liability couldn't be settled until dispute resolution.
+        UnderDispute,
+        /// Liability deadline is passed.
+        AgreementExpired,
+        /// Liability deadline isn't passed yet.
+        NotExpired,
+        /// Liability with milestones should be reported by milestones.
+        MilestoneReportRequired,
+        /// Milestone report payload doesn't match milestone technics.
+        BadMilestoneReport,
+        /// Previous milestone report waits for verdict.
+        MilestonePending,
+        /// Liability has no milestones.
+        NoMilestones,
+        /// Milestone isn't the next one to be paid.
+        BadMilestone,
+        /// Unable to load market order from storage.
+        OrderNotFound,
+        /// Market order is expired.
+        OrderExpired,
+        /// Too many open orders for the same technics.
+        TooManyOrders,
+        /// Orders are not demand and offer of the same technics with acceptable price.
+        OrdersMismatch,
+        /// Market order proof verification failed.
+        BadOrderProof,
+        /// Only order owner could cancel not expired order.
+        BadOrderOwner,
+        /// Liability agreement couldn't be made of market orders.
+        OrdersNotSupported,
+        /// Promisor reputation isn't acceptable for agreement.
+        LowReputation,
+        /// Liability isn't finalized yet.
+        NotFinalized,
+        /// Retention period of finalized liability isn't over yet.
+        RetentionPeriodNotExpired,
+        /// Promisee and promisor of liability are the same account.
+        SelfDealing,
+        /// Too many placed orders wait for matching, order could be placed later.
+        MatchQueueFull,
+        /// Runtime reports couldn't wait for validator verdict.
+        ValidatorNotSupported,
     }
 
     #[pallet::storage]
@@ -115,10 +656,129 @@ pub mod pallet {
     /// Result of liability execution.
     pub(super) type ReportOf<T: Config> = StorageMap<_, Twox64Concat, Index, ReportFor<T>>;
 
+    #[pallet::storage]
+    #[pallet::getter(fn nonce_of)]
+    /// Next agreement nonce acceptable for promisee account.
+    pub(super) type NonceOf<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, AgreementNonce, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn settlement_of)]
+    /// Liability verdicts waiting for the end of challenge period.
+    pub(super) type SettlementOf<T: Config> = StorageMap<_, Twox64Concat, Index, SettlementFor<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn expired_of)]
+    /// Block number when liability was expired without report.
+    pub(super) type ExpiredOf<T: Config> = StorageMap<_, Twox64Concat, Index, BlockNumberFor<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn milestones_paid)]
+    /// Number of paid liability milestones.
+    pub(super) type MilestonesPaid<T: Config> = StorageMap<_, Twox64Concat, Index, u32, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn milestone_report_of)]
+    /// Milestone report waiting for validator verdict: [milestone, payload hash].
+    pub(super) type MilestoneReportOf<T: Config> =
+        StorageMap<_, Twox64Concat, Index, (u32, T::Hash)>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn promisee_liabilities)]
+    /// Liabilities of promisee account with its status.
+    pub(super) type PromiseeLiabilities<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, Index, LiabilityStatus>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn promisor_liabilities)]
+    /// Liabilities of promisor account with its status.
+    pub(super) type PromisorLiabilities<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, Index, LiabilityStatus>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn reputation_of)]
+    /// Liability outcomes of promisor account.
+    pub(super) type ReputationOf<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, ReputationFor<T>, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn deposit_of)]
+    /// Storage deposit of liability with account it was held from.
+    pub(super) type DepositOf<T: Config> =
+        StorageMap<_, Twox64Concat, Index, (T::AccountId, BalanceOf<T>)>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn finalized_at)]
+    /// Block number when liability was finalized.
+    pub(super) type FinalizedAt<T: Config> = StorageMap<_, Twox64Concat, Index, BlockNumberFor<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn next_order_index)]
+    /// Next market order index.
+    pub(super) type NextOrderIndex<T: Config> = StorageValue<_, OrderIndex, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn order_of)]
+    /// Open market orders.
+    pub(super) type OrderOf<T: Config> = StorageMap<_, Twox64Concat, OrderIndex, OrderFor<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn order_book)]
+    /// Open market orders by technics hash.
+    pub(super) type OrderBook<T: Config> =
+        StorageMap<_, Identity, T::Hash, BoundedVec<OrderIndex, T::MaxOrders>, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn order_deposit_of)]
+    /// Deposit of open market order with account it was held from.
+    pub(super) type OrderDepositOf<T: Config> =
+        StorageMap<_, Twox64Concat, OrderIndex, (T::AccountId, BalanceOf<T>)>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn matched_orders_of)]
+    /// Demand and offer orders matched into liability, their signatures prove agreement
+    /// parameters instead of agreement signatures.
+    pub(super) type MatchedOrdersOf<T: Config> =
+        StorageMap<_, Twox64Concat, Index, (OrderFor<T>, OrderFor<T>)>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn match_queue)]
+    /// Recently placed orders waiting for matching on idle.
+    pub(super) type MatchQueue<T: Config> =
+        StorageValue<_, BoundedVec<OrderIndex, T::MaxOrders>, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn reap_cursor)]
+    /// Technics hash of the order book reaped last on idle, reaping goes on from the next one.
+    pub(super) type ReapCursor<T: Config> = StorageValue<_, T::Hash>;
+
+    /// A reason for the pallet placing a hold on funds.
+    #[pallet::composite_enum]
+    pub enum HoldReason {
+        /// Liability price is held in escrow until liability finished.
+        #[codec(index = 0)]
+        Escrow,
+        /// Dispute bond is held from promisee until dispute resolved.
+        #[codec(index = 1)]
+        DisputeBond,
+        /// Storage deposit of liability or market order.
+        #[codec(index = 2)]
+        StorageDeposit,
+    }
+
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let mut consumed = Self::process_match_queue(remaining_weight);
+            consumed.saturating_accrue(Self::reap_expired_orders(
+                remaining_weight.saturating_sub(consumed),
+            ));
+            consumed
+        }
+    }
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::call]
@@ -127,26 +787,27 @@ pub mod pallet {
         #[pallet::weight(T::WeightInfo::create())]
         #[pallet::call_index(0)]
         pub fn create(origin: OriginFor<T>, agreement: T::Agreement) -> DispatchResultWithPostInfo {
-            let _ = ensure_signed(origin)?;
+            let sender = ensure_signed(origin)?;
 
-            ensure!(agreement.verify(), Error::<T>::BadAgreementProof);
-
-            // Start agreement processing
-            agreement.on_start()?;
+            // Agreement signatures are bound to this chain by genesis hash
+            let genesis_hash = <frame_system::Pallet<T>>::block_hash(BlockNumberFor::<T>::zero());
+            ensure!(
+                agreement.verify(genesis_hash.as_ref()),
+                Error::<T>::BadAgreementProof
+            );
 
-            // Store agreement on storage
-            let next_index = <NextIndex<T>>::get();
-            <AgreementOf<T>>::insert(next_index, agreement.clone());
-            <NextIndex<T>>::put(next_index + 1u32);
+            // Each agreement nonce could be used by promisee only once
+            let promisee = agreement.promisee();
+            let nonce = agreement.nonce();
+            ensure!(
+                nonce >= <NonceOf<T>>::get(&promisee),
+                Error::<T>::AgreementNonceUsed
+            );
+            let next_nonce = nonce.checked_add(1).ok_or(Error::<T>::AgreementNonceUsed)?;
 
-            // Emit event
-            Self::deposit_event(Event::NewLiability(
-                next_index,
-                agreement.technical(),
-                agreement.economical(),
-                agreement.promisee(),
-                agreement.promisor(),
-            ));
+            // Start agreement processing and store it on storage
+            Self::start_liability(agreement, &sender)?;
+            <NonceOf<T>>::insert(&promisee, next_nonce);
 
             Ok(().into())
         }
@@ -175,10 +836,27 @@ pub mod pallet {
                     Error::<T>::BadReportSender
                 );
 
+                // Liability with milestones is finalized by last milestone
+                ensure!(
+                    agreement.milestones() == 0,
+                    Error::<T>::MilestoneReportRequired
+                );
+
+                // Check report deadline
+                if let Some(deadline) = agreement.expires_at() {
+                    ensure!(
+                        <frame_system::Pallet<T>>::block_number() <= deadline,
+                        Error::<T>::AgreementExpired
+                    );
+                }
+
                 // Run agreement final processing
-                match report.is_confirmed() {
-                    None => Err(Error::<T>::OracleIsNotReady)?,
-                    Some(x) => agreement.on_finish(x)?,
+                match (agreement.validator(), report.is_confirmed()) {
+                    // Report is pending until validator verdict
+                    (Some(_), None) => (),
+                    (Some(_), Some(_)) => Err(Error::<T>::ValidatorVerdictRequired)?,
+                    (None, None) => Err(Error::<T>::OracleIsNotReady)?,
+                    (None, Some(x)) => Self::conclude(index, &agreement, x)?,
                 }
 
                 // Store report on storage
@@ -191,261 +869,3039 @@ pub mod pallet {
                 Err(Error::<T>::AgreementNotFound.into())
             }
         }
-    }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::economics::SimpleMarket;
-    use crate::signed::*;
-    use crate::technics::IPFS;
-    use crate::traits::*;
-    use crate::{self as liability, *};
-    use frame_support::{assert_err, assert_ok, derive_impl, parameter_types};
-    use hex_literal::hex;
-    use sp_core::{crypto::Pair, sr25519};
-    use sp_runtime::{
-        traits::{IdentifyAccount, IdentityLookup, Verify},
-        AccountId32, BuildStorage, MultiSignature,
-    };
+        /// Confirm or reject pending report by validator named in agreement.
+        #[pallet::weight(T::WeightInfo::confirm())]
+        #[pallet::call_index(2)]
+        pub fn confirm(
+            origin: OriginFor<T>,
+            index: Index,
+            verdict: bool,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+
+            let agreement = <AgreementOf<T>>::get(index).ok_or(Error::<T>::AgreementNotFound)?;
+            ensure!(
+                agreement.validator() == Some(sender.clone()),
+                Error::<T>::BadValidator
+            );
 
-    type Block = frame_system::mocking::MockBlock<Runtime>;
-    type Balance = u128;
+            // Milestone report goes to settlement with validator decision
+            if <MilestoneReportOf<T>>::take(index).is_some() {
+                Self::conclude(index, &agreement, verdict)?;
+                Self::deposit_event(Event::ReportConfirmed(index, sender, verdict));
+                return Ok(().into());
+            }
+
+            let mut report = <ReportOf<T>>::get(index).ok_or(Error::<T>::ReportNotFound)?;
+            ensure!(
+                report.is_confirmed().is_none(),
+                Error::<T>::AlreadyFinalized
+            );
 
-    const XRT: Balance = 1_000_000_000;
+            // Run agreement final processing with validator decision
+            report.confirm(verdict);
+            Self::conclude(index, &agreement, verdict)?;
+            <ReportOf<T>>::insert(index, report);
 
-    frame_support::construct_runtime!(
-        pub enum Runtime {
-            System: frame_system,
-            Balances: pallet_balances,
-            Liability: liability,
+            Self::deposit_event(Event::ReportConfirmed(index, sender, verdict));
+            Ok(().into())
         }
-    );
 
-    parameter_types! {
-        pub const BlockHashCount: u64 = 250;
-    }
-
-    #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
-    impl frame_system::Config for Runtime {
-        type Block = Block;
-        type AccountId = AccountId32;
-        type Lookup = IdentityLookup<Self::AccountId>;
-        type AccountData = pallet_balances::AccountData<Balance>;
-    }
-
-    parameter_types! {
-        pub const MaxLocks: u32 = 50;
-        pub const MaxReserves: u32 = 50;
-        pub const ExistentialDeposit: Balance = 10;
-    }
-
-    impl pallet_balances::Config for Runtime {
-        type MaxLocks = MaxLocks;
-        type MaxReserves = MaxReserves;
-        type ReserveIdentifier = [u8; 8];
-        type Balance = Balance;
-        type RuntimeEvent = RuntimeEvent;
-        type DustRemoval = ();
-        type ExistentialDeposit = ExistentialDeposit;
-        type AccountStore = System;
-        type WeightInfo = ();
-        type FreezeIdentifier = ();
-        type MaxFreezes = ();
-        type RuntimeHoldReason = ();
-        type RuntimeFreezeReason = ();
-        type DoneSlashHandler = ();
-    }
-
-    impl Config for Runtime {
-        type RuntimeEvent = RuntimeEvent;
-        type Agreement = SignedAgreement<
-            // Provide task in IPFS
-            IPFS,
-            // Liability has a price
-            SimpleMarket<Self::AccountId, Balances>,
-            // Use standard accounts
-            Self::AccountId,
-            // Use standard signatures
-            MultiSignature,
-        >;
-        type Report = SignedReport<
-            // Indexing liabilities
-            Index,
-            // Use standard accounts
-            Self::AccountId,
-            // Use standard signatures
-            MultiSignature,
-            // Provide report in IPFS
-            IPFS,
-        >;
-        type WeightInfo = weights::TestWeightInfo;
-    }
+        /// Dispute liability report by promisee during challenge period.
+        #[pallet::weight(T::WeightInfo::dispute())]
+        #[pallet::call_index(3)]
+        pub fn dispute(
+            origin: OriginFor<T>,
+            index: Index,
+            evidence: T::Evidence,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
 
-    // IPFS raw hash (sha256)
-    const IPFS_HASH: [u8; 32] =
-        hex!["30f3d649b3d140a6601e11a2cfbe3560e60dc5434f62d702ac8ceff4e1890015"];
+            let agreement = <AgreementOf<T>>::get(index).ok_or(Error::<T>::AgreementNotFound)?;
+            ensure!(agreement.promisee() == sender, Error::<T>::BadDisputant);
 
-    fn new_test_ext() -> sp_io::TestExternalities {
-        use sp_keyring::sr25519::Keyring;
+            let mut settlement =
+                <SettlementOf<T>>::get(index).ok_or(Error::<T>::SettlementNotFound)?;
+            ensure!(settlement.dispute.is_none(), Error::<T>::AlreadyDisputed);
+            ensure!(
+                <frame_system::Pallet<T>>::block_number() <= settlement.deadline,
+                Error::<T>::ChallengePeriodExpired
+            );
 
-        let storage = RuntimeGenesisConfig {
-            system: Default::default(),
-            balances: pallet_balances::GenesisConfig::<Runtime> {
-                balances: vec![
-                    (Keyring::Alice.into(), 100 * XRT),
-                    (Keyring::Bob.into(), 100 * XRT),
-                ],
-                dev_accounts: None,
-            },
+            // Hold dispute bond from promisee
+            let bond = T::DisputeBond::get();
+            T::Currency::hold(&HoldReason::DisputeBond.into(), &sender, bond)?;
+
+            settlement.dispute = Some(Dispute {
+                bond,
+                evidence: evidence.clone(),
+            });
+            <SettlementOf<T>>::insert(index, settlement);
+            if Self::is_reputable(&agreement) {
+                <ReputationOf<T>>::mutate(agreement.promisor(), |r| {
+                    r.disputed = r.disputed.saturating_add(1)
+                });
+            }
+
+            Self::deposit_event(Event::Disputed(index, sender, evidence));
+            Ok(().into())
         }
-        .build_storage()
-        .unwrap();
-        storage.into()
-    }
 
-    #[test]
-    fn test_initial_setup() {
-        new_test_ext().execute_with(|| {
-            assert_eq!(Liability::next_index(), 0);
-        });
-    }
+        /// Resolve disputed liability, `verdict` is the final result of execution.
+        ///
+        /// Dispute bond is returned to promisee when arbiter overturns report verdict,
+        /// otherwise it goes to promisor.
+        #[pallet::weight(T::WeightInfo::resolve())]
+        #[pallet::call_index(4)]
+        pub fn resolve(
+            origin: OriginFor<T>,
+            index: Index,
+            verdict: bool,
+        ) -> DispatchResultWithPostInfo {
+            T::ArbiterOrigin::ensure_origin(origin)?;
 
-    fn get_params_proof(
-        uri: &str,
-        technics: &TechnicsFor<Runtime>,
-        economics: &EconomicsFor<Runtime>,
-    ) -> (AccountId32, MultiSignature) {
-        let pair = sr25519::Pair::from_string(uri, None).unwrap();
-        let sender = <MultiSignature as Verify>::Signer::from(pair.public()).into_account();
-        let signature = <ProofSigner<_> as AgreementProofBuilder<_, _, _, _>>::proof(
-            technics, economics, &pair,
-        )
-        .into();
-        (sender, signature)
-    }
+            let agreement = <AgreementOf<T>>::get(index).ok_or(Error::<T>::AgreementNotFound)?;
+            let settlement = <SettlementOf<T>>::get(index).ok_or(Error::<T>::SettlementNotFound)?;
+            let dispute = settlement.dispute.ok_or(Error::<T>::NotDisputed)?;
 
-    fn get_report_proof(uri: &str, index: &u32, message: &IPFS) -> (AccountId32, MultiSignature) {
-        let pair = sr25519::Pair::from_string(uri, None).unwrap();
-        let sender = <MultiSignature as Verify>::Signer::from(pair.public()).into_account();
-        let signature =
-            <ProofSigner<_> as ReportProofBuilder<_, _, _, _>>::proof(index, message, &pair).into();
-        (sender, signature)
-    }
+            // Split dispute bond according to arbiter verdict
+            let promisee = agreement.promisee();
+            let reason = HoldReason::DisputeBond.into();
+            if verdict == settlement.verdict {
+                T::Currency::transfer_on_hold(
+                    &reason,
+                    &promisee,
+                    &agreement.promisor(),
+                    dispute.bond,
+                    Precision::BestEffort,
+                    Restriction::Free,
+                    Fortitude::Polite,
+                )?;
+            } else {
+                T::Currency::release(&reason, &promisee, dispute.bond, Precision::BestEffort)?;
+            }
 
-    #[test]
-    fn test_liability_proofs() {
-        let technics = IPFS {
-            hash: IPFS_HASH.into(),
-        };
-        let economics = SimpleMarket { price: 10 };
-        let (sender, signature) = get_params_proof("//Alice", &technics, &economics);
-        let agreement: <Runtime as Config>::Agreement = SignedAgreement {
-            technics,
-            economics,
-            promisee: sender.clone(),
-            promisor: sender.clone(),
-            promisee_signature: signature.clone(),
-            promisor_signature: signature.clone(),
-        };
-        assert_eq!(agreement.verify(), true);
+            // Run agreement final processing with arbiter decision
+            <SettlementOf<T>>::remove(index);
+            Self::apply_verdict(index, &agreement, verdict)?;
 
-        let index = 1;
-        let payload = IPFS {
-            hash: IPFS_HASH.into(),
-        };
-        let (sender, signature) = get_report_proof("//Alice", &index, &payload);
-        let report = SignedReport {
-            index,
-            sender,
-            payload,
-            signature,
-        };
-        assert_eq!(report.verify(), true);
-    }
+            Self::deposit_event(Event::DisputeResolved(index, verdict));
+            Ok(().into())
+        }
 
-    #[test]
-    fn test_liability_lifecycle() {
-        new_test_ext().execute_with(|| {
-            assert_eq!(Liability::next_index(), 0);
+        /// Settle undisputed liability when challenge period is over.
+        ///
+        /// Disputed liability that wasn't resolved by arbiter during resolution period
+        /// is settled with report verdict, dispute bond is returned to promisee.
+        #[pallet::weight(T::WeightInfo::settle())]
+        #[pallet::call_index(5)]
+        pub fn settle(origin: OriginFor<T>, index: Index) -> DispatchResultWithPostInfo {
+            let _ = ensure_signed(origin)?;
 
-            let technics = IPFS {
-                hash: IPFS_HASH.into(),
-            };
-            let economics = SimpleMarket { price: 10 * XRT };
+            let settlement = <SettlementOf<T>>::get(index).ok_or(Error::<T>::SettlementNotFound)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                now > settlement.deadline,
+                Error::<T>::ChallengePeriodNotExpired
+            );
 
-            let (alice, promisee_signature) = get_params_proof("//Alice", &technics, &economics);
-            let (bob, promisor_signature) = get_params_proof("//Bob", &technics, &economics);
+            let agreement = <AgreementOf<T>>::get(index).ok_or(Error::<T>::AgreementNotFound)?;
+            if let Some(dispute) = settlement.dispute {
+                ensure!(
+                    now > settlement
+                        .deadline
+                        .saturating_add(T::ResolutionPeriod::get()),
+                    Error::<T>::UnderDispute
+                );
+                T::Currency::release(
+                    &HoldReason::DisputeBond.into(),
+                    &agreement.promisee(),
+                    dispute.bond,
+                    Precision::BestEffort,
+                )?;
+            }
+            <SettlementOf<T>>::remove(index);
+            Self::apply_verdict(index, &agreement, settlement.verdict)?;
 
-            assert_eq!(System::account(&alice).data.free, 100 * XRT);
-            assert_eq!(System::account(&bob).data.free, 100 * XRT);
+            Self::deposit_event(Event::Settled(index, settlement.verdict));
+            Ok(().into())
+        }
 
-            let agreement = SignedAgreement {
-                technics,
-                economics,
+        /// Fail liability that wasn't reported until its deadline.
+        #[pallet::weight(T::WeightInfo::expire())]
+        #[pallet::call_index(6)]
+        pub fn expire(origin: OriginFor<T>, index: Index) -> DispatchResultWithPostInfo {
+            let _ = ensure_signed(origin)?;
+
+            let agreement = <AgreementOf<T>>::get(index).ok_or(Error::<T>::AgreementNotFound)?;
+            ensure!(
+                <ReportOf<T>>::get(index) == None && !<ExpiredOf<T>>::contains_key(index),
+                Error::<T>::AlreadyFinalized
+            );
+
+            let paid = <MilestonesPaid<T>>::get(index);
+            let milestones = agreement.milestones();
+            ensure!(
+                milestones == 0 || paid < milestones,
+                Error::<T>::AlreadyFinalized
+            );
+            // Reported milestone should be settled first
+            ensure!(
+                !<SettlementOf<T>>::contains_key(index),
+                Error::<T>::MilestonePending
+            );
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            match agreement.expires_at() {
+                Some(deadline) if now > deadline => (),
+                _ => Err(Error::<T>::NotExpired)?,
+            }
+
+            // Refund milestones that wasn't reached and run final processing as failed
+            for n in paid..milestones {
+                agreement.on_milestone(n, false)?;
+            }
+            agreement.on_finish(false)?;
+            <MilestoneReportOf<T>>::remove(index);
+            <ExpiredOf<T>>::insert(index, now);
+            Self::set_finalized(index, &agreement, None);
+
+            Self::deposit_event(Event::Expired(index));
+            Ok(().into())
+        }
+
+        /// Publish report of reached milestone.
+        ///
+        /// Milestone report goes the same way as liability report: it waits for verdict of
+        /// agreement validator, if any, and for the end of challenge period before payment.
+        #[pallet::weight(T::WeightInfo::report_milestone())]
+        #[pallet::call_index(7)]
+        pub fn report_milestone(
+            origin: OriginFor<T>,
+            index: Index,
+            n: u32,
+            payload: ReportMessageFor<T>,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+
+            let agreement = <AgreementOf<T>>::get(index).ok_or(Error::<T>::AgreementNotFound)?;
+            ensure!(sender == agreement.promisor(), Error::<T>::BadReportSender);
+
+            let milestones = agreement.milestones();
+            ensure!(milestones > 0, Error::<T>::NoMilestones);
+            let paid = <MilestonesPaid<T>>::get(index);
+            ensure!(
+                paid < milestones && !<ExpiredOf<T>>::contains_key(index),
+                Error::<T>::AlreadyFinalized
+            );
+            ensure!(n == paid, Error::<T>::BadMilestone);
+            ensure!(
+                !<MilestoneReportOf<T>>::contains_key(index)
+                    && !<SettlementOf<T>>::contains_key(index),
+                Error::<T>::MilestonePending
+            );
+            ensure!(
+                agreement.is_milestone_report(n, &payload),
+                Error::<T>::BadMilestoneReport
+            );
+
+            // Check report deadline
+            if let Some(deadline) = agreement.expires_at() {
+                ensure!(
+                    <frame_system::Pallet<T>>::block_number() <= deadline,
+                    Error::<T>::AgreementExpired
+                );
+            }
+
+            Self::deposit_event(Event::MilestoneReported(index, n, payload.clone()));
+            if agreement.validator().is_some() {
+                // Report is pending until validator verdict
+                <MilestoneReportOf<T>>::insert(index, (n, T::Hashing::hash_of(&payload)));
+            } else {
+                Self::conclude(index, &agreement, true)?;
+            }
+
+            Ok(().into())
+        }
+
+        /// Place market order signed by its owner.
+        #[pallet::weight(T::WeightInfo::place_order())]
+        #[pallet::call_index(8)]
+        pub fn place_order(origin: OriginFor<T>, order: OrderFor<T>) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(
+                <frame_system::Pallet<T>>::block_number() <= order.expires,
+                Error::<T>::OrderExpired
+            );
+
+            // Order nonce shares owner agreement nonce sequence
+            ensure!(
+                order.nonce >= <NonceOf<T>>::get(&order.owner),
+                Error::<T>::AgreementNonceUsed
+            );
+            let next_nonce = order
+                .nonce
+                .checked_add(1)
+                .ok_or(Error::<T>::AgreementNonceUsed)?;
+
+            ensure!(Self::verify_order(&order), Error::<T>::BadOrderProof);
+
+            let index = <NextOrderIndex<T>>::get();
+            let technics_hash = T::Hashing::hash_of(&order.technics);
+            <OrderBook<T>>::try_mutate(technics_hash, |book| book.try_push(index))
+                .map_err(|_| Error::<T>::TooManyOrders)?;
+            <MatchQueue<T>>::try_mutate(|queue| queue.try_push(index))
+                .map_err(|_| Error::<T>::MatchQueueFull)?;
+            let deposit = T::OrderDeposit::get();
+            if !deposit.is_zero() {
+                T::Currency::hold(&HoldReason::StorageDeposit.into(), &sender, deposit)?;
+                <OrderDepositOf<T>>::insert(index, (sender, deposit));
+            }
+
+            <NonceOf<T>>::insert(&order.owner, next_nonce);
+            <NextOrderIndex<T>>::put(index + 1);
+            <OrderOf<T>>::insert(index, order.clone());
+
+            Self::deposit_event(Event::OrderPlaced(
+                index,
+                order.side,
+                technics_hash,
+                order.owner,
+            ));
+            Ok(().into())
+        }
+
+        /// Cancel market order, expired orders could be removed by anyone.
+        #[pallet::weight(T::WeightInfo::cancel_order())]
+        #[pallet::call_index(9)]
+        pub fn cancel_order(origin: OriginFor<T>, index: OrderIndex) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+
+            let order = <OrderOf<T>>::get(index).ok_or(Error::<T>::OrderNotFound)?;
+            if sender == order.owner {
+                Self::remove_order(index, &order);
+                Self::deposit_event(Event::OrderCancelled(index));
+            } else {
+                ensure!(
+                    <frame_system::Pallet<T>>::block_number() > order.expires,
+                    Error::<T>::BadOrderOwner
+                );
+                Self::remove_order(index, &order);
+                Self::deposit_event(Event::OrderExpired(index));
+            }
+
+            Ok(().into())
+        }
+
+        /// Make liability of matching demand and offer orders.
+        #[pallet::weight(T::WeightInfo::match_orders())]
+        #[pallet::call_index(10)]
+        pub fn match_orders(
+            origin: OriginFor<T>,
+            demand: OrderIndex,
+            offer: OrderIndex,
+        ) -> DispatchResultWithPostInfo {
+            let _ = ensure_signed(origin)?;
+
+            let demand_order = <OrderOf<T>>::get(demand).ok_or(Error::<T>::OrderNotFound)?;
+            let offer_order = <OrderOf<T>>::get(offer).ok_or(Error::<T>::OrderNotFound)?;
+            Self::do_match(demand, demand_order, offer, offer_order)?;
+
+            Ok(().into())
+        }
+
+        /// Remove finalized liability after retention period and return its storage deposit.
+        #[pallet::weight(T::WeightInfo::prune())]
+        #[pallet::call_index(11)]
+        pub fn prune(origin: OriginFor<T>, index: Index) -> DispatchResultWithPostInfo {
+            let _ = ensure_signed(origin)?;
+
+            let agreement = <AgreementOf<T>>::get(index).ok_or(Error::<T>::AgreementNotFound)?;
+            let promisee = agreement.promisee();
+            ensure!(
+                <PromiseeLiabilities<T>>::get(&promisee, index) == Some(LiabilityStatus::Finalized),
+                Error::<T>::NotFinalized
+            );
+            let finalized_at = <FinalizedAt<T>>::get(index).ok_or(Error::<T>::NotFinalized)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                now > finalized_at.saturating_add(T::RetentionPeriod::get()),
+                Error::<T>::RetentionPeriodNotExpired
+            );
+
+            let report_hash = <ReportOf<T>>::take(index).map(|r| T::Hashing::hash_of(&r));
+            <AgreementOf<T>>::remove(index);
+            <ExpiredOf<T>>::remove(index);
+            <MilestonesPaid<T>>::remove(index);
+            <FinalizedAt<T>>::remove(index);
+            <PromiseeLiabilities<T>>::remove(&promisee, index);
+            <PromisorLiabilities<T>>::remove(agreement.promisor(), index);
+            <MatchedOrdersOf<T>>::remove(index);
+            if let Some((depositor, deposit)) = <DepositOf<T>>::take(index) {
+                T::Currency::release(
+                    &HoldReason::StorageDeposit.into(),
+                    &depositor,
+                    deposit,
+                    Precision::BestEffort,
+                )?;
+            }
+
+            Self::deposit_event(Event::Pruned(
+                index,
+                T::Hashing::hash_of(&agreement),
+                report_hash,
+            ));
+
+            Ok(().into())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Start liability processing, store it and emit its event,
+        /// storage deposit is held from `depositor`.
+        fn start_liability(
+            agreement: T::Agreement,
+            depositor: &T::AccountId,
+        ) -> Result<Index, DispatchError> {
+            let promisor = agreement.promisor();
+            ensure!(agreement.promisee() != promisor, Error::<T>::SelfDealing);
+            // Liability with validator would never get its report confirmed
+            ensure!(
+                agreement.validator().is_none() || <ReportFor<T> as RealWorldOracle>::VALIDATED,
+                Error::<T>::ValidatorNotSupported
+            );
+            ensure!(
+                agreement.is_acceptable(&<ReputationOf<T>>::get(&promisor)),
+                Error::<T>::LowReputation
+            );
+            agreement.on_start()?;
+            if Self::is_reputable(&agreement) {
+                <ReputationOf<T>>::mutate(&promisor, |r| r.created = r.created.saturating_add(1));
+            }
+
+            let index = <NextIndex<T>>::get();
+            <AgreementOf<T>>::insert(index, agreement.clone());
+            <NextIndex<T>>::put(index + 1u32);
+            let deposit = T::StorageDeposit::get();
+            if !deposit.is_zero() {
+                T::Currency::hold(&HoldReason::StorageDeposit.into(), depositor, deposit)?;
+                <DepositOf<T>>::insert(index, (depositor.clone(), deposit));
+            }
+            <PromiseeLiabilities<T>>::insert(agreement.promisee(), index, LiabilityStatus::Open);
+            <PromisorLiabilities<T>>::insert(agreement.promisor(), index, LiabilityStatus::Open);
+
+            Self::deposit_event(Event::NewLiability(
+                index,
+                agreement.technical(),
+                agreement.economical(),
+                agreement.promisee(),
+                agreement.promisor(),
+            ));
+            Ok(index)
+        }
+
+        /// Check market order proof, signatures are bound to this chain by genesis hash.
+        fn verify_order(order: &OrderFor<T>) -> bool {
+            #[cfg(feature = "runtime-benchmarks")]
+            {
+                // In benchmark mode, skip signature verification
+                let _ = order;
+                return true;
+            }
+
+            #[cfg(not(feature = "runtime-benchmarks"))]
+            {
+                let genesis_hash =
+                    <frame_system::Pallet<T>>::block_hash(BlockNumberFor::<T>::zero());
+                let encoded = crate::signed::order_payload(
+                    genesis_hash.as_ref(),
+                    &order.side,
+                    &order.technics,
+                    &order.price,
+                    &order.expires,
+                    &order.nonce,
+                );
+                order.signature.verify(&encoded[..], &order.owner)
+            }
+        }
+
+        /// Check liability proof, liability made of market orders is proven by their signatures.
+        pub fn verify_liability(index: Index) -> bool {
+            if let Some((demand, offer)) = <MatchedOrdersOf<T>>::get(index) {
+                return Self::verify_order(&demand) && Self::verify_order(&offer);
+            }
+            let genesis_hash = <frame_system::Pallet<T>>::block_hash(BlockNumberFor::<T>::zero());
+            <AgreementOf<T>>::get(index).map_or(false, |a| a.verify(genesis_hash.as_ref()))
+        }
+
+        /// Liabilities of zero value are free to make, so they don't affect promisor reputation.
+        fn is_reputable(agreement: &T::Agreement) -> bool {
+            !agreement.value().is_zero()
+        }
+
+        /// Mark liability as finalized in account indexes and account its outcome in
+        /// promisor reputation, `None` verdict means liability expired.
+        fn set_finalized(index: Index, agreement: &T::Agreement, verdict: Option<bool>) {
+            if Self::is_reputable(agreement) {
+                <ReputationOf<T>>::mutate(agreement.promisor(), |r| match verdict {
+                    Some(true) => {
+                        r.completed = r.completed.saturating_add(1);
+                        r.settled = r.settled.saturating_add(agreement.value());
+                    }
+                    Some(false) => r.failed = r.failed.saturating_add(1),
+                    None => r.expired = r.expired.saturating_add(1),
+                });
+            }
+            <PromiseeLiabilities<T>>::insert(
+                agreement.promisee(),
+                index,
+                LiabilityStatus::Finalized,
+            );
+            <PromisorLiabilities<T>>::insert(
+                agreement.promisor(),
+                index,
+                LiabilityStatus::Finalized,
+            );
+            <FinalizedAt<T>>::insert(index, <frame_system::Pallet<T>>::block_number());
+        }
+
+        /// Liabilities of account in given role filtered by status, paginated by
+        /// `offset` and `limit`.
+        pub fn liabilities_of(
+            account: &T::AccountId,
+            role: LiabilityRole,
+            status: Option<LiabilityStatus>,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<(Index, LiabilityStatus)> {
+            let filter = |(_, s): &(Index, LiabilityStatus)| status.map_or(true, |x| x == *s);
+            match role {
+                LiabilityRole::Promisee => <PromiseeLiabilities<T>>::iter_prefix(account)
+                    .filter(filter)
+                    .skip(offset as usize)
+                    .take(limit as usize)
+                    .collect(),
+                LiabilityRole::Promisor => <PromisorLiabilities<T>>::iter_prefix(account)
+                    .filter(filter)
+                    .skip(offset as usize)
+                    .take(limit as usize)
+                    .collect(),
+            }
+        }
+
+        /// Remove market order from storage and order book, order deposit is returned.
+        fn remove_order(index: OrderIndex, order: &OrderFor<T>) {
+            <OrderOf<T>>::remove(index);
+            if let Some((depositor, deposit)) = <OrderDepositOf<T>>::take(index) {
+                let _ = T::Currency::release(
+                    &HoldReason::StorageDeposit.into(),
+                    &depositor,
+                    deposit,
+                    Precision::BestEffort,
+                );
+            }
+            <OrderBook<T>>::mutate_exists(T::Hashing::hash_of(&order.technics), |book| {
+                if let Some(orders) = book {
+                    orders.retain(|i| *i != index);
+                    if orders.is_empty() {
+                        *book = None;
+                    }
+                }
+            });
+        }
+
+        /// Make liability of demand and offer, liability price is the offer price.
+        fn do_match(
+            demand: OrderIndex,
+            demand_order: OrderFor<T>,
+            offer: OrderIndex,
+            offer_order: OrderFor<T>,
+        ) -> Result<Index, DispatchError> {
+            ensure!(
+                demand_order.side == OrderSide::Demand
+                    && offer_order.side == OrderSide::Offer
+                    && demand_order.technics == offer_order.technics
+                    && offer_order.price <= demand_order.price,
+                Error::<T>::OrdersMismatch
+            );
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                now <= demand_order.expires && now <= offer_order.expires,
+                Error::<T>::OrderExpired
+            );
+
+            let agreement = T::Agreement::from_orders(
+                demand_order.technics.clone(),
+                offer_order.price,
+                (demand_order.owner.clone(), demand_order.signature.clone()),
+                (offer_order.owner.clone(), offer_order.signature.clone()),
+                demand_order.nonce,
+            )
+            .ok_or(Error::<T>::OrdersNotSupported)?;
+            // Liability of matched orders is submitted on behalf of demand owner
+            let index = Self::start_liability(agreement, &demand_order.owner)?;
+
+            Self::remove_order(demand, &demand_order);
+            Self::remove_order(offer, &offer_order);
+            // Agreement signatures are taken of orders, so orders are kept as its proof
+            <MatchedOrdersOf<T>>::insert(index, (demand_order, offer_order));
+
+            Self::deposit_event(Event::OrdersMatched(demand, offer, index));
+            Ok(index)
+        }
+
+        /// Match recently placed orders while block has enough weight.
+        fn process_match_queue(remaining_weight: Weight) -> Weight {
+            let mut consumed = T::DbWeight::get().reads(1);
+            if remaining_weight.any_lt(consumed) {
+                return Weight::zero();
+            }
+
+            let queue = <MatchQueue<T>>::get().into_inner();
+            if queue.is_empty() {
+                return consumed;
+            }
+            consumed.saturating_accrue(T::DbWeight::get().writes(1));
+
+            let step = T::WeightInfo::match_orders()
+                .saturating_add(T::DbWeight::get().reads(T::MaxOrders::get().into()));
+            let mut processed = 0;
+            for index in queue.iter() {
+                if remaining_weight.any_lt(consumed.saturating_add(step)) {
+                    break;
+                }
+                consumed.saturating_accrue(step);
+                Self::match_queued(*index);
+                processed += 1;
+            }
+            <MatchQueue<T>>::put(BoundedVec::truncate_from(queue[processed..].to_vec()));
+
+            consumed
+        }
+
+        /// Remove expired orders of order books following reap cursor.
+        ///
+        /// Each call goes on from the cursor up to the last order book, so books
+        /// take turns and expired order deposits are returned without `cancel_order`.
+        fn reap_expired_orders(remaining_weight: Weight) -> Weight {
+            let mut consumed = T::DbWeight::get().reads_writes(1, 1);
+            if remaining_weight.any_lt(consumed) {
+                return Weight::zero();
+            }
+
+            // Whole order book could be expired
+            let max_orders = T::MaxOrders::get();
+            let step = T::DbWeight::get()
+                .reads(max_orders.saturating_add(1).into())
+                .saturating_add(T::WeightInfo::cancel_order().saturating_mul(max_orders.into()));
+            let now = <frame_system::Pallet<T>>::block_number();
+            let mut cursor = <ReapCursor<T>>::get();
+            while consumed.saturating_add(step).all_lte(remaining_weight) {
+                let next = match cursor {
+                    Some(last) => {
+                        <OrderBook<T>>::iter_keys_from(<OrderBook<T>>::hashed_key_for(last)).next()
+                    }
+                    None => <OrderBook<T>>::iter_keys().next(),
+                };
+                let Some(technics_hash) = next else {
+                    cursor = None;
+                    break;
+                };
+                consumed.saturating_accrue(step);
+
+                for index in <OrderBook<T>>::get(technics_hash) {
+                    let Some(order) = <OrderOf<T>>::get(index) else {
+                        continue;
+                    };
+                    if now > order.expires {
+                        Self::remove_order(index, &order);
+                        Self::deposit_event(Event::OrderExpired(index));
+                    }
+                }
+                cursor = Some(technics_hash);
+            }
+
+            match cursor {
+                Some(last) => <ReapCursor<T>>::put(last),
+                None => <ReapCursor<T>>::kill(),
+            }
+            consumed
+        }
+
+        /// Match queued order with the best counter order of the same technics.
+        fn match_queued(index: OrderIndex) {
+            let Some(order) = <OrderOf<T>>::get(index) else {
+                return;
+            };
+            let now = <frame_system::Pallet<T>>::block_number();
+            if now > order.expires {
+                Self::remove_order(index, &order);
+                Self::deposit_event(Event::OrderExpired(index));
+                return;
+            }
+
+            // Cheapest offer for demand and the most expensive demand for offer
+            let mut best: Option<(OrderIndex, OrderFor<T>)> = None;
+            for counter in <OrderBook<T>>::get(T::Hashing::hash_of(&order.technics)) {
+                let Some(counter_order) = <OrderOf<T>>::get(counter) else {
+                    continue;
+                };
+                let acceptable = match order.side {
+                    OrderSide::Demand => counter_order.price <= order.price,
+                    OrderSide::Offer => counter_order.price >= order.price,
+                };
+                if !acceptable
+                    || counter_order.side == order.side
+                    || counter_order.technics != order.technics
+                    || now > counter_order.expires
+                {
+                    continue;
+                }
+                let better = match (&order.side, &best) {
+                    (OrderSide::Demand, Some((_, b))) => counter_order.price < b.price,
+                    (OrderSide::Offer, Some((_, b))) => counter_order.price > b.price,
+                    (_, None) => true,
+                };
+                if better {
+                    best = Some((counter, counter_order));
+                }
+            }
+
+            let Some((counter, counter_order)) = best else {
+                return;
+            };
+            let _ = frame_support::storage::with_storage_layer(|| match order.side {
+                OrderSide::Demand => Self::do_match(index, order, counter, counter_order),
+                OrderSide::Offer => Self::do_match(counter, counter_order, index, order),
+            });
+        }
+
+        /// Finish agreement processing or hold verdict until the end of challenge period.
+        fn conclude(index: Index, agreement: &T::Agreement, verdict: bool) -> DispatchResult {
+            let period = T::ChallengePeriod::get();
+            if period.is_zero() {
+                return Self::apply_verdict(index, agreement, verdict);
+            }
+
+            let deadline = <frame_system::Pallet<T>>::block_number().saturating_add(period);
+            <SettlementOf<T>>::insert(
+                index,
+                Settlement {
+                    verdict,
+                    deadline,
+                    dispute: None,
+                },
+            );
+            Self::deposit_event(Event::SettlementScheduled(index, verdict, deadline));
+            Ok(())
+        }
+
+        /// Run agreement final processing with verdict, liability with milestones
+        /// gets verdict on its reported milestone.
+        fn apply_verdict(index: Index, agreement: &T::Agreement, verdict: bool) -> DispatchResult {
+            let milestones = agreement.milestones();
+            if milestones == 0 {
+                agreement.on_finish(verdict)?;
+                Self::set_finalized(index, agreement, Some(verdict));
+                return Ok(());
+            }
+
+            // Rejected milestone could be reported again until deadline
+            let n = <MilestonesPaid<T>>::get(index);
+            if !verdict {
+                Self::deposit_event(Event::MilestoneRejected(index, n));
+                return Ok(());
+            }
+
+            // Release milestone payment
+            agreement.on_milestone(n, true)?;
+            <MilestonesPaid<T>>::insert(index, n + 1);
+            Self::deposit_event(Event::MilestonePaid(index, n));
+
+            // Last milestone finalizes liability
+            if n + 1 == milestones {
+                agreement.on_finish(true)?;
+                Self::set_finalized(index, agreement, Some(true));
+                Self::deposit_event(Event::MilestonesCompleted(index));
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::economics::SimpleMarket;
+    use crate::mock::{
+        simple_market::{
+            new_test_ext, ChallengePeriod, Liability, OrderDeposit, RetentionPeriod, Runtime,
+            RuntimeOrigin, StorageDeposit, System,
+        },
+        Balance, XRT,
+    };
+    use crate::signed::*;
+    use crate::technics::IPFS;
+    use crate::traits::*;
+    use crate::*;
+    use frame_support::{assert_err, assert_ok};
+    use hex_literal::hex;
+    use sp_core::{crypto::Pair, sr25519};
+    use sp_runtime::{
+        traits::{Hash, IdentifyAccount, Verify},
+        AccountId32, MultiSignature,
+    };
+
+    // IPFS raw hash (sha256)
+    const IPFS_HASH: [u8; 32] =
+        hex!["30f3d649b3d140a6601e11a2cfbe3560e60dc5434f62d702ac8ceff4e1890015"];
+
+    #[test]
+    fn test_initial_setup() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(Liability::next_index(), 0);
+        });
+    }
+
+    #[test]
+    fn test_technics_cid() {
+        use crate::technics::{Technics, RAW, SHA2_256};
+
+        // CIDv0 is converted to CIDv1 of DAG-PB codec
+        let technics =
+            Technics::from_cid_str("QmbWqxBEKC3P8tqsKc98xmWNzrzDtRLMiMPL8wBuTGsMnR").unwrap();
+        assert_eq!(
+            technics,
+            Technics::from(IPFS {
+                hash: hex!["c3c4733ec8affd06cf9e9ff50ffc6bcd2ec85a6170004bb709669c31de94391a"]
+                    .into(),
+            })
+        );
+        let cid = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+        assert_eq!(technics.to_cid_string().unwrap(), cid);
+        assert_eq!(Technics::from_cid_str(cid), Some(technics.clone()));
+        assert_eq!(
+            Technics::from_cid_bytes(&technics.to_cid_bytes().unwrap()),
+            Some(technics)
+        );
+
+        let raw = Technics::cid(
+            RAW,
+            SHA2_256,
+            &hex!["ae297ac7654c7c2bc56d6d86831d19f371886927deeb3a984cb0d9fde91eb1b2"],
+        )
+        .unwrap();
+        let cid = "bafkreifoff5mozkmpqv4k3lnq2br2gptogegsj665m5jqtfq3h66shvrwi";
+        assert_eq!(raw.to_cid_string().unwrap(), cid);
+        assert_eq!(Technics::from_cid_str(cid), Some(raw));
+
+        // Only content identifiers have CID
+        let inline = Technics::Inline(b"move to (1, 2)".to_vec().try_into().unwrap());
+        assert_eq!(inline.to_cid_string(), None);
+        assert_eq!(Technics::CpsNode(42).to_cid_bytes(), None);
+
+        assert_eq!(Technics::from_cid_str("x123"), None);
+        assert_eq!(Technics::from_cid_bytes(&[1, 0x55, 0x12, 0x20, 0]), None);
+        assert_eq!(Technics::cid(RAW, SHA2_256, &[0u8; 65]), None);
+    }
+
+    #[test]
+    fn test_agreement_payload_encoding() {
+        use crate::technics::Technics;
+
+        // Same vector is checked by libcps agreement signing
+        let technics =
+            Technics::from_cid_str("bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi")
+                .unwrap();
+        let economics: EconomicsFor<Runtime> = SimpleMarket { price: 10 };
+        let payload =
+            agreement_payload(&[1u8; 32], &technics, &economics, &None::<AccountId32>, &7);
+        assert_eq!(
+            payload,
+            [
+                &hex!["800101010101010101010101010101010101010101010101010101010101010101"][..],
+                &hex!["00c1014880"],
+                &hex!["c3c4733ec8affd06cf9e9ff50ffc6bcd2ec85a6170004bb709669c31de94391a"],
+                &hex!["28000700000000000000"],
+            ]
+            .concat()
+        );
+    }
+
+    fn genesis_hash() -> Vec<u8> {
+        System::block_hash(0).as_ref().to_vec()
+    }
+
+    fn get_params_proof(
+        uri: &str,
+        domain: &[u8],
+        technics: &TechnicsFor<Runtime>,
+        economics: &EconomicsFor<Runtime>,
+        nonce: AgreementNonce,
+    ) -> (AccountId32, MultiSignature) {
+        let pair = sr25519::Pair::from_string(uri, None).unwrap();
+        let sender = <MultiSignature as Verify>::Signer::from(pair.public()).into_account();
+        let signature = <ProofSigner<_> as AgreementProofBuilder<_, _, AccountId32, _, _>>::proof(
+            domain, technics, economics, &None, &nonce, &pair,
+        )
+        .into();
+        (sender, signature)
+    }
+
+    fn get_report_proof(uri: &str, index: &u32, message: &IPFS) -> (AccountId32, MultiSignature) {
+        let pair = sr25519::Pair::from_string(uri, None).unwrap();
+        let sender = <MultiSignature as Verify>::Signer::from(pair.public()).into_account();
+        let signature =
+            <ProofSigner<_> as ReportProofBuilder<_, _, _, _>>::proof(index, message, &pair).into();
+        (sender, signature)
+    }
+
+    #[test]
+    fn test_liability_proofs() {
+        let technics = IPFS {
+            hash: IPFS_HASH.into(),
+        };
+        let economics = SimpleMarket { price: 10 };
+        let domain = [1u8; 32];
+        let (sender, signature) = get_params_proof("//Alice", &domain, &technics, &economics, 0);
+        let agreement: <Runtime as Config>::Agreement = SignedAgreement {
+            technics,
+            economics,
+            promisee: sender.clone(),
+            promisor: sender.clone(),
+            validator: None,
+            nonce: 0,
+            promisee_signature: signature.clone(),
+            promisor_signature: signature.clone(),
+        };
+        assert_eq!(agreement.verify(&domain), true);
+        assert_eq!(agreement.verify(&[2u8; 32]), false);
+        assert_eq!(
+            SignedAgreement {
+                nonce: 1,
+                ..agreement.clone()
+            }
+            .verify(&domain),
+            false
+        );
+
+        let index = 1;
+        let payload = IPFS {
+            hash: IPFS_HASH.into(),
+        };
+        let (sender, signature) = get_report_proof("//Alice", &index, &payload);
+        let report = SignedReport {
+            index,
+            sender,
+            payload,
+            signature,
+        };
+        assert_eq!(report.verify(), true);
+    }
+
+    #[test]
+    fn test_simple_market_refund() {
+        new_test_ext().execute_with(|| {
+            let technics = IPFS {
+                hash: IPFS_HASH.into(),
+            };
+            let economics = SimpleMarket { price: 10 * XRT };
+            let (alice, signature) = get_params_proof("//Alice", &[], &technics, &economics, 0);
+            let (bob, _) = get_params_proof("//Bob", &[], &technics, &economics, 0);
+            let agreement: <Runtime as Config>::Agreement = SignedAgreement {
+                technics,
+                economics,
+                promisee: alice.clone(),
+                promisor: bob.clone(),
+                validator: None,
+                nonce: 0,
+                promisee_signature: signature.clone(),
+                promisor_signature: signature,
+            };
+
+            assert_ok!(agreement.on_start());
+            assert_eq!(System::account(&alice).data.reserved, 10 * XRT);
+
+            // Failed liability unreserves the whole price and reports success
+            assert_ok!(agreement.on_finish(false));
+            assert_eq!(System::account(&alice).data.free, 100 * XRT);
+            assert_eq!(System::account(&alice).data.reserved, 0);
+            assert_eq!(System::account(&bob).data.free, 100 * XRT);
+
+            // Nothing left to unreserve is an error
+            assert_err!(
+                agreement.on_finish(false),
+                sp_runtime::DispatchError::Other("reserved less than expected"),
+            );
+        })
+    }
+
+    #[test]
+    fn test_validator_not_supported() {
+        new_test_ext().execute_with(|| {
+            let technics = IPFS {
+                hash: IPFS_HASH.into(),
+            };
+            let economics = SimpleMarket { price: 10 * XRT };
+            let validator = Some(AccountId32::new([3; 32]));
+            let domain = genesis_hash();
+            let proof = |uri: &str| {
+                let pair = sr25519::Pair::from_string(uri, None).unwrap();
+                let signature: MultiSignature =
+                    <ProofSigner<_> as AgreementProofBuilder<_, _, _, _, _>>::proof(
+                        &domain[..],
+                        &technics,
+                        &economics,
+                        &validator,
+                        &0,
+                        &pair,
+                    )
+                    .into();
+                (
+                    <MultiSignature as Verify>::Signer::from(pair.public()).into_account(),
+                    signature,
+                )
+            };
+            let (alice, promisee_signature) = proof("//Alice");
+            let (bob, promisor_signature) = proof("//Bob");
+
+            // Signed report is never pending, so validator couldn't confirm it
+            assert_err!(
+                Liability::create(
+                    RuntimeOrigin::signed(bob.clone()),
+                    SignedAgreement {
+                        technics: technics.clone(),
+                        economics: economics.clone(),
+                        promisee: alice.clone(),
+                        promisor: bob,
+                        validator: validator.clone(),
+                        nonce: 0,
+                        promisee_signature,
+                        promisor_signature,
+                    }
+                ),
+                Error::<Runtime>::ValidatorNotSupported,
+            );
+            assert_eq!(Liability::next_index(), 0);
+            assert_eq!(System::account(&alice).data.free, 100 * XRT);
+        })
+    }
+
+    #[test]
+    fn test_reputation_pumping() {
+        new_test_ext().execute_with(|| {
+            let technics = IPFS {
+                hash: IPFS_HASH.into(),
+            };
+            let domain = genesis_hash();
+
+            // Promisor couldn't make liability with itself
+            let economics = SimpleMarket { price: 10 * XRT };
+            let (alice, signature) = get_params_proof("//Alice", &domain, &technics, &economics, 0);
+            assert_err!(
+                Liability::create(
+                    RuntimeOrigin::signed(alice.clone()),
+                    SignedAgreement {
+                        technics: technics.clone(),
+                        economics,
+                        promisee: alice.clone(),
+                        promisor: alice.clone(),
+                        validator: None,
+                        nonce: 0,
+                        promisee_signature: signature.clone(),
+                        promisor_signature: signature,
+                    }
+                ),
+                Error::<Runtime>::SelfDealing,
+            );
+
+            // Liability of zero value is free to make, so it doesn't make reputation
+            let economics = SimpleMarket { price: 0 };
+            let (_, promisee_signature) =
+                get_params_proof("//Alice", &domain, &technics, &economics, 0);
+            let (bob, promisor_signature) =
+                get_params_proof("//Bob", &domain, &technics, &economics, 0);
+            assert_ok!(Liability::create(
+                RuntimeOrigin::signed(bob.clone()),
+                SignedAgreement {
+                    technics: technics.clone(),
+                    economics,
+                    promisee: alice,
+                    promisor: bob.clone(),
+                    validator: None,
+                    nonce: 0,
+                    promisee_signature,
+                    promisor_signature,
+                }
+            ));
+            let (_, signature) = get_report_proof("//Bob", &0, &technics);
+            assert_ok!(Liability::finalize(
+                RuntimeOrigin::signed(bob.clone()),
+                SignedReport {
+                    index: 0,
+                    sender: bob.clone(),
+                    payload: technics,
+                    signature,
+                }
+            ));
+            assert_eq!(
+                Liability::promisor_liabilities(&bob, 0),
+                Some(LiabilityStatus::Finalized)
+            );
+            assert_eq!(Liability::reputation_of(&bob), Default::default());
+        })
+    }
+
+    #[test]
+    fn test_liability_lifecycle() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(Liability::next_index(), 0);
+
+            let technics = IPFS {
+                hash: IPFS_HASH.into(),
+            };
+            let economics = SimpleMarket { price: 10 * XRT };
+
+            let domain = genesis_hash();
+            let (alice, promisee_signature) =
+                get_params_proof("//Alice", &domain, &technics, &economics, 0);
+            let (bob, promisor_signature) =
+                get_params_proof("//Bob", &domain, &technics, &economics, 0);
+
+            assert_eq!(System::account(&alice).data.free, 100 * XRT);
+            assert_eq!(System::account(&bob).data.free, 100 * XRT);
+
+            let agreement = SignedAgreement {
+                technics,
+                economics,
+                promisee: alice.clone(),
+                promisor: bob.clone(),
+                validator: None,
+                nonce: 0,
+                promisee_signature: promisor_signature.clone(),
+                promisor_signature,
+            };
+
+            assert_err!(
+                Liability::create(
+                    RuntimeOrigin::signed(agreement.promisor.clone()),
+                    agreement.clone()
+                ),
+                Error::<Runtime>::BadAgreementProof,
+            );
+            assert_eq!(Liability::next_index(), 0);
+            assert_eq!(System::account(&alice).data.free, 100 * XRT);
+            assert_eq!(System::account(&bob).data.free, 100 * XRT);
+
+            let agreement = SignedAgreement {
+                promisee_signature,
+                ..agreement
+            };
+            assert_ok!(Liability::create(
+                RuntimeOrigin::signed(agreement.promisor.clone()),
+                agreement.clone()
+            ),);
+            assert_eq!(Liability::next_index(), 1);
+            assert_eq!(Liability::report_of(0), None);
+            assert_eq!(Liability::agreement_of(0), Some(agreement));
+            assert_eq!(System::account(&alice).data.free, 90 * XRT);
+            assert_eq!(System::account(&bob).data.free, 100 * XRT);
+
+            let index = 0u32;
+            let payload = IPFS {
+                hash: IPFS_HASH.into(),
+            };
+            let (_, bad_signature) = get_report_proof("//Alice", &index, &payload);
+            let (sender, signature) = get_report_proof("//Bob", &index, &payload);
+
+            let report = SignedReport {
+                index,
+                sender,
+                payload,
+                signature: bad_signature,
+            };
+            assert_err!(
+                Liability::finalize(RuntimeOrigin::signed(report.sender.clone()), report.clone()),
+                Error::<Runtime>::BadReportProof,
+            );
+            assert_eq!(Liability::report_of(0), None);
+            assert_eq!(System::account(&alice).data.free, 90 * XRT);
+            assert_eq!(System::account(&bob).data.free, 100 * XRT);
+
+            let report = SignedReport {
+                signature,
+                ..report.clone()
+            };
+            assert_ok!(Liability::finalize(
+                RuntimeOrigin::signed(report.sender.clone()),
+                report.clone()
+            ));
+            assert_eq!(Liability::report_of(0), Some(report));
+            assert_eq!(System::account(&alice).data.free, 90 * XRT);
+            assert_eq!(System::account(&bob).data.free, 110 * XRT);
+        })
+    }
+
+    #[test]
+    fn test_agreement_replay() {
+        new_test_ext().execute_with(|| {
+            let technics = IPFS {
+                hash: IPFS_HASH.into(),
+            };
+            let economics = SimpleMarket { price: 10 * XRT };
+
+            let domain = genesis_hash();
+            let (alice, promisee_signature) =
+                get_params_proof("//Alice", &domain, &technics, &economics, 0);
+            let (bob, promisor_signature) =
+                get_params_proof("//Bob", &domain, &technics, &economics, 0);
+            let agreement = SignedAgreement {
+                technics: technics.clone(),
+                economics: economics.clone(),
+                promisee: alice.clone(),
+                promisor: bob.clone(),
+                validator: None,
+                nonce: 0,
+                promisee_signature,
+                promisor_signature,
+            };
+
+            // Signature made for another chain is not valid here
+            let (_, foreign_signature) =
+                get_params_proof("//Alice", &[0u8; 32], &technics, &economics, 0);
+            assert_err!(
+                Liability::create(
+                    RuntimeOrigin::signed(bob.clone()),
+                    SignedAgreement {
+                        promisee_signature: foreign_signature,
+                        ..agreement.clone()
+                    }
+                ),
+                Error::<Runtime>::BadAgreementProof,
+            );
+
+            assert_ok!(Liability::create(
+                RuntimeOrigin::signed(bob.clone()),
+                agreement.clone()
+            ));
+            assert_eq!(Liability::nonce_of(&alice), 1);
+            assert_eq!(System::account(&alice).data.free, 90 * XRT);
+
+            // The same signed agreement can't lock promisee funds again
+            assert_err!(
+                Liability::create(RuntimeOrigin::signed(bob.clone()), agreement),
+                Error::<Runtime>::AgreementNonceUsed,
+            );
+            assert_eq!(Liability::next_index(), 1);
+            assert_eq!(System::account(&alice).data.free, 90 * XRT);
+
+            // Fresh nonce makes a new agreement
+            let (_, promisee_signature) =
+                get_params_proof("//Alice", &domain, &technics, &economics, 1);
+            let (_, promisor_signature) =
+                get_params_proof("//Bob", &domain, &technics, &economics, 1);
+            assert_ok!(Liability::create(
+                RuntimeOrigin::signed(bob.clone()),
+                SignedAgreement {
+                    technics,
+                    economics,
+                    promisee: alice.clone(),
+                    promisor: bob,
+                    validator: None,
+                    nonce: 1,
+                    promisee_signature,
+                    promisor_signature,
+                }
+            ));
+            assert_eq!(Liability::nonce_of(&alice), 2);
+            assert_eq!(System::account(&alice).data.free, 80 * XRT);
+        })
+    }
+
+    #[test]
+    fn test_account_liabilities() {
+        new_test_ext().execute_with(|| {
+            let technics = IPFS {
+                hash: IPFS_HASH.into(),
+            };
+            let economics = SimpleMarket { price: 10 * XRT };
+            let domain = genesis_hash();
+
+            for nonce in 0..3 {
+                let (alice, promisee_signature) =
+                    get_params_proof("//Alice", &domain, &technics, &economics, nonce);
+                let (bob, promisor_signature) =
+                    get_params_proof("//Bob", &domain, &technics, &economics, nonce);
+                assert_ok!(Liability::create(
+                    RuntimeOrigin::signed(bob.clone()),
+                    SignedAgreement {
+                        technics: technics.clone(),
+                        economics: economics.clone(),
+                        promisee: alice,
+                        promisor: bob,
+                        validator: None,
+                        nonce,
+                        promisee_signature,
+                        promisor_signature,
+                    }
+                ));
+            }
+
+            let payload = IPFS {
+                hash: IPFS_HASH.into(),
+            };
+            let (bob, signature) = get_report_proof("//Bob", &1, &payload);
+            assert_ok!(Liability::finalize(
+                RuntimeOrigin::signed(bob.clone()),
+                SignedReport {
+                    index: 1,
+                    sender: bob.clone(),
+                    payload,
+                    signature,
+                }
+            ));
+            let alice = Liability::agreement_of(0).unwrap().promisee;
+
+            assert_eq!(
+                Liability::promisee_liabilities(&alice, 1),
+                Some(LiabilityStatus::Finalized)
+            );
+            assert_eq!(
+                Liability::promisor_liabilities(&bob, 0),
+                Some(LiabilityStatus::Open)
+            );
+            assert_eq!(Liability::promisor_liabilities(&alice, 0), None);
+
+            let mut open = Liability::liabilities_of(
+                &bob,
+                LiabilityRole::Promisor,
+                Some(LiabilityStatus::Open),
+                0,
+                10,
+            );
+            open.sort_by_key(|(index, _)| *index);
+            assert_eq!(
+                open,
+                vec![(0, LiabilityStatus::Open), (2, LiabilityStatus::Open)]
+            );
+            assert_eq!(
+                Liability::liabilities_of(
+                    &alice,
+                    LiabilityRole::Promisee,
+                    Some(LiabilityStatus::Finalized),
+                    0,
+                    10
+                ),
+                vec![(1, LiabilityStatus::Finalized)],
+            );
+            assert_eq!(
+                Liability::liabilities_of(&bob, LiabilityRole::Promisee, None, 0, 10),
+                vec![],
+            );
+
+            // Pages don't overlap and cover all account liabilities
+            let mut pages = vec![];
+            for offset in 0..3 {
+                let page =
+                    Liability::liabilities_of(&alice, LiabilityRole::Promisee, None, offset, 1);
+                assert_eq!(page.len(), 1);
+                pages.extend(page);
+            }
+            pages.sort_by_key(|(index, _)| *index);
+            assert_eq!(
+                pages.into_iter().map(|(i, _)| i).collect::<Vec<_>>(),
+                vec![0, 1, 2]
+            );
+            assert!(
+                Liability::liabilities_of(&alice, LiabilityRole::Promisee, None, 3, 1).is_empty()
+            );
+        })
+    }
+
+    #[test]
+    fn test_liability_prune() {
+        new_test_ext().execute_with(|| {
+            StorageDeposit::set(XRT);
+            RetentionPeriod::set(10);
+            System::set_block_number(1);
+
+            let technics = IPFS {
+                hash: IPFS_HASH.into(),
+            };
+            let economics = SimpleMarket { price: 10 * XRT };
+            let domain = genesis_hash();
+            let (alice, promisee_signature) =
+                get_params_proof("//Alice", &domain, &technics, &economics, 0);
+            let (bob, promisor_signature) =
+                get_params_proof("//Bob", &domain, &technics, &economics, 0);
+            let agreement = SignedAgreement {
+                technics,
+                economics,
                 promisee: alice.clone(),
                 promisor: bob.clone(),
-                promisee_signature: promisor_signature.clone(),
+                validator: None,
+                nonce: 0,
+                promisee_signature,
                 promisor_signature,
             };
+            assert_ok!(Liability::create(
+                RuntimeOrigin::signed(bob.clone()),
+                agreement.clone()
+            ));
+            assert_eq!(Liability::deposit_of(0), Some((bob.clone(), XRT)));
+            assert_eq!(System::account(&bob).data.reserved, XRT);
+            assert_err!(
+                Liability::prune(RuntimeOrigin::signed(alice.clone()), 0),
+                Error::<Runtime>::NotFinalized,
+            );
+
+            let payload = IPFS {
+                hash: IPFS_HASH.into(),
+            };
+            let (_, signature) = get_report_proof("//Bob", &0, &payload);
+            let report = SignedReport {
+                index: 0,
+                sender: bob.clone(),
+                payload,
+                signature,
+            };
+            assert_ok!(Liability::finalize(
+                RuntimeOrigin::signed(bob.clone()),
+                report.clone()
+            ));
+            assert_eq!(Liability::finalized_at(0), Some(1));
+
+            System::set_block_number(11);
+            assert_err!(
+                Liability::prune(RuntimeOrigin::signed(alice.clone()), 0),
+                Error::<Runtime>::RetentionPeriodNotExpired,
+            );
+
+            // Anyone could prune liability, deposit returns to submitter
+            System::set_block_number(12);
+            assert_ok!(Liability::prune(RuntimeOrigin::signed(alice.clone()), 0));
+            System::assert_last_event(
+                Event::<Runtime>::Pruned(
+                    0,
+                    <Runtime as frame_system::Config>::Hashing::hash_of(&agreement),
+                    Some(<Runtime as frame_system::Config>::Hashing::hash_of(&report)),
+                )
+                .into(),
+            );
+            assert_eq!(System::account(&bob).data.reserved, 0);
+            assert_eq!(System::account(&bob).data.free, 110 * XRT);
+            assert_eq!(Liability::agreement_of(0), None);
+            assert_eq!(Liability::report_of(0), None);
+            assert_eq!(Liability::deposit_of(0), None);
+            assert_eq!(Liability::finalized_at(0), None);
+            assert_eq!(Liability::promisee_liabilities(&alice, 0), None);
+            assert_eq!(Liability::promisor_liabilities(&bob, 0), None);
+            assert_err!(
+                Liability::prune(RuntimeOrigin::signed(alice), 0),
+                Error::<Runtime>::AgreementNotFound,
+            );
+        })
+    }
+
+    #[test]
+    fn test_liability_dispute() {
+        new_test_ext().execute_with(|| {
+            ChallengePeriod::set(10);
+            System::set_block_number(1);
+
+            let technics = IPFS {
+                hash: IPFS_HASH.into(),
+            };
+            let economics = SimpleMarket { price: 10 * XRT };
+            let payload = IPFS {
+                hash: IPFS_HASH.into(),
+            };
+            let domain = genesis_hash();
+
+            // Create and report liability with given nonce
+            let make_liability = |nonce: AgreementNonce| -> Index {
+                let (alice, promisee_signature) =
+                    get_params_proof("//Alice", &domain, &technics, &economics, nonce);
+                let (bob, promisor_signature) =
+                    get_params_proof("//Bob", &domain, &technics, &economics, nonce);
+                let index = Liability::next_index();
+                assert_ok!(Liability::create(
+                    RuntimeOrigin::signed(bob.clone()),
+                    SignedAgreement {
+                        technics: technics.clone(),
+                        economics: economics.clone(),
+                        promisee: alice,
+                        promisor: bob.clone(),
+                        validator: None,
+                        nonce,
+                        promisee_signature,
+                        promisor_signature,
+                    }
+                ));
+                let (sender, signature) = get_report_proof("//Bob", &index, &payload);
+                assert_ok!(Liability::finalize(
+                    RuntimeOrigin::signed(bob),
+                    SignedReport {
+                        index,
+                        sender,
+                        payload: payload.clone(),
+                        signature,
+                    }
+                ));
+                index
+            };
+            let alice: AccountId32 = sp_keyring::sr25519::Keyring::Alice.into();
+            let bob: AccountId32 = sp_keyring::sr25519::Keyring::Bob.into();
+
+            // Payment is held during challenge period
+            let index = make_liability(0);
+            assert_eq!(
+                Liability::settlement_of(index),
+                Some(Settlement {
+                    verdict: true,
+                    deadline: 11,
+                    dispute: None,
+                })
+            );
+            assert_eq!(System::account(&alice).data.free, 90 * XRT);
+            assert_eq!(System::account(&bob).data.free, 100 * XRT);
+            assert_err!(
+                Liability::settle(RuntimeOrigin::signed(bob.clone()), index),
+                Error::<Runtime>::ChallengePeriodNotExpired,
+            );
+
+            // Only promisee could dispute the report
+            assert_err!(
+                Liability::dispute(RuntimeOrigin::signed(bob.clone()), index, payload.clone()),
+                Error::<Runtime>::BadDisputant,
+            );
+            assert_ok!(Liability::dispute(
+                RuntimeOrigin::signed(alice.clone()),
+                index,
+                payload.clone()
+            ));
+            assert_eq!(System::account(&alice).data.free, 85 * XRT);
+            assert_err!(
+                Liability::dispute(RuntimeOrigin::signed(alice.clone()), index, payload.clone()),
+                Error::<Runtime>::AlreadyDisputed,
+            );
+
+            // Disputed liability waits for arbiter
+            System::set_block_number(12);
+            assert_err!(
+                Liability::settle(RuntimeOrigin::signed(bob.clone()), index),
+                Error::<Runtime>::UnderDispute,
+            );
+            assert_err!(
+                Liability::resolve(RuntimeOrigin::signed(alice.clone()), index, false),
+                sp_runtime::DispatchError::BadOrigin,
+            );
+
+            // Arbiter overturns the report: escrow and bond are returned to promisee
+            assert_ok!(Liability::resolve(RuntimeOrigin::root(), index, false));
+            assert_eq!(Liability::settlement_of(index), None);
+            assert_eq!(System::account(&alice).data.free, 100 * XRT);
+            assert_eq!(System::account(&alice).data.reserved, 0);
+            assert_eq!(System::account(&bob).data.free, 100 * XRT);
+
+            // Arbiter upholds the report: escrow and bond go to promisor
+            let index = make_liability(1);
+            assert_ok!(Liability::dispute(
+                RuntimeOrigin::signed(alice.clone()),
+                index,
+                payload.clone()
+            ));
+            assert_ok!(Liability::resolve(RuntimeOrigin::root(), index, true));
+            assert_eq!(System::account(&alice).data.free, 85 * XRT);
+            assert_eq!(System::account(&alice).data.reserved, 0);
+            assert_eq!(System::account(&bob).data.free, 115 * XRT);
+
+            // Undisputed liability settled after challenge period
+            let index = make_liability(2);
+            assert_err!(
+                Liability::resolve(RuntimeOrigin::root(), index, false),
+                Error::<Runtime>::NotDisputed,
+            );
+            System::set_block_number(23);
+            assert_err!(
+                Liability::dispute(RuntimeOrigin::signed(alice.clone()), index, payload.clone()),
+                Error::<Runtime>::ChallengePeriodExpired,
+            );
+            assert_ok!(Liability::settle(RuntimeOrigin::signed(bob.clone()), index));
+            assert_eq!(Liability::settlement_of(index), None);
+            assert_eq!(System::account(&alice).data.free, 75 * XRT);
+            assert_eq!(System::account(&bob).data.free, 125 * XRT);
+            assert_eq!(
+                Liability::reputation_of(&bob),
+                Reputation {
+                    created: 3,
+                    completed: 2,
+                    failed: 1,
+                    expired: 0,
+                    disputed: 2,
+                    settled: 20 * XRT,
+                }
+            );
+            assert_err!(
+                Liability::settle(RuntimeOrigin::signed(bob), index),
+                Error::<Runtime>::SettlementNotFound,
+            );
+        })
+    }
+
+    #[test]
+    fn test_dispute_resolution_timeout() {
+        new_test_ext().execute_with(|| {
+            ChallengePeriod::set(10);
+            System::set_block_number(1);
+
+            let technics = IPFS {
+                hash: IPFS_HASH.into(),
+            };
+            let economics = SimpleMarket { price: 10 * XRT };
+            let payload = IPFS {
+                hash: IPFS_HASH.into(),
+            };
+            let domain = genesis_hash();
+            let (alice, promisee_signature) =
+                get_params_proof("//Alice", &domain, &technics, &economics, 0);
+            let (bob, promisor_signature) =
+                get_params_proof("//Bob", &domain, &technics, &economics, 0);
+            assert_ok!(Liability::create(
+                RuntimeOrigin::signed(bob.clone()),
+                SignedAgreement {
+                    technics,
+                    economics,
+                    promisee: alice.clone(),
+                    promisor: bob.clone(),
+                    validator: None,
+                    nonce: 0,
+                    promisee_signature,
+                    promisor_signature,
+                }
+            ));
+            let (sender, signature) = get_report_proof("//Bob", &0, &payload);
+            assert_ok!(Liability::finalize(
+                RuntimeOrigin::signed(bob.clone()),
+                SignedReport {
+                    index: 0,
+                    sender,
+                    payload: payload.clone(),
+                    signature,
+                }
+            ));
+            assert_ok!(Liability::dispute(
+                RuntimeOrigin::signed(alice.clone()),
+                0,
+                payload
+            ));
+            assert_eq!(System::account(&alice).data.reserved, 15 * XRT);
+
+            // Arbiter has resolution period after challenge period
+            System::set_block_number(21);
+            assert_err!(
+                Liability::settle(RuntimeOrigin::signed(bob.clone()), 0),
+                Error::<Runtime>::UnderDispute,
+            );
+
+            // Unresolved dispute falls back to report verdict, bond is returned
+            System::set_block_number(22);
+            assert_ok!(Liability::settle(RuntimeOrigin::signed(bob.clone()), 0));
+            System::assert_last_event(Event::<Runtime>::Settled(0, true).into());
+            assert_eq!(Liability::settlement_of(0), None);
+            assert_eq!(System::account(&alice).data.free, 90 * XRT);
+            assert_eq!(System::account(&alice).data.reserved, 0);
+            assert_eq!(System::account(&bob).data.free, 110 * XRT);
+            assert_err!(
+                Liability::resolve(RuntimeOrigin::root(), 0, false),
+                Error::<Runtime>::SettlementNotFound,
+            );
+        })
+    }
+
+    fn get_order(
+        uri: &str,
+        side: OrderSide,
+        price: Balance,
+        expires: u64,
+        nonce: AgreementNonce,
+    ) -> OrderFor<Runtime> {
+        let pair = sr25519::Pair::from_string(uri, None).unwrap();
+        let owner = <MultiSignature as Verify>::Signer::from(pair.public()).into_account();
+        let technics = IPFS {
+            hash: IPFS_HASH.into(),
+        };
+        let payload = order_payload(&genesis_hash(), &side, &technics, &price, &expires, &nonce);
+        Order {
+            side,
+            technics,
+            price,
+            owner,
+            expires,
+            nonce,
+            signature: pair.sign(&payload).into(),
+        }
+    }
+
+    #[test]
+    fn test_order_matching() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+
+            let demand = get_order("//Alice", OrderSide::Demand, 20 * XRT, 10, 0);
+            let alice = demand.owner.clone();
+            let bob = get_order("//Bob", OrderSide::Offer, 0, 0, 0).owner;
+
+            // Order price is signed by owner
+            assert_err!(
+                Liability::place_order(
+                    RuntimeOrigin::signed(bob.clone()),
+                    Order {
+                        price: 30 * XRT,
+                        ..demand.clone()
+                    }
+                ),
+                Error::<Runtime>::BadOrderProof,
+            );
+
+            assert_ok!(Liability::place_order(
+                RuntimeOrigin::signed(bob.clone()),
+                demand.clone()
+            ));
+            assert_eq!(Liability::nonce_of(&alice), 1);
+            assert_eq!(Liability::order_of(0), Some(demand.clone()));
+
+            // The same signed order can't be placed again
+            assert_err!(
+                Liability::place_order(RuntimeOrigin::signed(bob.clone()), demand),
+                Error::<Runtime>::AgreementNonceUsed,
+            );
+
+            let expensive = get_order("//Bob", OrderSide::Offer, 30 * XRT, 10, 0);
+            let cheap = get_order("//Bob", OrderSide::Offer, 10 * XRT, 10, 1);
+            assert_ok!(Liability::place_order(
+                RuntimeOrigin::signed(bob.clone()),
+                expensive
+            ));
+            assert_ok!(Liability::place_order(
+                RuntimeOrigin::signed(bob.clone()),
+                cheap
+            ));
+            let technics_hash = <Runtime as frame_system::Config>::Hashing::hash_of(&IPFS {
+                hash: IPFS_HASH.into(),
+            });
+            assert_eq!(
+                Liability::order_book(technics_hash).into_inner(),
+                vec![0, 1, 2]
+            );
+
+            // Offer price is out of demand price range
+            assert_err!(
+                Liability::match_orders(RuntimeOrigin::signed(bob.clone()), 0, 1),
+                Error::<Runtime>::OrdersMismatch,
+            );
+            // Demand and offer are swapped
+            assert_err!(
+                Liability::match_orders(RuntimeOrigin::signed(bob.clone()), 2, 0),
+                Error::<Runtime>::OrdersMismatch,
+            );
+
+            assert_ok!(Liability::match_orders(
+                RuntimeOrigin::signed(bob.clone()),
+                0,
+                2
+            ));
+            System::assert_last_event(Event::<Runtime>::OrdersMatched(0, 2, 0).into());
+            assert_eq!(Liability::order_of(0), None);
+            assert_eq!(Liability::order_of(2), None);
+            assert_eq!(Liability::order_book(technics_hash).into_inner(), vec![1]);
+
+            let agreement = Liability::agreement_of(0).unwrap();
+            assert_eq!(agreement.promisee, alice);
+            assert_eq!(agreement.promisor, bob);
+            assert_eq!(agreement.economics.price, 10 * XRT);
+            assert_eq!(System::account(&alice).data.free, 90 * XRT);
+        })
+    }
+
+    #[test]
+    fn test_order_expiry() {
+        use frame_support::traits::Hooks;
+
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+
+            let demand = get_order("//Alice", OrderSide::Demand, 20 * XRT, 5, 0);
+            let alice = demand.owner.clone();
+            let bob = get_order("//Bob", OrderSide::Offer, 0, 0, 0).owner;
+
+            // Only owner could cancel order until expiration
+            assert_ok!(Liability::place_order(
+                RuntimeOrigin::signed(alice.clone()),
+                demand
+            ));
+            assert_err!(
+                Liability::cancel_order(RuntimeOrigin::signed(bob.clone()), 0),
+                Error::<Runtime>::BadOrderOwner,
+            );
+            assert_ok!(Liability::cancel_order(
+                RuntimeOrigin::signed(alice.clone()),
+                0
+            ));
+            System::assert_last_event(Event::<Runtime>::OrderCancelled(0).into());
+            assert_eq!(Liability::order_of(0), None);
+
+            // Expired order could be removed by anyone
+            let demand = get_order("//Alice", OrderSide::Demand, 20 * XRT, 5, 1);
+            assert_ok!(Liability::place_order(
+                RuntimeOrigin::signed(alice.clone()),
+                demand
+            ));
+            System::set_block_number(6);
+            assert_ok!(Liability::cancel_order(
+                RuntimeOrigin::signed(bob.clone()),
+                1
+            ));
+            System::assert_last_event(Event::<Runtime>::OrderExpired(1).into());
+
+            assert_err!(
+                Liability::place_order(
+                    RuntimeOrigin::signed(alice.clone()),
+                    get_order("//Alice", OrderSide::Demand, 20 * XRT, 5, 2)
+                ),
+                Error::<Runtime>::OrderExpired,
+            );
+
+            // Placed orders are matched with the cheapest offer on idle
+            for order in [
+                get_order("//Alice", OrderSide::Demand, 20 * XRT, 20, 2),
+                get_order("//Bob", OrderSide::Offer, 15 * XRT, 20, 0),
+                get_order("//Bob", OrderSide::Offer, 12 * XRT, 20, 1),
+            ] {
+                assert_ok!(Liability::place_order(
+                    RuntimeOrigin::signed(bob.clone()),
+                    order
+                ));
+            }
+            Liability::on_idle(6, frame_support::weights::Weight::MAX);
+            assert!(Liability::match_queue().is_empty());
+            assert_eq!(Liability::order_of(2), None);
+            assert!(Liability::order_of(3).is_some());
+            assert_eq!(Liability::order_of(4), None);
+            assert_eq!(
+                Liability::agreement_of(0).unwrap().economics.price,
+                12 * XRT
+            );
+            assert_eq!(System::account(&alice).data.free, 88 * XRT);
+        })
+    }
+
+    #[test]
+    fn test_expired_orders_reaped_on_idle() {
+        use frame_support::traits::Hooks;
+
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            OrderDeposit::set(XRT);
+
+            let bob = get_order("//Bob", OrderSide::Offer, 0, 0, 0).owner;
+            for order in [
+                get_order("//Bob", OrderSide::Offer, 10 * XRT, 5, 0),
+                get_order("//Bob", OrderSide::Offer, 12 * XRT, 20, 1),
+            ] {
+                assert_ok!(Liability::place_order(
+                    RuntimeOrigin::signed(bob.clone()),
+                    order
+                ));
+            }
+
+            // Orders without counter orders stay in order book
+            Liability::on_idle(1, frame_support::weights::Weight::MAX);
+            assert!(Liability::match_queue().is_empty());
+            assert_eq!(Liability::reap_cursor(), None);
+            let technics_hash = <Runtime as frame_system::Config>::Hashing::hash_of(&IPFS {
+                hash: IPFS_HASH.into(),
+            });
+            assert_eq!(
+                Liability::order_book(technics_hash).into_inner(),
+                vec![0, 1]
+            );
+            assert_eq!(System::account(&bob).data.reserved, 2 * XRT);
+
+            // Expired order is removed without cancellation
+            System::set_block_number(6);
+            Liability::on_idle(6, frame_support::weights::Weight::MAX);
+            System::assert_last_event(Event::<Runtime>::OrderExpired(0).into());
+            assert_eq!(Liability::order_of(0), None);
+            assert!(Liability::order_of(1).is_some());
+            assert_eq!(Liability::order_book(technics_hash).into_inner(), vec![1]);
+            assert_eq!(System::account(&bob).data.reserved, XRT);
+            assert_eq!(Liability::reap_cursor(), None);
+        })
+    }
+
+    #[test]
+    fn test_match_queue_full() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+
+            let bob = get_order("//Bob", OrderSide::Offer, 0, 0, 0).owner;
+            MatchQueue::<Runtime>::put(frame_support::BoundedVec::truncate_from(
+                (100..116).collect::<Vec<_>>(),
+            ));
+
+            // Order isn't placed when it couldn't be matched on idle
+            assert_err!(
+                Liability::place_order(
+                    RuntimeOrigin::signed(bob.clone()),
+                    get_order("//Bob", OrderSide::Offer, 10 * XRT, 5, 0)
+                ),
+                Error::<Runtime>::MatchQueueFull,
+            );
+            assert_eq!(Liability::order_of(0), None);
+            assert_eq!(Liability::nonce_of(&bob), 0);
+
+            MatchQueue::<Runtime>::kill();
+            assert_ok!(Liability::place_order(
+                RuntimeOrigin::signed(bob.clone()),
+                get_order("//Bob", OrderSide::Offer, 10 * XRT, 5, 0)
+            ));
+            assert_eq!(Liability::match_queue().into_inner(), vec![0]);
+        })
+    }
+
+    #[test]
+    fn test_order_deposit() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            OrderDeposit::set(XRT);
+
+            let demand = get_order("//Alice", OrderSide::Demand, 20 * XRT, 5, 0);
+            let alice = demand.owner.clone();
+            let bob = get_order("//Bob", OrderSide::Offer, 0, 0, 0).owner;
+
+            // Deposit is returned to submitter when order cancelled
+            assert_ok!(Liability::place_order(
+                RuntimeOrigin::signed(alice.clone()),
+                demand
+            ));
+            assert_eq!(Liability::order_deposit_of(0), Some((alice.clone(), XRT)));
+            assert_eq!(System::account(&alice).data.reserved, XRT);
+            assert_ok!(Liability::cancel_order(
+                RuntimeOrigin::signed(alice.clone()),
+                0
+            ));
+            assert_eq!(Liability::order_deposit_of(0), None);
+            assert_eq!(System::account(&alice).data.reserved, 0);
+
+            // and when expired order removed by anyone
+            assert_ok!(Liability::place_order(
+                RuntimeOrigin::signed(bob.clone()),
+                get_order("//Bob", OrderSide::Offer, 10 * XRT, 5, 0)
+            ));
+            assert_eq!(System::account(&bob).data.reserved, XRT);
+            System::set_block_number(6);
+            assert_ok!(Liability::cancel_order(
+                RuntimeOrigin::signed(alice.clone()),
+                1
+            ));
+            assert_eq!(System::account(&bob).data.reserved, 0);
+
+            // and when orders matched
+            let demand = get_order("//Alice", OrderSide::Demand, 20 * XRT, 20, 1);
+            let offer = get_order("//Bob", OrderSide::Offer, 10 * XRT, 20, 1);
+            for order in [demand.clone(), offer.clone()] {
+                assert_ok!(Liability::place_order(
+                    RuntimeOrigin::signed(bob.clone()),
+                    order
+                ));
+            }
+            assert_eq!(System::account(&bob).data.reserved, 2 * XRT);
+            assert_ok!(Liability::match_orders(
+                RuntimeOrigin::signed(bob.clone()),
+                2,
+                3
+            ));
+            assert_eq!(System::account(&bob).data.reserved, 0);
+            assert_eq!(System::account(&alice).data.reserved, 10 * XRT);
+
+            // Matched liability is proven by orders instead of agreement signatures
+            assert_eq!(Liability::matched_orders_of(0), Some((demand, offer)));
+            assert!(!Liability::agreement_of(0).unwrap().verify(&genesis_hash()));
+            assert!(Liability::verify_liability(0));
+
+            // Deposit couldn't be held from poor submitter
+            let poor = AccountId32::new([7; 32]);
+            assert!(Liability::place_order(
+                RuntimeOrigin::signed(poor),
+                get_order("//Alice", OrderSide::Demand, 20 * XRT, 20, 2)
+            )
+            .is_err());
+        })
+    }
+
+    type AgreementV0 = migration::OldSignedAgreement<
+        IPFS,
+        SimpleMarket<AccountId32, crate::mock::simple_market::Balances>,
+        AccountId32,
+        MultiSignature,
+    >;
+
+    #[frame_support::storage_alias]
+    type AgreementOfV0 = StorageMap<Liability, frame_support::Twox64Concat, Index, AgreementV0>;
+
+    #[test]
+    fn test_migration_to_v1() {
+        use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+        use sp_keyring::sr25519::Keyring;
+
+        new_test_ext().execute_with(|| {
+            let technics = IPFS {
+                hash: IPFS_HASH.into(),
+            };
+            let economics = SimpleMarket { price: 10 * XRT };
+            let signature = MultiSignature::Sr25519(sr25519::Signature::from_raw([1; 64]));
+            StorageVersion::new(0).put::<Liability>();
+            for index in 0..2 {
+                AgreementOfV0::insert(
+                    index,
+                    AgreementV0 {
+                        technics: technics.clone(),
+                        economics: economics.clone(),
+                        promisee: Keyring::Alice.into(),
+                        promisor: Keyring::Bob.into(),
+                        promisee_signature: signature.clone(),
+                        promisor_signature: signature.clone(),
+                    },
+                );
+            }
+
+            migration::MigrationToV1::<Runtime, AgreementV0, <Runtime as Config>::Agreement>::on_runtime_upgrade();
+            assert_eq!(Liability::on_chain_storage_version(), 1);
+            for index in 0..2 {
+                assert_eq!(
+                    Liability::agreement_of(index),
+                    Some(SignedAgreement {
+                        technics: technics.clone(),
+                        economics: economics.clone(),
+                        promisee: Keyring::Alice.into(),
+                        promisor: Keyring::Bob.into(),
+                        validator: None,
+                        nonce: 0,
+                        promisee_signature: signature.clone(),
+                        promisor_signature: signature.clone(),
+                    })
+                );
+            }
+        })
+    }
+
+    #[test]
+    fn test_migration_to_v2() {
+        use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+        use sp_keyring::sr25519::Keyring;
+
+        new_test_ext().execute_with(|| {
+            let signature = MultiSignature::Sr25519(sr25519::Signature::from_raw([1; 64]));
+            let bob: AccountId32 = Keyring::Bob.into();
+            StorageVersion::new(1).put::<Liability>();
+            for index in 0..2 {
+                AgreementOf::<Runtime>::insert(
+                    index,
+                    SignedAgreement {
+                        technics: IPFS {
+                            hash: IPFS_HASH.into(),
+                        },
+                        economics: SimpleMarket { price: 10 * XRT },
+                        promisee: Keyring::Alice.into(),
+                        promisor: bob.clone(),
+                        validator: None,
+                        nonce: index as AgreementNonce,
+                        promisee_signature: signature.clone(),
+                        promisor_signature: signature.clone(),
+                    },
+                );
+            }
+            // Liability finished before finalization block was tracked
+            ExpiredOf::<Runtime>::insert(0, 1);
+
+            System::set_block_number(5);
+            migration::MigrationToV2::<Runtime, <Runtime as Config>::Agreement>::on_runtime_upgrade(
+            );
+            assert_eq!(Liability::on_chain_storage_version(), 2);
+            assert_eq!(
+                Liability::promisor_liabilities(&bob, 0),
+                Some(LiabilityStatus::Finalized)
+            );
+            assert_eq!(Liability::reputation_of(&bob).expired, 1);
 
+            // Retention period of finished liability starts at upgrade
+            assert_eq!(Liability::finalized_at(0), Some(5));
+            assert_eq!(Liability::finalized_at(1), None);
+            RetentionPeriod::set(10);
+            System::set_block_number(15);
             assert_err!(
-                Liability::create(
-                    RuntimeOrigin::signed(agreement.promisor.clone()),
-                    agreement.clone()
-                ),
-                Error::<Runtime>::BadAgreementProof,
+                Liability::prune(RuntimeOrigin::signed(bob.clone()), 0),
+                Error::<Runtime>::RetentionPeriodNotExpired,
             );
-            assert_eq!(Liability::next_index(), 0);
-            assert_eq!(System::account(&alice).data.free, 100 * XRT);
-            assert_eq!(System::account(&bob).data.free, 100 * XRT);
+            System::set_block_number(16);
+            assert_ok!(Liability::prune(RuntimeOrigin::signed(bob.clone()), 0));
+            assert_err!(
+                Liability::prune(RuntimeOrigin::signed(bob), 1),
+                Error::<Runtime>::NotFinalized,
+            );
+        })
+    }
 
-            let agreement = SignedAgreement {
-                promisee_signature,
-                ..agreement
+    mod technics_cid {
+        use super::*;
+        use crate::mock::technics_cid::{new_test_ext, Balances, Liability, Runtime};
+        use crate::technics::Technics;
+        use frame_support::{
+            traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+            Twox64Concat,
+        };
+        use sp_keyring::sr25519::Keyring;
+
+        type Economics = SimpleMarket<AccountId32, Balances>;
+        type AgreementV0 =
+            migration::OldSignedAgreement<IPFS, Economics, AccountId32, MultiSignature>;
+        type AgreementV2 = SignedAgreement<IPFS, Economics, AccountId32, MultiSignature>;
+        type OrderV2 = Order<IPFS, Balance, AccountId32, u64, MultiSignature>;
+
+        #[frame_support::storage_alias]
+        type AgreementOfV0 = StorageMap<Liability, Twox64Concat, Index, AgreementV0>;
+
+        #[frame_support::storage_alias]
+        type AgreementOfV2 = StorageMap<Liability, Twox64Concat, Index, AgreementV2>;
+
+        #[frame_support::storage_alias]
+        type OrderOfV2 = StorageMap<Liability, Twox64Concat, OrderIndex, OrderV2>;
+
+        #[test]
+        fn test_migration_to_v3() {
+            new_test_ext().execute_with(|| {
+                let ipfs = IPFS {
+                    hash: IPFS_HASH.into(),
+                };
+                let economics = SimpleMarket { price: 10 * XRT };
+                let signature = MultiSignature::Sr25519(sr25519::Signature::from_raw([1; 64]));
+                let alice: AccountId32 = Keyring::Alice.into();
+                let bob: AccountId32 = Keyring::Bob.into();
+
+                // Agreement stored before nonce was introduced
+                StorageVersion::new(0).put::<Liability>();
+                AgreementOfV0::insert(
+                    0,
+                    AgreementV0 {
+                        technics: ipfs.clone(),
+                        economics: economics.clone(),
+                        promisee: alice.clone(),
+                        promisor: bob.clone(),
+                        promisee_signature: signature.clone(),
+                        promisor_signature: signature.clone(),
+                    },
+                );
+
+                migration::MigrationToV1::<Runtime, AgreementV0, AgreementV2>::on_runtime_upgrade();
+                let agreement = AgreementOfV2::get(0).unwrap();
+                assert_eq!(agreement.technics, ipfs);
+                assert_eq!(agreement.nonce, 0);
+
+                migration::MigrationToV2::<Runtime, AgreementV2>::on_runtime_upgrade();
+                assert_eq!(Liability::on_chain_storage_version(), 2);
+                assert_eq!(
+                    Liability::promisor_liabilities(&bob, 0),
+                    Some(LiabilityStatus::Open)
+                );
+                assert_eq!(Liability::reputation_of(&bob).created, 1);
+
+                // Order placed with IPFS technics
+                OrderOfV2::insert(
+                    0,
+                    OrderV2 {
+                        side: OrderSide::Demand,
+                        technics: ipfs.clone(),
+                        price: 10 * XRT,
+                        owner: alice.clone(),
+                        expires: 10,
+                        nonce: 1,
+                        signature: signature.clone(),
+                    },
+                );
+
+                migration::MigrationToV3::<
+                    Runtime,
+                    migration::OldTechnics<AgreementV2>,
+                    migration::OldTechnics<OrderV2>,
+                >::on_runtime_upgrade();
+                assert_eq!(Liability::on_chain_storage_version(), 3);
+                let technics = Technics::from(ipfs);
+                assert_eq!(
+                    Liability::agreement_of(0),
+                    Some(SignedAgreement {
+                        technics: technics.clone(),
+                        economics,
+                        promisee: alice.clone(),
+                        promisor: bob,
+                        validator: None,
+                        nonce: 0,
+                        promisee_signature: signature.clone(),
+                        promisor_signature: signature.clone(),
+                    })
+                );
+                let order = Liability::order_of(0).unwrap();
+                assert_eq!(order.technics, technics);
+                assert_eq!(order.owner, alice);
+                let technics_hash = <Runtime as frame_system::Config>::Hashing::hash_of(&technics);
+                assert_eq!(Liability::order_book(technics_hash).to_vec(), vec![0]);
+            })
+        }
+    }
+
+    mod collateralized_market {
+        use super::*;
+        use crate::economics::CollateralizedMarket;
+
+        use crate::mock::collateralized_market::{
+            new_test_ext, Liability, Runtime, RuntimeOrigin, System,
+        };
+
+        fn create_liability(nonce: AgreementNonce, deadline: u64) -> Index {
+            let technics = IPFS {
+                hash: IPFS_HASH.into(),
+            };
+            let economics: EconomicsFor<Runtime> = CollateralizedMarket {
+                price: 10 * XRT,
+                collateral: 20 * XRT,
+                deadline,
             };
+            let domain = System::block_hash(0);
+            let proof = |uri: &str| -> (AccountId32, MultiSignature) {
+                let pair = sr25519::Pair::from_string(uri, None).unwrap();
+                let sender = <MultiSignature as Verify>::Signer::from(pair.public()).into_account();
+                let signature = <ProofSigner<_> as AgreementProofBuilder<
+                    _,
+                    _,
+                    AccountId32,
+                    _,
+                    _,
+                >>::proof(
+                    domain.as_ref(), &technics, &economics, &None, &nonce, &pair
+                )
+                .into();
+                (sender, signature)
+            };
+            let (alice, promisee_signature) = proof("//Alice");
+            let (bob, promisor_signature) = proof("//Bob");
+
+            let index = Liability::next_index();
             assert_ok!(Liability::create(
-                RuntimeOrigin::signed(agreement.promisor.clone()),
-                agreement.clone()
-            ),);
-            assert_eq!(Liability::next_index(), 1);
-            assert_eq!(Liability::report_of(0), None);
-            assert_eq!(Liability::agreement_of(0), Some(agreement));
-            assert_eq!(System::account(&alice).data.free, 90 * XRT);
-            assert_eq!(System::account(&bob).data.free, 100 * XRT);
+                RuntimeOrigin::signed(bob.clone()),
+                SignedAgreement {
+                    technics: technics.clone(),
+                    economics: economics.clone(),
+                    promisee: alice,
+                    promisor: bob,
+                    validator: None,
+                    nonce,
+                    promisee_signature,
+                    promisor_signature,
+                }
+            ));
+            index
+        }
 
-            let index = 0u32;
+        fn report(index: Index) -> <Runtime as Config>::Report {
             let payload = IPFS {
                 hash: IPFS_HASH.into(),
             };
-            let (_, bad_signature) = get_report_proof("//Alice", &index, &payload);
             let (sender, signature) = get_report_proof("//Bob", &index, &payload);
-
-            let report = SignedReport {
+            SignedReport {
                 index,
                 sender,
                 payload,
-                signature: bad_signature,
+                signature,
+            }
+        }
+
+        #[test]
+        fn test_collateral_release() {
+            new_test_ext().execute_with(|| {
+                use sp_keyring::sr25519::Keyring;
+                let alice: AccountId32 = Keyring::Alice.into();
+                let bob: AccountId32 = Keyring::Bob.into();
+                System::set_block_number(1);
+
+                let index = create_liability(0, 10);
+                assert_eq!(System::account(&alice).data.reserved, 10 * XRT);
+                assert_eq!(System::account(&bob).data.reserved, 20 * XRT);
+
+                assert_ok!(Liability::finalize(
+                    RuntimeOrigin::signed(bob.clone()),
+                    report(index)
+                ));
+                assert_eq!(System::account(&alice).data.free, 90 * XRT);
+                assert_eq!(System::account(&alice).data.reserved, 0);
+                assert_eq!(System::account(&bob).data.free, 110 * XRT);
+                assert_eq!(System::account(&bob).data.reserved, 0);
+                assert_err!(
+                    Liability::expire(RuntimeOrigin::signed(alice), index),
+                    Error::<Runtime>::AlreadyFinalized,
+                );
+            })
+        }
+
+        #[test]
+        fn test_collateral_slash_on_expiry() {
+            new_test_ext().execute_with(|| {
+                use sp_keyring::sr25519::Keyring;
+                let alice: AccountId32 = Keyring::Alice.into();
+                let bob: AccountId32 = Keyring::Bob.into();
+                System::set_block_number(1);
+
+                let index = create_liability(0, 10);
+                assert_err!(
+                    Liability::expire(RuntimeOrigin::signed(alice.clone()), index),
+                    Error::<Runtime>::NotExpired,
+                );
+
+                // Late report isn't accepted
+                System::set_block_number(11);
+                assert_err!(
+                    Liability::finalize(RuntimeOrigin::signed(bob.clone()), report(index)),
+                    Error::<Runtime>::AgreementExpired,
+                );
+
+                // Collateral goes to promisee
+                assert_ok!(Liability::expire(
+                    RuntimeOrigin::signed(alice.clone()),
+                    index
+                ));
+                assert_eq!(Liability::expired_of(index), Some(11));
+                assert_eq!(System::account(&alice).data.free, 120 * XRT);
+                assert_eq!(System::account(&alice).data.reserved, 0);
+                assert_eq!(System::account(&bob).data.free, 80 * XRT);
+                assert_eq!(System::account(&bob).data.reserved, 0);
+                assert_err!(
+                    Liability::expire(RuntimeOrigin::signed(alice), index),
+                    Error::<Runtime>::AlreadyFinalized,
+                );
+            })
+        }
+    }
+
+    mod milestone_market {
+        use super::*;
+        use crate::economics::{Milestone, MilestoneMarket};
+
+        use crate::mock::milestone_market::{
+            new_test_ext, ChallengePeriod, Liability, Runtime, RuntimeOrigin, System,
+        };
+
+        fn create_liability(nonce: AgreementNonce, validator: Option<AccountId32>) -> Index {
+            let technics = IPFS {
+                hash: IPFS_HASH.into(),
             };
-            assert_err!(
-                Liability::finalize(RuntimeOrigin::signed(report.sender.clone()), report.clone()),
-                Error::<Runtime>::BadReportProof,
-            );
-            assert_eq!(Liability::report_of(0), None);
-            assert_eq!(System::account(&alice).data.free, 90 * XRT);
-            assert_eq!(System::account(&bob).data.free, 100 * XRT);
+            let milestone = |amount: Balance| Milestone {
+                amount: amount * XRT,
+                technics: technics.clone(),
+            };
+            let economics: EconomicsFor<Runtime> = MilestoneMarket {
+                milestones: vec![milestone(10), milestone(20), milestone(30)]
+                    .try_into()
+                    .unwrap(),
+                deadline: 10,
+            };
+            let domain = System::block_hash(0);
+            let proof = |uri: &str| -> (AccountId32, MultiSignature) {
+                let pair = sr25519::Pair::from_string(uri, None).unwrap();
+                let sender = <MultiSignature as Verify>::Signer::from(pair.public()).into_account();
+                let signature =
+                    <ProofSigner<_> as AgreementProofBuilder<_, _, AccountId32, _, _>>::proof(
+                        domain.as_ref(),
+                        &technics,
+                        &economics,
+                        &validator,
+                        &nonce,
+                        &pair,
+                    )
+                    .into();
+                (sender, signature)
+            };
+            let (alice, promisee_signature) = proof("//Alice");
+            let (bob, promisor_signature) = proof("//Bob");
 
-            let report = SignedReport {
-                signature,
-                ..report.clone()
+            let index = Liability::next_index();
+            assert_ok!(Liability::create(
+                RuntimeOrigin::signed(bob.clone()),
+                SignedAgreement {
+                    technics: technics.clone(),
+                    economics: economics.clone(),
+                    promisee: alice,
+                    promisor: bob,
+                    validator,
+                    nonce,
+                    promisee_signature,
+                    promisor_signature,
+                }
+            ));
+            index
+        }
+
+        #[test]
+        fn test_milestone_payouts() {
+            new_test_ext().execute_with(|| {
+                use sp_keyring::sr25519::Keyring;
+                let alice: AccountId32 = Keyring::Alice.into();
+                let bob: AccountId32 = Keyring::Bob.into();
+                let payload = IPFS {
+                    hash: IPFS_HASH.into(),
+                };
+                System::set_block_number(1);
+
+                // All milestones reached
+                let index = create_liability(0, None);
+                assert_eq!(System::account(&alice).data.reserved, 60 * XRT);
+                assert_err!(
+                    Liability::report_milestone(
+                        RuntimeOrigin::signed(alice.clone()),
+                        index,
+                        0,
+                        payload.clone()
+                    ),
+                    Error::<Runtime>::BadReportSender,
+                );
+                assert_err!(
+                    Liability::report_milestone(
+                        RuntimeOrigin::signed(bob.clone()),
+                        index,
+                        1,
+                        payload.clone()
+                    ),
+                    Error::<Runtime>::BadMilestone,
+                );
+                let (sender, signature) = get_report_proof("//Bob", &index, &payload);
+                assert_err!(
+                    Liability::finalize(
+                        RuntimeOrigin::signed(bob.clone()),
+                        SignedReport {
+                            index,
+                            sender,
+                            payload: payload.clone(),
+                            signature,
+                        }
+                    ),
+                    Error::<Runtime>::MilestoneReportRequired,
+                );
+                for n in 0..3 {
+                    assert_ok!(Liability::report_milestone(
+                        RuntimeOrigin::signed(bob.clone()),
+                        index,
+                        n,
+                        payload.clone()
+                    ));
+                }
+                System::assert_last_event(Event::<Runtime>::MilestonesCompleted(index).into());
+                assert_eq!(Liability::milestones_paid(index), 3);
+                assert_eq!(System::account(&alice).data.free, 40 * XRT);
+                assert_eq!(System::account(&alice).data.reserved, 0);
+                assert_eq!(System::account(&bob).data.free, 160 * XRT);
+
+                System::set_block_number(11);
+                assert_err!(
+                    Liability::expire(RuntimeOrigin::signed(alice.clone()), index),
+                    Error::<Runtime>::AlreadyFinalized,
+                );
+            })
+        }
+
+        #[test]
+        fn test_milestone_expiry() {
+            new_test_ext().execute_with(|| {
+                use sp_keyring::sr25519::Keyring;
+                let alice: AccountId32 = Keyring::Alice.into();
+                let bob: AccountId32 = Keyring::Bob.into();
+                let payload = IPFS {
+                    hash: IPFS_HASH.into(),
+                };
+                System::set_block_number(1);
+
+                let index = create_liability(0, None);
+                assert_ok!(Liability::report_milestone(
+                    RuntimeOrigin::signed(bob.clone()),
+                    index,
+                    0,
+                    payload.clone()
+                ));
+                assert_eq!(System::account(&alice).data.reserved, 50 * XRT);
+                assert_eq!(System::account(&bob).data.free, 110 * XRT);
+
+                // Unreached milestones are refunded on expiry
+                System::set_block_number(11);
+                assert_err!(
+                    Liability::report_milestone(
+                        RuntimeOrigin::signed(bob.clone()),
+                        index,
+                        1,
+                        payload.clone()
+                    ),
+                    Error::<Runtime>::AgreementExpired,
+                );
+                assert_ok!(Liability::expire(
+                    RuntimeOrigin::signed(alice.clone()),
+                    index
+                ));
+                assert_eq!(System::account(&alice).data.free, 90 * XRT);
+                assert_eq!(System::account(&alice).data.reserved, 0);
+                assert_eq!(System::account(&bob).data.free, 110 * XRT);
+                assert_err!(
+                    Liability::report_milestone(RuntimeOrigin::signed(bob), index, 1, payload),
+                    Error::<Runtime>::AlreadyFinalized,
+                );
+            })
+        }
+
+        #[test]
+        fn test_milestone_settlement() {
+            new_test_ext().execute_with(|| {
+                use sp_keyring::sr25519::Keyring;
+                let alice: AccountId32 = Keyring::Alice.into();
+                let bob: AccountId32 = Keyring::Bob.into();
+                let charlie: AccountId32 = Keyring::Charlie.into();
+                let payload = IPFS {
+                    hash: IPFS_HASH.into(),
+                };
+                ChallengePeriod::set(2);
+                System::set_block_number(1);
+
+                let index = create_liability(0, Some(charlie.clone()));
+
+                // Milestone report should deliver milestone technics
+                assert_err!(
+                    Liability::report_milestone(
+                        RuntimeOrigin::signed(bob.clone()),
+                        index,
+                        0,
+                        IPFS {
+                            hash: [0u8; 32].into(),
+                        }
+                    ),
+                    Error::<Runtime>::BadMilestoneReport,
+                );
+
+                // Milestone report waits for validator verdict
+                assert_ok!(Liability::report_milestone(
+                    RuntimeOrigin::signed(bob.clone()),
+                    index,
+                    0,
+                    payload.clone()
+                ));
+                assert!(Liability::milestone_report_of(index).is_some());
+                assert_err!(
+                    Liability::report_milestone(
+                        RuntimeOrigin::signed(bob.clone()),
+                        index,
+                        0,
+                        payload.clone()
+                    ),
+                    Error::<Runtime>::MilestonePending,
+                );
+                assert_err!(
+                    Liability::confirm(RuntimeOrigin::signed(bob.clone()), index, true),
+                    Error::<Runtime>::BadValidator,
+                );
+
+                // Rejected milestone isn't paid and could be reported again
+                assert_ok!(Liability::confirm(
+                    RuntimeOrigin::signed(charlie.clone()),
+                    index,
+                    false
+                ));
+                assert_eq!(Liability::milestone_report_of(index), None);
+                System::set_block_number(4);
+                assert_ok!(Liability::settle(RuntimeOrigin::signed(bob.clone()), index));
+                System::assert_has_event(Event::<Runtime>::MilestoneRejected(index, 0).into());
+                assert_eq!(Liability::milestones_paid(index), 0);
+                assert_eq!(System::account(&alice).data.reserved, 60 * XRT);
+
+                // Confirmed milestone is paid after challenge period
+                assert_ok!(Liability::report_milestone(
+                    RuntimeOrigin::signed(bob.clone()),
+                    index,
+                    0,
+                    payload.clone()
+                ));
+                assert_ok!(Liability::confirm(
+                    RuntimeOrigin::signed(charlie.clone()),
+                    index,
+                    true
+                ));
+                assert_eq!(System::account(&bob).data.free, 100 * XRT);
+                assert_err!(
+                    Liability::settle(RuntimeOrigin::signed(bob.clone()), index),
+                    Error::<Runtime>::ChallengePeriodNotExpired,
+                );
+                System::set_block_number(7);
+                assert_ok!(Liability::settle(RuntimeOrigin::signed(bob.clone()), index));
+                System::assert_has_event(Event::<Runtime>::MilestonePaid(index, 0).into());
+                assert_eq!(Liability::milestones_paid(index), 1);
+                assert_eq!(System::account(&alice).data.reserved, 50 * XRT);
+                assert_eq!(System::account(&bob).data.free, 110 * XRT);
+
+                // Unsettled milestone can't be expired
+                assert_ok!(Liability::report_milestone(
+                    RuntimeOrigin::signed(bob.clone()),
+                    index,
+                    1,
+                    payload
+                ));
+                assert_ok!(Liability::confirm(
+                    RuntimeOrigin::signed(charlie),
+                    index,
+                    true
+                ));
+                System::set_block_number(11);
+                assert_err!(
+                    Liability::expire(RuntimeOrigin::signed(alice.clone()), index),
+                    Error::<Runtime>::MilestonePending,
+                );
+                assert_ok!(Liability::settle(RuntimeOrigin::signed(bob), index));
+                assert_ok!(Liability::expire(
+                    RuntimeOrigin::signed(alice.clone()),
+                    index
+                ));
+                assert_eq!(System::account(&alice).data.free, 70 * XRT);
+                assert_eq!(System::account(&alice).data.reserved, 0);
+            })
+        }
+    }
+
+    mod multi_asset_market {
+        use super::*;
+        use crate::economics::MultiAssetMarket;
+
+        use crate::mock::{
+            multi_asset_market::{
+                new_test_ext, Assets, AssetsHolder, Liability, Runtime, RuntimeHoldReason,
+                RuntimeOrigin, System,
+            },
+            USDT,
+        };
+        use frame_support::traits::fungibles::InspectHold;
+
+        fn get_params_proof(
+            uri: &str,
+            technics: &TechnicsFor<Runtime>,
+            economics: &EconomicsFor<Runtime>,
+        ) -> (AccountId32, MultiSignature) {
+            let pair = sr25519::Pair::from_string(uri, None).unwrap();
+            let sender = <MultiSignature as Verify>::Signer::from(pair.public()).into_account();
+            let domain = System::block_hash(0);
+            let signature =
+                <ProofSigner<_> as AgreementProofBuilder<_, _, AccountId32, _, _>>::proof(
+                    domain.as_ref(),
+                    technics,
+                    economics,
+                    &None,
+                    &0,
+                    &pair,
+                )
+                .into();
+            (sender, signature)
+        }
+
+        #[test]
+        fn test_asset_escrow() {
+            new_test_ext().execute_with(|| {
+                let technics = IPFS {
+                    hash: IPFS_HASH.into(),
+                };
+                let economics = MultiAssetMarket {
+                    asset_id: USDT,
+                    amount: 10 * XRT,
+                    valuation: Default::default(),
+                };
+                let (alice, promisee_signature) =
+                    get_params_proof("//Alice", &technics, &economics);
+                let (bob, promisor_signature) = get_params_proof("//Bob", &technics, &economics);
+                let escrow: RuntimeHoldReason = HoldReason::Escrow.into();
+
+                assert_ok!(Liability::create(
+                    RuntimeOrigin::signed(bob.clone()),
+                    SignedAgreement {
+                        technics,
+                        economics,
+                        promisee: alice.clone(),
+                        promisor: bob.clone(),
+                        validator: None,
+                        nonce: 0,
+                        promisee_signature,
+                        promisor_signature,
+                    }
+                ));
+                assert_eq!(Assets::balance(USDT, &alice), 90 * XRT);
+                assert_eq!(
+                    AssetsHolder::balance_on_hold(USDT, &escrow, &alice),
+                    10 * XRT
+                );
+                // Native balances are untouched
+                assert_eq!(System::account(&alice).data.free, 100 * XRT);
+
+                let index = 0u32;
+                let payload = IPFS {
+                    hash: IPFS_HASH.into(),
+                };
+                let (sender, signature) = get_report_proof("//Bob", &index, &payload);
+                assert_ok!(Liability::finalize(
+                    RuntimeOrigin::signed(bob.clone()),
+                    SignedReport {
+                        index,
+                        sender,
+                        payload,
+                        signature,
+                    }
+                ));
+                assert_eq!(AssetsHolder::balance_on_hold(USDT, &escrow, &alice), 0);
+                assert_eq!(Assets::balance(USDT, &alice), 90 * XRT);
+                assert_eq!(Assets::balance(USDT, &bob), 10 * XRT);
+
+                // Asset amount is valued in native currency
+                assert_eq!(Liability::reputation_of(&bob).settled, 10 * XRT);
+            })
+        }
+    }
+
+    mod native_or_asset_market {
+        use super::*;
+        use crate::economics::{MultiAssetMarket, NativeOrAssetMarket};
+
+        use crate::mock::{
+            native_or_asset_market::{
+                new_test_ext, Assets, AssetsHolder, Liability, Runtime, RuntimeHoldReason,
+                RuntimeOrigin, System,
+            },
+            USDT,
+        };
+        use frame_support::traits::fungibles::InspectHold;
+
+        fn create_liability(economics: EconomicsFor<Runtime>, nonce: AgreementNonce) {
+            let technics = IPFS {
+                hash: IPFS_HASH.into(),
+            };
+            let domain = System::block_hash(0);
+            let proof = |uri: &str| {
+                let pair = sr25519::Pair::from_string(uri, None).unwrap();
+                let sender = <MultiSignature as Verify>::Signer::from(pair.public()).into_account();
+                let signature = <ProofSigner<_> as AgreementProofBuilder<
+                    _,
+                    _,
+                    AccountId32,
+                    _,
+                    _,
+                >>::proof(
+                    domain.as_ref(), &technics, &economics, &None, &nonce, &pair
+                )
+                .into();
+                (sender, signature)
+            };
+            let (alice, promisee_signature) = proof("//Alice");
+            let (bob, promisor_signature) = proof("//Bob");
+
+            assert_ok!(Liability::create(
+                RuntimeOrigin::signed(bob.clone()),
+                SignedAgreement {
+                    technics,
+                    economics,
+                    promisee: alice,
+                    promisor: bob,
+                    validator: None,
+                    nonce,
+                    promisee_signature,
+                    promisor_signature,
+                }
+            ));
+        }
+
+        fn finalize_liability(index: u32) {
+            let payload = IPFS {
+                hash: IPFS_HASH.into(),
             };
+            let (sender, signature) = get_report_proof("//Bob", &index, &payload);
             assert_ok!(Liability::finalize(
-                RuntimeOrigin::signed(report.sender.clone()),
-                report.clone()
+                RuntimeOrigin::signed(sender.clone()),
+                SignedReport {
+                    index,
+                    sender,
+                    payload,
+                    signature,
+                }
             ));
-            assert_eq!(Liability::report_of(0), Some(report));
-            assert_eq!(System::account(&alice).data.free, 90 * XRT);
-            assert_eq!(System::account(&bob).data.free, 110 * XRT);
-        })
+        }
+
+        #[test]
+        fn test_native_and_asset_escrow() {
+            new_test_ext().execute_with(|| {
+                let alice: AccountId32 = sp_keyring::sr25519::Keyring::Alice.into();
+                let bob: AccountId32 = sp_keyring::sr25519::Keyring::Bob.into();
+                let escrow: RuntimeHoldReason = HoldReason::Escrow.into();
+
+                create_liability(
+                    NativeOrAssetMarket::Native(SimpleMarket { price: 10 * XRT }),
+                    0,
+                );
+                create_liability(
+                    NativeOrAssetMarket::Asset(MultiAssetMarket {
+                        asset_id: USDT,
+                        amount: 20 * XRT,
+                        valuation: Default::default(),
+                    }),
+                    1,
+                );
+                assert_eq!(System::account(&alice).data.reserved, 10 * XRT);
+                assert_eq!(Assets::balance(USDT, &alice), 80 * XRT);
+                assert_eq!(
+                    AssetsHolder::balance_on_hold(USDT, &escrow, &alice),
+                    20 * XRT
+                );
+
+                let bob_free = System::account(&bob).data.free;
+                finalize_liability(0);
+                finalize_liability(1);
+                assert_eq!(System::account(&alice).data.reserved, 0);
+                assert_eq!(System::account(&alice).data.free, 90 * XRT);
+                assert_eq!(System::account(&bob).data.free, bob_free + 10 * XRT);
+                assert_eq!(AssetsHolder::balance_on_hold(USDT, &escrow, &alice), 0);
+                assert_eq!(Assets::balance(USDT, &bob), 20 * XRT);
+
+                // Both payments are valued in native currency
+                assert_eq!(Liability::reputation_of(&bob).settled, 30 * XRT);
+            })
+        }
+    }
+
+    mod validator_oracle {
+        use super::*;
+
+        use crate::mock::validator_oracle::{
+            new_test_ext, Liability, Runtime, RuntimeOrigin, System,
+        };
+
+        fn get_params_proof(
+            uri: &str,
+            technics: &TechnicsFor<Runtime>,
+            economics: &EconomicsFor<Runtime>,
+            validator: &Option<AccountId32>,
+        ) -> (AccountId32, MultiSignature) {
+            let pair = sr25519::Pair::from_string(uri, None).unwrap();
+            let sender = <MultiSignature as Verify>::Signer::from(pair.public()).into_account();
+            let domain = System::block_hash(0);
+            let signature = <ProofSigner<_> as AgreementProofBuilder<_, _, _, _, _>>::proof(
+                domain.as_ref(),
+                technics,
+                economics,
+                validator,
+                &0,
+                &pair,
+            )
+            .into();
+            (sender, signature)
+        }
+
+        #[test]
+        fn test_validated_report() {
+            new_test_ext().execute_with(|| {
+                use sp_keyring::sr25519::Keyring;
+
+                let technics = IPFS {
+                    hash: IPFS_HASH.into(),
+                };
+                let economics = SimpleMarket { price: 10 * XRT };
+                let validator: AccountId32 = Keyring::Charlie.into();
+
+                let (alice, promisee_signature) =
+                    get_params_proof("//Alice", &technics, &economics, &Some(validator.clone()));
+                let (bob, promisor_signature) =
+                    get_params_proof("//Bob", &technics, &economics, &Some(validator.clone()));
+                assert_ok!(Liability::create(
+                    RuntimeOrigin::signed(bob.clone()),
+                    SignedAgreement {
+                        technics,
+                        economics,
+                        promisee: alice.clone(),
+                        promisor: bob.clone(),
+                        validator: Some(validator.clone()),
+                        nonce: 0,
+                        promisee_signature,
+                        promisor_signature,
+                    }
+                ));
+                assert_eq!(System::account(&alice).data.free, 90 * XRT);
+
+                let index = 0u32;
+                let payload = IPFS {
+                    hash: IPFS_HASH.into(),
+                };
+                let (_, signature) = get_report_proof("//Bob", &index, &payload);
+                let report = ValidatedReport {
+                    index,
+                    sender: bob.clone(),
+                    payload,
+                    signature,
+                    verdict: None,
+                };
+
+                // Promisor can't confirm report by itself
+                assert_err!(
+                    Liability::finalize(
+                        RuntimeOrigin::signed(bob.clone()),
+                        ValidatedReport {
+                            verdict: Some(true),
+                            ..report.clone()
+                        }
+                    ),
+                    Error::<Runtime>::ValidatorVerdictRequired,
+                );
+
+                // Report is pending until validator verdict
+                assert_ok!(Liability::finalize(
+                    RuntimeOrigin::signed(bob.clone()),
+                    report.clone()
+                ));
+                assert_eq!(Liability::report_of(index), Some(report.clone()));
+                assert_eq!(System::account(&alice).data.free, 90 * XRT);
+                assert_eq!(System::account(&bob).data.free, 100 * XRT);
+                assert_err!(
+                    Liability::finalize(RuntimeOrigin::signed(bob.clone()), report.clone()),
+                    Error::<Runtime>::AlreadyFinalized,
+                );
+
+                assert_err!(
+                    Liability::confirm(RuntimeOrigin::signed(bob.clone()), index, true),
+                    Error::<Runtime>::BadValidator,
+                );
+
+                // Validator rejects the report, promisee funds unlocked
+                assert_ok!(Liability::confirm(
+                    RuntimeOrigin::signed(validator.clone()),
+                    index,
+                    false
+                ));
+                assert_eq!(
+                    Liability::report_of(index).and_then(|r| r.is_confirmed()),
+                    Some(false)
+                );
+                assert_eq!(System::account(&alice).data.free, 100 * XRT);
+                assert_eq!(System::account(&bob).data.free, 100 * XRT);
+
+                assert_err!(
+                    Liability::confirm(RuntimeOrigin::signed(validator), index, true),
+                    Error::<Runtime>::AlreadyFinalized,
+                );
+            })
+        }
+    }
+
+    mod reputable_market {
+        use super::*;
+        use crate::economics::ReputableMarket;
+
+        use crate::mock::reputable_market::{
+            new_test_ext, Liability, Runtime, RuntimeOrigin, System,
+        };
+
+        fn make_agreement(
+            min_completed: u32,
+            min_settled: Balance,
+            nonce: AgreementNonce,
+        ) -> <Runtime as Config>::Agreement {
+            let technics = IPFS {
+                hash: IPFS_HASH.into(),
+            };
+            let economics = ReputableMarket {
+                price: 10 * XRT,
+                min_completed,
+                max_failed: 0,
+                min_settled,
+            };
+            let domain = System::block_hash(0);
+            let sign = |uri: &str| {
+                let pair = sr25519::Pair::from_string(uri, None).unwrap();
+                let sender = <MultiSignature as Verify>::Signer::from(pair.public()).into_account();
+                let signature: MultiSignature =
+                    <ProofSigner<_> as AgreementProofBuilder<_, _, AccountId32, _, _>>::proof(
+                        domain.as_ref(),
+                        &technics,
+                        &economics,
+                        &None,
+                        &nonce,
+                        &pair,
+                    )
+                    .into();
+                (sender, signature)
+            };
+            let (alice, promisee_signature) = sign("//Alice");
+            let (bob, promisor_signature) = sign("//Bob");
+            SignedAgreement {
+                technics,
+                economics,
+                promisee: alice,
+                promisor: bob,
+                validator: None,
+                nonce,
+                promisee_signature,
+                promisor_signature,
+            }
+        }
+
+        #[test]
+        fn test_min_reputation() {
+            new_test_ext().execute_with(|| {
+                // Promisor without completed liabilities isn't acceptable
+                let agreement = make_agreement(1, 0, 0);
+                let bob = agreement.promisor.clone();
+                assert_err!(
+                    Liability::create(RuntimeOrigin::signed(bob.clone()), agreement),
+                    Error::<Runtime>::LowReputation,
+                );
+
+                assert_ok!(Liability::create(
+                    RuntimeOrigin::signed(bob.clone()),
+                    make_agreement(0, 0, 0)
+                ));
+                let payload = IPFS {
+                    hash: IPFS_HASH.into(),
+                };
+                let (_, signature) = get_report_proof("//Bob", &0, &payload);
+                assert_ok!(Liability::finalize(
+                    RuntimeOrigin::signed(bob.clone()),
+                    SignedReport {
+                        index: 0,
+                        sender: bob.clone(),
+                        payload,
+                        signature,
+                    }
+                ));
+                assert_eq!(Liability::reputation_of(&bob).completed, 1);
+                assert_eq!(Liability::reputation_of(&bob).settled, 10 * XRT);
+
+                // Completed liabilities should be of enough value
+                assert_err!(
+                    Liability::create(
+                        RuntimeOrigin::signed(bob.clone()),
+                        make_agreement(1, 20 * XRT, 1)
+                    ),
+                    Error::<Runtime>::LowReputation,
+                );
+
+                // Completed liability makes promisor acceptable
+                assert_ok!(Liability::create(
+                    RuntimeOrigin::signed(bob.clone()),
+                    make_agreement(1, 10 * XRT, 1)
+                ));
+                assert_eq!(Liability::reputation_of(&bob).created, 2);
+            })
+        }
     }
 }
@@ -0,0 +1,238 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2026 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Mock runtimes for testing `Liability`.
+//!
+//! Agreement economics is a `Config` type, so every economics model gets its
+//! own runtime generated by `mock_runtime!` with the same pallets and genesis.
+
+use crate::economics::{
+    CollateralizedMarket, MilestoneMarket, MultiAssetMarket, NativeOrAssetMarket, ReputableMarket,
+    SimpleMarket,
+};
+use crate::signed::{SignedAgreement, SignedReport, ValidatedReport};
+use crate::technics::{Technics, IPFS};
+use crate::Index;
+use frame_support::traits::tokens::ConversionFromAssetBalance;
+use sp_runtime::{AccountId32, MultiSignature};
+
+pub type Balance = u128;
+
+pub const XRT: Balance = 1_000_000_000;
+
+/// Asset used to pay multi-asset liabilities.
+pub const USDT: u32 = 1984;
+
+/// Asset amount is valued in native currency one to one.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AssetValue;
+
+impl ConversionFromAssetBalance<Balance, u32, Balance> for AssetValue {
+    type Error = ();
+
+    fn from_asset_balance(balance: Balance, _asset_id: u32) -> Result<Balance, ()> {
+        Ok(balance)
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn ensure_successful(_asset_id: u32) {}
+}
+
+macro_rules! mock_runtime {
+    ($name:ident, $economics:ty, $report:ty) => {
+        mock_runtime!($name, IPFS, $economics, $report);
+    };
+    ($name:ident, $technics:ty, $economics:ty, $report:ty) => {
+        pub mod $name {
+            use super::*;
+            use crate::{self as liability, weights, Config};
+            use frame_support::{derive_impl, parameter_types, traits::AsEnsureOriginWithArg};
+            use frame_system::{EnsureRoot, EnsureSigned};
+            use sp_runtime::{traits::IdentityLookup, BuildStorage};
+
+            type Block = frame_system::mocking::MockBlock<Runtime>;
+
+            frame_support::construct_runtime!(
+                pub enum Runtime {
+                    System: frame_system,
+                    Balances: pallet_balances,
+                    Assets: pallet_assets,
+                    AssetsHolder: pallet_assets_holder,
+                    Liability: liability,
+                }
+            );
+
+            #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+            impl frame_system::Config for Runtime {
+                type Block = Block;
+                type AccountId = AccountId32;
+                type Lookup = IdentityLookup<Self::AccountId>;
+                type AccountData = pallet_balances::AccountData<Balance>;
+            }
+
+            parameter_types! {
+                pub const MaxLocks: u32 = 50;
+                pub const MaxReserves: u32 = 50;
+                pub const ExistentialDeposit: Balance = 10;
+                pub static ChallengePeriod: u64 = 0;
+                pub const DisputeBond: Balance = 5 * XRT;
+                pub const ResolutionPeriod: u64 = 10;
+                pub static OrderDeposit: Balance = 0;
+                pub static StorageDeposit: Balance = 0;
+                pub static RetentionPeriod: u64 = 0;
+            }
+
+            impl pallet_balances::Config for Runtime {
+                type MaxLocks = MaxLocks;
+                type MaxReserves = MaxReserves;
+                type ReserveIdentifier = [u8; 8];
+                type Balance = Balance;
+                type RuntimeEvent = RuntimeEvent;
+                type DustRemoval = ();
+                type ExistentialDeposit = ExistentialDeposit;
+                type AccountStore = System;
+                type WeightInfo = ();
+                type FreezeIdentifier = ();
+                type MaxFreezes = ();
+                type RuntimeHoldReason = RuntimeHoldReason;
+                type RuntimeFreezeReason = ();
+                type DoneSlashHandler = ();
+            }
+
+            #[derive_impl(pallet_assets::config_preludes::TestDefaultConfig)]
+            impl pallet_assets::Config for Runtime {
+                type Balance = Balance;
+                type Currency = Balances;
+                type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<Self::AccountId>>;
+                type ForceOrigin = EnsureRoot<Self::AccountId>;
+                type Freezer = ();
+                type Holder = AssetsHolder;
+            }
+
+            impl pallet_assets_holder::Config for Runtime {
+                type RuntimeEvent = RuntimeEvent;
+                type RuntimeHoldReason = RuntimeHoldReason;
+            }
+
+            impl Config for Runtime {
+                type RuntimeEvent = RuntimeEvent;
+                type Agreement = SignedAgreement<
+                    // Task description
+                    $technics,
+                    // Economics model under test
+                    $economics,
+                    // Use standard accounts
+                    Self::AccountId,
+                    // Use standard signatures
+                    MultiSignature,
+                >;
+                type Report = $report;
+                type RuntimeHoldReason = RuntimeHoldReason;
+                type Currency = Balances;
+                type Evidence = IPFS;
+                type ChallengePeriod = ChallengePeriod;
+                type DisputeBond = DisputeBond;
+                type ArbiterOrigin = EnsureRoot<Self::AccountId>;
+                type ResolutionPeriod = ResolutionPeriod;
+                type OrderSignature = MultiSignature;
+                type MaxOrders = frame_support::traits::ConstU32<16>;
+                type OrderDeposit = OrderDeposit;
+                type StorageDeposit = StorageDeposit;
+                type RetentionPeriod = RetentionPeriod;
+                type WeightInfo = weights::TestWeightInfo;
+            }
+
+            pub fn new_test_ext() -> sp_io::TestExternalities {
+                use sp_keyring::sr25519::Keyring;
+
+                let storage = RuntimeGenesisConfig {
+                    system: Default::default(),
+                    balances: pallet_balances::GenesisConfig::<Runtime> {
+                        balances: vec![
+                            (Keyring::Alice.into(), 100 * XRT),
+                            (Keyring::Bob.into(), 100 * XRT),
+                        ],
+                        dev_accounts: None,
+                    },
+                    assets: pallet_assets::GenesisConfig::<Runtime> {
+                        assets: vec![(USDT, Keyring::Alice.into(), true, 1)],
+                        accounts: vec![(USDT, Keyring::Alice.into(), 100 * XRT)],
+                        ..Default::default()
+                    },
+                }
+                .build_storage()
+                .unwrap();
+                storage.into()
+            }
+        }
+    };
+}
+
+type SignedReportOf = SignedReport<Index, AccountId32, MultiSignature, IPFS>;
+
+// Liability has a price
+mock_runtime!(simple_market, SimpleMarket<AccountId32, Balances>, SignedReportOf);
+
+// Promisor pledges collateral until deadline
+mock_runtime!(
+    collateralized_market,
+    CollateralizedMarket<AccountId32, Balances, u64>,
+    SignedReportOf
+);
+
+// Liability paid by milestones
+mock_runtime!(
+    milestone_market,
+    MilestoneMarket<AccountId32, Balances, IPFS, u64>,
+    SignedReportOf
+);
+
+// Liability paid in assets
+mock_runtime!(
+    multi_asset_market,
+    MultiAssetMarket<AccountId32, AssetsHolder, AssetValue>,
+    SignedReportOf
+);
+
+// Liability paid in native currency or in assets
+mock_runtime!(
+    native_or_asset_market,
+    NativeOrAssetMarket<AccountId32, Balances, AssetsHolder, AssetValue>,
+    SignedReportOf
+);
+
+// Reports are confirmed by third party validator
+mock_runtime!(
+    validator_oracle,
+    SimpleMarket<AccountId32, Balances>,
+    ValidatedReport<Index, AccountId32, MultiSignature, IPFS>
+);
+
+// Promisor should have acceptable reputation
+mock_runtime!(
+    reputable_market,
+    ReputableMarket<AccountId32, Balances>,
+    SignedReportOf
+);
+
+// Task described by CID of any content
+mock_runtime!(
+    technics_cid,
+    Technics,
+    SimpleMarket<AccountId32, Balances>,
+    SignedReportOf
+);
@@ -0,0 +1,44 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2026 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Runtime API definition for liability pallet.
+
+use crate::{Index, LiabilityRole, LiabilityStatus, Reputation};
+use parity_scale_codec::Codec;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+    /// Account-indexed liability lookup and promisor reputation.
+    pub trait LiabilityApi<AccountId, Balance>
+    where
+        AccountId: Codec,
+        Balance: Codec,
+    {
+        /// Liabilities of `account` in given `role` with their status, `status` filters
+        /// open or finalized liabilities. Results are paginated by `offset` and `limit`.
+        fn liabilities(
+            account: AccountId,
+            role: LiabilityRole,
+            status: Option<LiabilityStatus>,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<(Index, LiabilityStatus)>;
+
+        /// Liability outcomes of promisor `account`.
+        fn reputation(account: AccountId) -> Reputation<Balance>;
+    }
+}
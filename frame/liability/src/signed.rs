@@ -19,7 +19,11 @@
 
 use frame_support::{
     dispatch,
-    traits::{BalanceStatus, ReservableCurrency},
+    traits::{
+        fungibles,
+        tokens::{Fortitude, Precision, Restriction},
+        BalanceStatus, Currency, ReservableCurrency,
+    },
 };
 use frame_system::offchain::AppCrypto;
 use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
@@ -27,12 +31,16 @@ use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use sp_core::crypto::{Pair, Public};
 use sp_runtime::{
-    traits::{IdentifyAccount, Verify},
+    traits::{IdentifyAccount, Saturating, Verify, Zero},
     DispatchResult,
 };
-use sp_std::marker::PhantomData;
+use sp_std::{marker::PhantomData, prelude::*};
 
-use crate::economics::SimpleMarket;
+use crate::economics::{
+    CollateralizedMarket, MilestoneMarket, MultiAssetMarket, NativeOrAssetMarket, ReputableMarket,
+    SimpleMarket,
+};
+use crate::pallet::{HoldReason, OrderSide};
 use crate::traits::*;
 
 /// Agreement that could be proven by asymmetric cryptography.
@@ -44,10 +52,170 @@ pub struct SignedAgreement<T, E, AccountId, Signature> {
     pub economics: E,
     pub promisee: AccountId,
     pub promisor: AccountId,
+    pub validator: Option<AccountId>,
+    #[codec(compact)]
+    pub nonce: AgreementNonce,
     pub promisee_signature: Signature,
     pub promisor_signature: Signature,
 }
 
+/// Encode agreement parameters that should be signed by both parties.
+///
+/// Chain `domain` and promisee `nonce` make signature valid only once and only on one chain.
+pub fn agreement_payload<T: Encode, E: Encode, V: Encode>(
+    domain: &[u8],
+    technics: &T,
+    economics: &E,
+    validator: &Option<V>,
+    nonce: &AgreementNonce,
+) -> Vec<u8> {
+    (domain, technics, economics, validator, nonce).encode()
+}
+
+/// Encode market order parameters that should be signed by order owner.
+pub fn order_payload<T: Encode, P: Encode, B: Encode>(
+    domain: &[u8],
+    side: &OrderSide,
+    technics: &T,
+    price: &P,
+    expires: &B,
+    nonce: &AgreementNonce,
+) -> Vec<u8> {
+    (domain, side, technics, price, expires, nonce).encode()
+}
+
+// Liability price is the price of matched orders.
+impl<T, C: Currency<A>, A, S> FromOrders<C::Balance, A, S>
+    for SignedAgreement<T, SimpleMarket<A, C>, A, S>
+where
+    Self: Agreement<A, Technical = T>,
+{
+    fn from_orders(
+        technics: T,
+        price: C::Balance,
+        promisee: (A, S),
+        promisor: (A, S),
+        nonce: AgreementNonce,
+    ) -> Option<Self> {
+        Some(SignedAgreement {
+            technics,
+            economics: SimpleMarket { price },
+            promisee: promisee.0,
+            promisor: promisor.0,
+            validator: None,
+            nonce,
+            promisee_signature: promisee.1,
+            promisor_signature: promisor.1,
+        })
+    }
+}
+
+// Order price is in native currency.
+impl<T, C: Currency<A>, F: fungibles::Inspect<A>, V, A, S> FromOrders<C::Balance, A, S>
+    for SignedAgreement<T, NativeOrAssetMarket<A, C, F, V>, A, S>
+where
+    Self: Agreement<A, Technical = T>,
+{
+    fn from_orders(
+        technics: T,
+        price: C::Balance,
+        promisee: (A, S),
+        promisor: (A, S),
+        nonce: AgreementNonce,
+    ) -> Option<Self> {
+        Some(SignedAgreement {
+            technics,
+            economics: NativeOrAssetMarket::Native(SimpleMarket { price }),
+            promisee: promisee.0,
+            promisor: promisor.0,
+            validator: None,
+            nonce,
+            promisee_signature: promisee.1,
+            promisor_signature: promisor.1,
+        })
+    }
+}
+
+// Agreements with economics that couldn't be made of order price.
+impl<T, A, S, P> FromOrders<P, A, S> for SignedAgreement<T, (), A, S>
+where
+    Self: Agreement<A>,
+{
+    fn from_orders(
+        _: Self::Technical,
+        _: P,
+        _: (A, S),
+        _: (A, S),
+        _: AgreementNonce,
+    ) -> Option<Self> {
+        None
+    }
+}
+
+impl<T, C: Currency<A>, A, S, P> FromOrders<P, A, S>
+    for SignedAgreement<T, ReputableMarket<A, C>, A, S>
+where
+    Self: Agreement<A>,
+{
+    fn from_orders(
+        _: Self::Technical,
+        _: P,
+        _: (A, S),
+        _: (A, S),
+        _: AgreementNonce,
+    ) -> Option<Self> {
+        None
+    }
+}
+
+impl<T, C: Currency<A>, A, S, B, P> FromOrders<P, A, S>
+    for SignedAgreement<T, CollateralizedMarket<A, C, B>, A, S>
+where
+    Self: Agreement<A>,
+{
+    fn from_orders(
+        _: Self::Technical,
+        _: P,
+        _: (A, S),
+        _: (A, S),
+        _: AgreementNonce,
+    ) -> Option<Self> {
+        None
+    }
+}
+
+impl<T, C: Currency<A>, A, S, M, B, P> FromOrders<P, A, S>
+    for SignedAgreement<T, MilestoneMarket<A, C, M, B>, A, S>
+where
+    Self: Agreement<A>,
+{
+    fn from_orders(
+        _: Self::Technical,
+        _: P,
+        _: (A, S),
+        _: (A, S),
+        _: AgreementNonce,
+    ) -> Option<Self> {
+        None
+    }
+}
+
+impl<T, F: fungibles::Inspect<A>, V, A, S, P> FromOrders<P, A, S>
+    for SignedAgreement<T, MultiAssetMarket<A, F, V>, A, S>
+where
+    Self: Agreement<A>,
+{
+    fn from_orders(
+        _: Self::Technical,
+        _: P,
+        _: (A, S),
+        _: (A, S),
+        _: AgreementNonce,
+    ) -> Option<Self> {
+        None
+    }
+}
+
 // No economical parameters for agreement.
 impl<T, A, S> Processing for SignedAgreement<T, (), A, S> {
     fn on_start(&self) -> DispatchResult {
@@ -76,13 +244,348 @@ where
             )
             .map(|_| ())
         } else {
-            if C::unreserve(&self.promisee, self.economics.price) == self.economics.price {
+            // Unreserve returns the amount that couldn't be released
+            if C::unreserve(&self.promisee, self.economics.price).is_zero() {
+                Ok(())
+            } else {
+                Err("reserved less than expected")?
+            }
+        }
+    }
+}
+
+impl<T, C, A, S> Processing for SignedAgreement<T, ReputableMarket<A, C>, A, S>
+where
+    C: ReservableCurrency<A>,
+{
+    fn on_start(&self) -> DispatchResult {
+        C::reserve(&self.promisee, self.economics.price)
+    }
+
+    fn on_finish(&self, success: bool) -> DispatchResult {
+        if success {
+            C::repatriate_reserved(
+                &self.promisee,
+                &self.promisor,
+                self.economics.price,
+                BalanceStatus::Free,
+            )
+            .map(|_| ())
+        } else {
+            if C::unreserve(&self.promisee, self.economics.price).is_zero() {
+                Ok(())
+            } else {
+                Err("reserved less than expected")?
+            }
+        }
+    }
+}
+
+impl<T, C, A, S, B> Processing for SignedAgreement<T, CollateralizedMarket<A, C, B>, A, S>
+where
+    C: ReservableCurrency<A>,
+{
+    fn on_start(&self) -> DispatchResult {
+        C::reserve(&self.promisee, self.economics.price)?;
+        C::reserve(&self.promisor, self.economics.collateral)
+    }
+
+    fn on_finish(&self, success: bool) -> DispatchResult {
+        let (payer, payee, payment, refund) = if success {
+            // Promisor gets paid and takes collateral back
+            (
+                &self.promisee,
+                &self.promisor,
+                self.economics.price,
+                self.economics.collateral,
+            )
+        } else {
+            // Promisee takes price back and gets collateral
+            (
+                &self.promisor,
+                &self.promisee,
+                self.economics.collateral,
+                self.economics.price,
+            )
+        };
+        C::repatriate_reserved(payer, payee, payment, BalanceStatus::Free)?;
+        if C::unreserve(payee, refund).is_zero() {
+            Ok(())
+        } else {
+            Err("reserved less than expected")?
+        }
+    }
+}
+
+impl<T, F, V, A, S> Processing for SignedAgreement<T, MultiAssetMarket<A, F, V>, A, S>
+where
+    F: fungibles::MutateHold<A>,
+    F::Reason: From<HoldReason>,
+{
+    fn on_start(&self) -> DispatchResult {
+        F::hold(
+            self.economics.asset_id.clone(),
+            &HoldReason::Escrow.into(),
+            &self.promisee,
+            self.economics.amount,
+        )
+    }
+
+    fn on_finish(&self, success: bool) -> DispatchResult {
+        let reason = HoldReason::Escrow.into();
+        if success {
+            F::transfer_on_hold(
+                self.economics.asset_id.clone(),
+                &reason,
+                &self.promisee,
+                &self.promisor,
+                self.economics.amount,
+                Precision::Exact,
+                Restriction::Free,
+                Fortitude::Polite,
+            )
+            .map(|_| ())
+        } else {
+            F::release(
+                self.economics.asset_id.clone(),
+                &reason,
+                &self.promisee,
+                self.economics.amount,
+                Precision::Exact,
+            )
+            .map(|_| ())
+        }
+    }
+}
+
+impl<T, C, F, V, A, S> Processing for SignedAgreement<T, NativeOrAssetMarket<A, C, F, V>, A, S>
+where
+    C: ReservableCurrency<A>,
+    F: fungibles::MutateHold<A>,
+    F::Reason: From<HoldReason>,
+{
+    fn on_start(&self) -> DispatchResult {
+        match &self.economics {
+            NativeOrAssetMarket::Native(market) => C::reserve(&self.promisee, market.price),
+            NativeOrAssetMarket::Asset(market) => F::hold(
+                market.asset_id.clone(),
+                &HoldReason::Escrow.into(),
+                &self.promisee,
+                market.amount,
+            ),
+        }
+    }
+
+    fn on_finish(&self, success: bool) -> DispatchResult {
+        match &self.economics {
+            NativeOrAssetMarket::Native(market) => {
+                if success {
+                    C::repatriate_reserved(
+                        &self.promisee,
+                        &self.promisor,
+                        market.price,
+                        BalanceStatus::Free,
+                    )
+                    .map(|_| ())
+                } else if C::unreserve(&self.promisee, market.price).is_zero() {
+                    Ok(())
+                } else {
+                    Err("reserved less than expected")?
+                }
+            }
+            NativeOrAssetMarket::Asset(market) => {
+                let reason = HoldReason::Escrow.into();
+                if success {
+                    F::transfer_on_hold(
+                        market.asset_id.clone(),
+                        &reason,
+                        &self.promisee,
+                        &self.promisor,
+                        market.amount,
+                        Precision::Exact,
+                        Restriction::Free,
+                        Fortitude::Polite,
+                    )
+                    .map(|_| ())
+                } else {
+                    F::release(
+                        market.asset_id.clone(),
+                        &reason,
+                        &self.promisee,
+                        market.amount,
+                        Precision::Exact,
+                    )
+                    .map(|_| ())
+                }
+            }
+        }
+    }
+}
+
+impl<T, C, A, S, M, B> Processing for SignedAgreement<T, MilestoneMarket<A, C, M, B>, A, S>
+where
+    C: ReservableCurrency<A>,
+{
+    fn on_start(&self) -> DispatchResult {
+        let total = self
+            .economics
+            .milestones
+            .iter()
+            .fold(Zero::zero(), |total: C::Balance, m| {
+                total.saturating_add(m.amount)
+            });
+        C::reserve(&self.promisee, total)
+    }
+
+    // Liability price already paid or refunded by milestones.
+    fn on_finish(&self, _success: bool) -> DispatchResult {
+        Ok(())
+    }
+}
+
+impl<T, C, A, S, M, B> Milestones for SignedAgreement<T, MilestoneMarket<A, C, M, B>, A, S>
+where
+    C: ReservableCurrency<A>,
+    M: Encode,
+{
+    fn milestones(&self) -> u32 {
+        self.economics.milestones.len() as u32
+    }
+
+    fn on_milestone(&self, n: u32, success: bool) -> DispatchResult {
+        let amount = self
+            .economics
+            .milestones
+            .get(n as usize)
+            .ok_or("unknown milestone")?
+            .amount;
+        if success {
+            C::repatriate_reserved(&self.promisee, &self.promisor, amount, BalanceStatus::Free)
+                .map(|_| ())
+        } else {
+            if C::unreserve(&self.promisee, amount).is_zero() {
                 Ok(())
             } else {
                 Err("reserved less than expected")?
             }
         }
     }
+
+    fn is_milestone_report<P: Encode>(&self, n: u32, payload: &P) -> bool {
+        self.economics
+            .milestones
+            .get(n as usize)
+            .map_or(false, |m| {
+                m.technics
+                    .using_encoded(|technics| payload.using_encoded(|p| technics == p))
+            })
+    }
+}
+
+// Agreement paid at once, without milestones.
+impl<T, A, S> Milestones for SignedAgreement<T, (), A, S> {
+    fn milestones(&self) -> u32 {
+        0
+    }
+    fn on_milestone(&self, _n: u32, _success: bool) -> DispatchResult {
+        Err("agreement has no milestones")?
+    }
+    fn is_milestone_report<P: Encode>(&self, _n: u32, _payload: &P) -> bool {
+        false
+    }
+}
+
+impl<T, F: fungibles::Inspect<A>, V, A, S> Milestones
+    for SignedAgreement<T, MultiAssetMarket<A, F, V>, A, S>
+{
+    fn milestones(&self) -> u32 {
+        0
+    }
+    fn on_milestone(&self, _n: u32, _success: bool) -> DispatchResult {
+        Err("agreement has no milestones")?
+    }
+    fn is_milestone_report<P: Encode>(&self, _n: u32, _payload: &P) -> bool {
+        false
+    }
+}
+
+impl<T, C: Currency<A>, F: fungibles::Inspect<A>, V, A, S> Milestones
+    for SignedAgreement<T, NativeOrAssetMarket<A, C, F, V>, A, S>
+{
+    fn milestones(&self) -> u32 {
+        0
+    }
+    fn on_milestone(&self, _n: u32, _success: bool) -> DispatchResult {
+        Err("agreement has no milestones")?
+    }
+    fn is_milestone_report<P: Encode>(&self, _n: u32, _payload: &P) -> bool {
+        false
+    }
+}
+
+impl<T, C: Currency<A>, A, S> Milestones for SignedAgreement<T, SimpleMarket<A, C>, A, S> {
+    fn milestones(&self) -> u32 {
+        0
+    }
+    fn on_milestone(&self, _n: u32, _success: bool) -> DispatchResult {
+        Err("agreement has no milestones")?
+    }
+    fn is_milestone_report<P: Encode>(&self, _n: u32, _payload: &P) -> bool {
+        false
+    }
+}
+
+impl<T, C: Currency<A>, A, S> Milestones for SignedAgreement<T, ReputableMarket<A, C>, A, S> {
+    fn milestones(&self) -> u32 {
+        0
+    }
+    fn on_milestone(&self, _n: u32, _success: bool) -> DispatchResult {
+        Err("agreement has no milestones")?
+    }
+    fn is_milestone_report<P: Encode>(&self, _n: u32, _payload: &P) -> bool {
+        false
+    }
+}
+
+impl<T, C: Currency<A>, A, S, B> Milestones
+    for SignedAgreement<T, CollateralizedMarket<A, C, B>, A, S>
+{
+    fn milestones(&self) -> u32 {
+        0
+    }
+    fn on_milestone(&self, _n: u32, _success: bool) -> DispatchResult {
+        Err("agreement has no milestones")?
+    }
+    fn is_milestone_report<P: Encode>(&self, _n: u32, _payload: &P) -> bool {
+        false
+    }
+}
+
+impl<T, E, A, S, B> Expiration<B> for SignedAgreement<T, E, A, S>
+where
+    E: Expiration<B>,
+{
+    fn expires_at(&self) -> Option<B> {
+        self.economics.expires_at()
+    }
+}
+
+impl<T, E, A, S, B> Valuation<B> for SignedAgreement<T, E, A, S>
+where
+    E: Valuation<B>,
+{
+    fn value(&self) -> B {
+        self.economics.value()
+    }
+}
+
+impl<T, E, A, S, R> MinReputation<R> for SignedAgreement<T, E, A, S>
+where
+    E: MinReputation<R>,
+{
+    fn is_acceptable(&self, reputation: &R) -> bool {
+        self.economics.is_acceptable(reputation)
+    }
 }
 
 impl<T, E, A, V, I> Agreement<I> for SignedAgreement<T, E, I, V>
@@ -90,7 +593,7 @@ where
     A: IdentifyAccount<AccountId = I>,
     V: Verify<Signer = A> + dispatch::Parameter,
     I: dispatch::Parameter,
-    T: dispatch::Parameter,
+    T: dispatch::Parameter + MaxEncodedLen,
     E: dispatch::Parameter,
 {
     type Technical = T;
@@ -112,19 +615,33 @@ where
         self.promisor.clone()
     }
 
-    fn verify(&self) -> bool {
+    fn validator(&self) -> Option<I> {
+        self.validator.clone()
+    }
+
+    fn nonce(&self) -> AgreementNonce {
+        self.nonce
+    }
+
+    fn verify(&self, domain: &[u8]) -> bool {
         #[cfg(feature = "runtime-benchmarks")]
         {
             // In benchmark mode, skip signature verification
+            let _ = domain;
             return true;
         }
 
         #[cfg(not(feature = "runtime-benchmarks"))]
         {
-            (self.technics.clone(), self.economics.clone()).using_encoded(|encoded| {
-                self.promisee_signature.verify(encoded, &self.promisee)
-                    && self.promisor_signature.verify(encoded, &self.promisor)
-            })
+            let encoded = agreement_payload(
+                domain,
+                &self.technics,
+                &self.economics,
+                &self.validator,
+                &self.nonce,
+            );
+            self.promisee_signature.verify(&encoded[..], &self.promisee)
+                && self.promisor_signature.verify(&encoded[..], &self.promisor)
         }
     }
 }
@@ -145,6 +662,8 @@ impl<I, A, S, M> RealWorldOracle for SignedReport<I, A, S, M> {
         // Confirm all by default
         Some(true)
     }
+
+    fn confirm(&mut self, _verdict: bool) {}
 }
 
 impl<Index, A, V, I, M> Report<Index, I> for SignedReport<Index, I, V, M>
@@ -180,11 +699,69 @@ where
     }
 }
 
+/// Report that should be confirmed by validator named in agreement.
+#[derive(
+    Encode, Decode, Clone, PartialEq, Eq, TypeInfo, Debug, MaxEncodedLen, DecodeWithMemTracking,
+)]
+pub struct ValidatedReport<Index, AccountId, Signature, Message> {
+    pub index: Index,
+    pub sender: AccountId,
+    pub payload: Message,
+    pub signature: Signature,
+    pub verdict: Option<bool>,
+}
+
+impl<I, A, S, M> RealWorldOracle for ValidatedReport<I, A, S, M> {
+    const VALIDATED: bool = true;
+
+    fn is_confirmed(&self) -> Option<bool> {
+        // Pending until validator verdict
+        self.verdict
+    }
+
+    fn confirm(&mut self, verdict: bool) {
+        self.verdict = Some(verdict);
+    }
+}
+
+impl<Index, A, V, I, M> Report<Index, I> for ValidatedReport<Index, I, V, M>
+where
+    Index: dispatch::Parameter,
+    A: IdentifyAccount<AccountId = I>,
+    V: Verify<Signer = A> + dispatch::Parameter,
+    M: dispatch::Parameter,
+    I: dispatch::Parameter,
+{
+    type Message = M;
+
+    fn index(&self) -> Index {
+        self.index.clone()
+    }
+
+    fn sender(&self) -> I {
+        self.sender.clone()
+    }
+
+    fn verify(&self) -> bool {
+        #[cfg(feature = "runtime-benchmarks")]
+        {
+            // In benchmark mode, skip signature verification
+            return true;
+        }
+
+        #[cfg(not(feature = "runtime-benchmarks"))]
+        {
+            (self.index.clone(), self.payload.clone())
+                .using_encoded(|encoded| self.signature.verify(encoded, &self.sender))
+        }
+    }
+}
+
 /// Runtime AppCrypto proof builder.
 pub struct AppProofSigner<T>(PhantomData<T>);
 
-impl<T, E, A, AccountId, Signature, AppSigner> AgreementProofBuilder<T, E, AccountId, Signature>
-    for AppProofSigner<AppSigner>
+impl<T, E, V, A, AccountId, Signature, AppSigner>
+    AgreementProofBuilder<T, E, V, AccountId, Signature> for AppProofSigner<AppSigner>
 where
     AppSigner: AppCrypto<AccountId, Signature>,
     A: IdentifyAccount<AccountId = AccountId>,
@@ -192,10 +769,18 @@ where
     AccountId: Clone,
     T: Encode,
     E: Encode,
+    V: Encode,
 {
-    fn proof(technics: &T, economics: &E, sender: &AccountId) -> Signature {
-        (technics, economics)
-            .using_encoded(|params| AppSigner::sign(params, sender.clone()))
+    fn proof(
+        domain: &[u8],
+        technics: &T,
+        economics: &E,
+        validator: &Option<V>,
+        nonce: &AgreementNonce,
+        sender: &AccountId,
+    ) -> Signature {
+        let params = agreement_payload(domain, technics, economics, validator, nonce);
+        AppSigner::sign(&params[..], sender.clone())
             .expect("unable to sign using runtime application key")
     }
 }
@@ -222,17 +807,25 @@ where
 pub struct ProofSigner<T>(std::marker::PhantomData<T>);
 
 #[cfg(feature = "std")]
-impl<T, E, Account, AccountId, Signature, TPair> AgreementProofBuilder<T, E, TPair, Signature>
+impl<T, E, V, Account, AccountId, Signature, TPair> AgreementProofBuilder<T, E, V, TPair, Signature>
     for ProofSigner<TPair>
 where
     T: Encode,
     E: Encode,
+    V: Encode,
     TPair: Pair<Public = Account, Signature = Signature>,
     Account: IdentifyAccount<AccountId = AccountId> + Public + std::hash::Hash,
     Signature: Verify<Signer = Account>,
 {
-    fn proof(technics: &T, economics: &E, sender: &TPair) -> Signature {
-        (technics, economics).using_encoded(|params| sender.sign(params))
+    fn proof(
+        domain: &[u8],
+        technics: &T,
+        economics: &E,
+        validator: &Option<V>,
+        nonce: &AgreementNonce,
+        sender: &TPair,
+    ) -> Signature {
+        sender.sign(&agreement_payload(domain, technics, economics, validator, nonce)[..])
     }
 }
 
@@ -17,10 +17,14 @@
 ///////////////////////////////////////////////////////////////////////////////
 //! Set of approaches to handle technical aspects of agreement.
 
+use frame_support::{traits::ConstU32, BoundedVec};
 use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 use sp_core::H256;
-use sp_std::prelude::*;
+
+pub use robonomics_liability_technics::{
+    ParseError, DAG_PB, MAX_DIGEST_LEN, MAX_INLINE_LEN, RAW, SHA2_256,
+};
 
 /// Using IPFS to handle technical aspects of agreement without confirmation.
 #[derive(
@@ -29,3 +33,20 @@ use sp_std::prelude::*;
 pub struct IPFS {
     pub hash: H256,
 }
+
+/// Technical aspects of agreement: content identifier of task description,
+/// small inline task specification or reference to CPS node.
+pub type Technics = robonomics_liability_technics::Technics<
+    BoundedVec<u8, ConstU32<MAX_DIGEST_LEN>>,
+    BoundedVec<u8, ConstU32<MAX_INLINE_LEN>>,
+>;
+
+impl From<IPFS> for Technics {
+    fn from(ipfs: IPFS) -> Self {
+        Technics::Cid {
+            codec: DAG_PB,
+            hash: SHA2_256,
+            digest: BoundedVec::truncate_from(ipfs.hash.as_bytes().to_vec()),
+        }
+    }
+}
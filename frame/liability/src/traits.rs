@@ -17,8 +17,12 @@
 ///////////////////////////////////////////////////////////////////////////////
 //! Robonomics liability traits definitions.
 
-use frame_support::dispatch;
-use sp_runtime::DispatchResult;
+use frame_support::{dispatch, pallet_prelude::MaxEncodedLen};
+use parity_scale_codec::Encode;
+use sp_runtime::{traits::Zero, DispatchResult};
+
+/// Agreement nonce, makes each agreement signed by promisee unique.
+pub type AgreementNonce = u64;
 
 /// Transaction processing of agreement. Usually it consists of
 /// balance locking and transfers when liability successfully finished.
@@ -30,13 +34,82 @@ pub trait Processing {
     fn on_finish(&self, success: bool) -> DispatchResult;
 }
 
+/// Agreement that could be made on chain by matching demand and offer orders.
+pub trait FromOrders<Balance, AccountId, Signature>: Agreement<AccountId> + Sized {
+    /// Make agreement of matched orders, parties are given with signatures of their orders.
+    /// Order signatures don't prove agreement parameters, so matched orders are kept as
+    /// agreement proof. Returns `None` when agreement economics couldn't be made of order price.
+    fn from_orders(
+        technics: Self::Technical,
+        price: Balance,
+        promisee: (AccountId, Signature),
+        promisor: (AccountId, Signature),
+        nonce: AgreementNonce,
+    ) -> Option<Self>;
+}
+
+/// Agreement that paid by parts when milestones of execution are reached.
+pub trait Milestones {
+    /// Number of agreement milestones, zero means agreement paid at once on finish.
+    fn milestones(&self) -> u32;
+
+    /// This method called when milestone `n` reached (success) or expired.
+    fn on_milestone(&self, n: u32, success: bool) -> DispatchResult;
+
+    /// Check that report `payload` delivers technics of milestone `n`.
+    fn is_milestone_report<P: Encode>(&self, n: u32, payload: &P) -> bool;
+}
+
+/// Agreement that should be reported until some deadline.
+pub trait Expiration<BlockNumber> {
+    /// Last block when agreement report could be published,
+    /// `None` means agreement never expires.
+    fn expires_at(&self) -> Option<BlockNumber>;
+}
+
+impl<BlockNumber> Expiration<BlockNumber> for () {
+    fn expires_at(&self) -> Option<BlockNumber> {
+        None
+    }
+}
+
+/// Agreement value in native currency, it's accounted in promisor reputation.
+pub trait Valuation<Balance> {
+    /// Amount paid to promisor when liability successfully finished.
+    fn value(&self) -> Balance;
+}
+
+impl<Balance: Zero> Valuation<Balance> for () {
+    fn value(&self) -> Balance {
+        Zero::zero()
+    }
+}
+
+/// Agreement that could be made only with promisor of acceptable reputation.
+pub trait MinReputation<Reputation> {
+    /// Check promisor reputation before liability started.
+    fn is_acceptable(&self, reputation: &Reputation) -> bool;
+}
+
+impl<Reputation> MinReputation<Reputation> for () {
+    fn is_acceptable(&self, _reputation: &Reputation) -> bool {
+        true
+    }
+}
+
 /// Someone who can confirm agreement execution in real world.
 pub trait RealWorldOracle {
+    /// Report could stay pending until verdict of validator named in agreement.
+    const VALIDATED: bool = false;
+
     /// Waiting for replay from real world oracle.
     ///   None -> oracle decision isn't ready yet;
     ///   Some(true) -> oracle agree with report;
     ///   Some(false) -> oracle disagree with the report.
     fn is_confirmed(&self) -> Option<bool>;
+
+    /// Store real world oracle decision on the report.
+    fn confirm(&mut self, verdict: bool);
 }
 
 /// During execution one part of agreement should prepare report,
@@ -63,7 +136,7 @@ pub trait Report<Index, AccountId>: RealWorldOracle {
 pub trait Agreement<AccountId> {
     /// Technical parameter of agreement. Usually in this parameter one party of agreement
     /// provide technical information like task description, parameters and some additional data.
-    type Technical: dispatch::Parameter;
+    type Technical: dispatch::Parameter + MaxEncodedLen;
 
     /// Economical parameter of agreement. Usually in this parameter one party set task cost
     /// for another party. To come an agreement both parties should be agree with this parameter.
@@ -81,14 +154,29 @@ pub trait Agreement<AccountId> {
     /// The executive account.
     fn promisor(&self) -> AccountId;
 
-    /// Check validity of agreement params proof.
-    fn verify(&self) -> bool;
+    /// The third party account that confirms execution report, if any.
+    fn validator(&self) -> Option<AccountId>;
+
+    /// Promisee nonce of this agreement, protects agreement from replay.
+    fn nonce(&self) -> AgreementNonce;
+
+    /// Check validity of agreement params proof, `domain` separates signatures of different
+    /// chains (usually it's a chain genesis hash).
+    fn verify(&self, domain: &[u8]) -> bool;
 }
 
 /// Agreement proof maker.
-pub trait AgreementProofBuilder<Technical, Economical, Account, Proof> {
-    /// Make proof of technical and economical agreement parameters.
-    fn proof(technics: &Technical, economics: &Economical, sender: &Account) -> Proof;
+pub trait AgreementProofBuilder<Technical, Economical, Validator, Account, Proof> {
+    /// Make proof of technical and economical agreement parameters and report validator
+    /// for given chain `domain` and promisee `nonce`.
+    fn proof(
+        domain: &[u8],
+        technics: &Technical,
+        economics: &Economical,
+        validator: &Option<Validator>,
+        nonce: &AgreementNonce,
+        sender: &Account,
+    ) -> Proof;
 }
 
 /// Report proof maker.
@@ -25,6 +25,16 @@ use frame_support::weights::Weight;
 pub trait WeightInfo {
     fn create() -> Weight;
     fn finalize() -> Weight;
+    fn confirm() -> Weight;
+    fn dispute() -> Weight;
+    fn resolve() -> Weight;
+    fn settle() -> Weight;
+    fn expire() -> Weight;
+    fn report_milestone() -> Weight;
+    fn place_order() -> Weight;
+    fn cancel_order() -> Weight;
+    fn match_orders() -> Weight;
+    fn prune() -> Weight;
 }
 
 /// Test weight implementation that returns zero weight for all operations.
@@ -38,4 +48,34 @@ impl WeightInfo for TestWeightInfo {
     fn finalize() -> Weight {
         Weight::zero()
     }
+    fn confirm() -> Weight {
+        Weight::zero()
+    }
+    fn dispute() -> Weight {
+        Weight::zero()
+    }
+    fn resolve() -> Weight {
+        Weight::zero()
+    }
+    fn settle() -> Weight {
+        Weight::zero()
+    }
+    fn expire() -> Weight {
+        Weight::zero()
+    }
+    fn report_milestone() -> Weight {
+        Weight::zero()
+    }
+    fn place_order() -> Weight {
+        Weight::zero()
+    }
+    fn cancel_order() -> Weight {
+        Weight::zero()
+    }
+    fn match_orders() -> Weight {
+        Weight::zero()
+    }
+    fn prune() -> Weight {
+        Weight::zero()
+    }
 }
@@ -0,0 +1,308 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2026 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Liability technics shared by runtime and off-chain clients.
+//!
+//! [`Technics`] is generic over digest and inline containers: runtime uses bounded
+//! vectors while clients use plain `Vec<u8>`, both have the same SCALE encoding.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{format, string::String, vec::Vec};
+use core::{fmt, str::FromStr};
+use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+
+/// Multicodec code of DAG-PB, the codec of CIDv0.
+pub const DAG_PB: u64 = 0x70;
+/// Multicodec code of raw binary content.
+pub const RAW: u64 = 0x55;
+/// Multihash code of SHA2-256.
+pub const SHA2_256: u64 = 0x12;
+
+/// Maximal length of content identifier digest.
+pub const MAX_DIGEST_LEN: u32 = 64;
+/// Maximal length of inline task specification.
+pub const MAX_INLINE_LEN: u32 = 256;
+
+/// Technical aspects of agreement: content identifier of task description,
+/// small inline task specification or reference to CPS node.
+///
+/// Text form is a CID string (`Qm...`, `b...` or `z...`), `node:<id>`
+/// for CPS node or `inline:<text>` for task stored on chain.
+#[derive(
+    Encode, Decode, Clone, PartialEq, Eq, TypeInfo, Debug, MaxEncodedLen, DecodeWithMemTracking,
+)]
+pub enum Technics<Digest, Inline> {
+    /// Content identifier (CIDv1) with multicodec `codec` and multihash `hash` code.
+    #[codec(index = 0)]
+    Cid {
+        #[codec(compact)]
+        codec: u64,
+        #[codec(compact)]
+        hash: u64,
+        digest: Digest,
+    },
+    /// Task specification stored on chain.
+    #[codec(index = 1)]
+    Inline(Inline),
+    /// Reference to CPS node that describes the task.
+    #[codec(index = 2)]
+    CpsNode(#[codec(compact)] u64),
+}
+
+impl<Digest, Inline> Technics<Digest, Inline>
+where
+    Digest: AsRef<[u8]> + TryFrom<Vec<u8>>,
+{
+    /// Content identifier technics, `None` when digest is too long.
+    pub fn cid(codec: u64, hash: u64, digest: &[u8]) -> Option<Self> {
+        if digest.len() > MAX_DIGEST_LEN as usize {
+            return None;
+        }
+        Some(Technics::Cid {
+            codec,
+            hash,
+            digest: digest.to_vec().try_into().ok()?,
+        })
+    }
+
+    /// Binary CIDv1 of content identifier technics.
+    pub fn to_cid_bytes(&self) -> Option<Vec<u8>> {
+        match self {
+            Technics::Cid {
+                codec,
+                hash,
+                digest,
+            } => {
+                let digest = digest.as_ref();
+                let mut bytes = Vec::with_capacity(digest.len() + 8);
+                for n in [1, *codec, *hash, digest.len() as u64] {
+                    write_varint(n, &mut bytes);
+                }
+                bytes.extend_from_slice(digest);
+                Some(bytes)
+            }
+            _ => None,
+        }
+    }
+
+    /// Parse binary CIDv0 or CIDv1.
+    pub fn from_cid_bytes(bytes: &[u8]) -> Option<Self> {
+        // CIDv0 is a bare SHA2-256 multihash
+        if bytes.len() == 34 && bytes[0] == SHA2_256 as u8 && bytes[1] == 32 {
+            return Self::cid(DAG_PB, SHA2_256, &bytes[2..]);
+        }
+
+        let mut input = bytes;
+        if read_varint(&mut input)? != 1 {
+            return None;
+        }
+        let codec = read_varint(&mut input)?;
+        let hash = read_varint(&mut input)?;
+        let len = read_varint(&mut input)?;
+        if input.len() as u64 != len {
+            return None;
+        }
+        Self::cid(codec, hash, input)
+    }
+
+    /// CIDv1 string in base32 multibase.
+    pub fn to_cid_string(&self) -> Option<String> {
+        Some(format!("b{}", base32_encode(&self.to_cid_bytes()?)))
+    }
+
+    /// Parse CIDv0 string or CIDv1 string in base32 or base58btc multibase.
+    pub fn from_cid_str(cid: &str) -> Option<Self> {
+        let bytes = if cid.len() == 46 && cid.starts_with("Qm") {
+            base58_decode(cid)?
+        } else if let Some(encoded) = cid.strip_prefix('b') {
+            base32_decode(encoded)?
+        } else if let Some(encoded) = cid.strip_prefix('z') {
+            base58_decode(encoded)?
+        } else {
+            return None;
+        };
+        Self::from_cid_bytes(&bytes)
+    }
+}
+
+/// Error of parsing technics text form.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ParseError {
+    /// CPS node identifier isn't a number.
+    InvalidNode,
+    /// Inline task is longer than [`MAX_INLINE_LEN`].
+    InlineTooLong,
+    /// Malformed content identifier.
+    InvalidCid,
+    /// Text isn't a CID, `node:<id>` or `inline:<text>`.
+    Unsupported,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidNode => write!(f, "invalid CPS node ID"),
+            ParseError::InlineTooLong => {
+                write!(f, "inline technics longer than {} bytes", MAX_INLINE_LEN)
+            }
+            ParseError::InvalidCid => write!(f, "invalid CID"),
+            ParseError::Unsupported => {
+                write!(f, "expected CID, node:<id> or inline:<text>")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+impl<Digest, Inline> FromStr for Technics<Digest, Inline>
+where
+    Digest: AsRef<[u8]> + TryFrom<Vec<u8>>,
+    Inline: TryFrom<Vec<u8>>,
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(id) = s.strip_prefix("node:") {
+            let id = id.parse().map_err(|_| ParseError::InvalidNode)?;
+            return Ok(Technics::CpsNode(id));
+        }
+
+        if let Some(text) = s.strip_prefix("inline:") {
+            if text.len() > MAX_INLINE_LEN as usize {
+                return Err(ParseError::InlineTooLong);
+            }
+            let inline = text
+                .as_bytes()
+                .to_vec()
+                .try_into()
+                .map_err(|_| ParseError::InlineTooLong)?;
+            return Ok(Technics::Inline(inline));
+        }
+
+        if !(s.starts_with("Qm") || s.starts_with('b') || s.starts_with('z')) {
+            return Err(ParseError::Unsupported);
+        }
+        Self::from_cid_str(s).ok_or(ParseError::InvalidCid)
+    }
+}
+
+impl<Digest, Inline> fmt::Display for Technics<Digest, Inline>
+where
+    Digest: AsRef<[u8]> + TryFrom<Vec<u8>>,
+    Inline: AsRef<[u8]>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Technics::Cid { .. } => {
+                write!(f, "{}", self.to_cid_string().unwrap_or_default())
+            }
+            Technics::Inline(data) => {
+                write!(f, "inline:{}", String::from_utf8_lossy(data.as_ref()))
+            }
+            Technics::CpsNode(id) => write!(f, "node:{}", id),
+        }
+    }
+}
+
+fn write_varint(mut n: u64, out: &mut Vec<u8>) {
+    while n >= 0x80 {
+        out.push((n as u8) | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+fn read_varint(input: &mut &[u8]) -> Option<u64> {
+    let mut n = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = input.split_first()?;
+        *input = rest;
+        n |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(n);
+        }
+    }
+    None
+}
+
+const BASE32_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+fn base32_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len() * 8 / 5 + 1);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for &byte in data {
+        buffer = (buffer << 8) | u32::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
+
+fn base32_decode(encoded: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(encoded.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for c in encoded.bytes() {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|&a| a == c.to_ascii_lowercase())?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(out)
+}
+
+fn base58_decode(encoded: &str) -> Option<Vec<u8>> {
+    // Big number in little-endian bytes
+    let mut bytes: Vec<u8> = Vec::with_capacity(encoded.len());
+    for c in encoded.bytes() {
+        let mut carry = BASE58_ALPHABET.iter().position(|&a| a == c)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    let zeros = encoded.bytes().take_while(|&c| c == b'1').count();
+    Some(
+        core::iter::repeat_n(0, zeros)
+            .chain(bytes.into_iter().rev())
+            .collect(),
+    )
+}
@@ -40,8 +40,9 @@ use frame_support::{
     genesis_builder_helper::{build_state, get_preset},
     parameter_types,
     traits::{
-        fungible, tokens::imbalance::ResolveTo, ConstBool, ConstU32, ConstU64, Imbalance,
-        InstanceFilter, OnUnbalanced, WithdrawReasons,
+        fungible,
+        tokens::{imbalance::ResolveTo, ConversionFromAssetBalance},
+        ConstBool, ConstU32, ConstU64, Imbalance, InstanceFilter, OnUnbalanced, WithdrawReasons,
     },
     weights::{ConstantMultiplier, Weight},
     PalletId,
@@ -154,6 +155,8 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
                         pallet_robonomics_cps::Call::create_node { .. }
                             | pallet_robonomics_cps::Call::set_meta { .. }
                             | pallet_robonomics_cps::Call::set_payload { .. }
+                            | pallet_robonomics_cps::Call::set_meta_if { .. }
+                            | pallet_robonomics_cps::Call::set_payload_if { .. }
                             | pallet_robonomics_cps::Call::move_node { .. }
                             | pallet_robonomics_cps::Call::delete_node { .. }
                     )
@@ -175,6 +178,13 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
                     | RuntimeCall::CPS(pallet_robonomics_cps::Call::set_payload {
                         node_id, ..
                     })
+                    | RuntimeCall::CPS(pallet_robonomics_cps::Call::set_meta_if {
+                        node_id, ..
+                    })
+                    | RuntimeCall::CPS(pallet_robonomics_cps::Call::set_payload_if {
+                        node_id,
+                        ..
+                    })
                     | RuntimeCall::CPS(pallet_robonomics_cps::Call::move_node {
                         node_id, ..
                     })
@@ -375,6 +385,45 @@ impl pallet_vesting::Config for Runtime {
     const MAX_VESTING_SCHEDULES: u32 = 28;
 }
 
+parameter_types! {
+    pub const ForeignAssetDeposit: Balance = 10 * XRT;
+    pub const ForeignAssetAccountDeposit: Balance = deposit(1, 16);
+    pub const ForeignAssetApprovalDeposit: Balance = ExistentialDeposit::get();
+    pub const ForeignAssetMetadataDepositBase: Balance = deposit(1, 68);
+    pub const ForeignAssetMetadataDepositPerByte: Balance = deposit(0, 1);
+}
+
+/// Assets bridged from other consensus systems are identified by their XCM location.
+impl pallet_assets::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type AssetId = xcm::latest::Location;
+    type AssetIdParameter = xcm::latest::Location;
+    type Currency = Balances;
+    type CreateOrigin = xcm_config::ForeignCreators;
+    type ForceOrigin = EnsureRoot<AccountId>;
+    type AssetDeposit = ForeignAssetDeposit;
+    type AssetAccountDeposit = ForeignAssetAccountDeposit;
+    type MetadataDepositBase = ForeignAssetMetadataDepositBase;
+    type MetadataDepositPerByte = ForeignAssetMetadataDepositPerByte;
+    type ApprovalDeposit = ForeignAssetApprovalDeposit;
+    type StringLimit = ConstU32<50>;
+    type Holder = AssetsHolder;
+    type Freezer = ();
+    type Extra = ();
+    type ReserveData = ();
+    type CallbackHandle = ();
+    type RemoveItemsLimit = ConstU32<1000>;
+    type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = xcm_config::ForeignAssetBenchmarkHelper;
+}
+
+impl pallet_assets_holder::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeHoldReason = RuntimeHoldReason;
+}
+
 parameter_types! {
     pub const TransactionByteFee: Balance = 1 * COASE;
     pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
@@ -623,21 +672,66 @@ impl pallet_robonomics_rws::Config for Runtime {
     type WeightInfo = weights::pallet_robonomics_rws::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+    pub const CpsNodeDepositBase: Balance = deposit(1, 64);
+    pub const CpsByteDeposit: Balance = deposit(0, 1);
+}
+
 impl pallet_robonomics_cps::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type OnPayloadSet = ();
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type Currency = Balances;
+    type NodeDepositBase = CpsNodeDepositBase;
+    type ByteDeposit = CpsByteDeposit;
+    type Time = Timestamp;
     type WeightInfo = weights::pallet_robonomics_cps::WeightInfo<Runtime>;
 }
 
 impl pallet_robonomics_digital_twin::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
+    type MaxTopics = ConstU32<{ pallet_robonomics_cps::MAX_CHILDREN_PER_NODE }>;
+    type MaxSources = ConstU32<8>;
     type WeightInfo = weights::pallet_robonomics_digital_twin::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+    pub const LiabilityChallengePeriod: BlockNumber = 1 * DAYS;
+    pub const LiabilityDisputeBond: Balance = 1 * XRT;
+    pub const LiabilityResolutionPeriod: BlockNumber = 7 * DAYS;
+    pub const LiabilityOrderDeposit: Balance = deposit(1, 256);
+    pub const LiabilityStorageDeposit: Balance = deposit(2, 512);
+    pub const LiabilityRetentionPeriod: BlockNumber = 30 * DAYS;
+}
+
+/// Foreign assets have no conversion rate to XRT, liabilities paid in them
+/// have no native value and don't make promisor reputation.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ForeignAssetValue;
+
+impl ConversionFromAssetBalance<Balance, xcm::latest::Location, Balance> for ForeignAssetValue {
+    type Error = ();
+
+    fn from_asset_balance(
+        _balance: Balance,
+        _asset_id: xcm::latest::Location,
+    ) -> Result<Balance, ()> {
+        Err(())
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn ensure_successful(_asset_id: xcm::latest::Location) {}
+}
+
 impl pallet_robonomics_liability::Config for Runtime {
     type Agreement = pallet_robonomics_liability::SignedAgreement<
-        pallet_robonomics_liability::technics::IPFS,
-        pallet_robonomics_liability::economics::SimpleMarket<Self::AccountId, Balances>,
+        pallet_robonomics_liability::technics::Technics,
+        pallet_robonomics_liability::economics::NativeOrAssetMarket<
+            Self::AccountId,
+            Balances,
+            AssetsHolder,
+            ForeignAssetValue,
+        >,
         Self::AccountId,
         sp_runtime::MultiSignature,
     >;
@@ -647,6 +741,18 @@ impl pallet_robonomics_liability::Config for Runtime {
         sp_runtime::MultiSignature,
         pallet_robonomics_liability::technics::IPFS,
     >;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type Currency = Balances;
+    type Evidence = pallet_robonomics_liability::technics::IPFS;
+    type ChallengePeriod = LiabilityChallengePeriod;
+    type DisputeBond = LiabilityDisputeBond;
+    type ArbiterOrigin = EnsureRoot<AccountId>;
+    type ResolutionPeriod = LiabilityResolutionPeriod;
+    type OrderSignature = sp_runtime::MultiSignature;
+    type MaxOrders = ConstU32<100>;
+    type OrderDeposit = LiabilityOrderDeposit;
+    type StorageDeposit = LiabilityStorageDeposit;
+    type RetentionPeriod = LiabilityRetentionPeriod;
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = weights::pallet_robonomics_liability::WeightInfo<Runtime>;
 }
@@ -719,6 +825,12 @@ mod runtime {
     #[runtime::pallet_index(33)]
     pub type Vesting = pallet_vesting;
 
+    #[runtime::pallet_index(34)]
+    pub type ForeignAssets = pallet_assets;
+
+    #[runtime::pallet_index(35)]
+    pub type AssetsHolder = pallet_assets_holder;
+
     //
     // Robonomics Network pallets.
     //
@@ -822,6 +934,23 @@ pub type Executive = frame_executive::Executive<
     AllPalletsWithSystem,
 >;
 
+/// Liability agreement layout of storage versions 1 and 2, technics stored as IPFS hash.
+type LiabilityAgreementV2 = pallet_robonomics_liability::SignedAgreement<
+    pallet_robonomics_liability::technics::IPFS,
+    pallet_robonomics_liability::economics::SimpleMarket<AccountId, Balances>,
+    AccountId,
+    sp_runtime::MultiSignature,
+>;
+
+/// Liability market order layout of storage version 2, technics stored as IPFS hash.
+type LiabilityOrderV2 = pallet_robonomics_liability::Order<
+    pallet_robonomics_liability::technics::IPFS,
+    Balance,
+    AccountId,
+    BlockNumber,
+    sp_runtime::MultiSignature,
+>;
+
 /// Migrations to apply on runtime upgrade.
 type SingleBlockMigrations = (
     // Permanent
@@ -831,6 +960,38 @@ type SingleBlockMigrations = (
     // XCMP Queue migrations: v5 → v6 → v7
     cumulus_pallet_xcmp_queue::migration::v6::MigrateV5ToV6<Runtime>,
     cumulus_pallet_xcmp_queue::migration::v7::MigrateV6ToV7<Runtime>,
+    // Liability agreements got replay protection nonce
+    pallet_robonomics_liability::migration::MigrationToV1<
+        Runtime,
+        pallet_robonomics_liability::migration::OldSignedAgreement<
+            pallet_robonomics_liability::technics::IPFS,
+            pallet_robonomics_liability::economics::SimpleMarket<AccountId, Balances>,
+            AccountId,
+            sp_runtime::MultiSignature,
+        >,
+        LiabilityAgreementV2,
+    >,
+    // Liability promisee and promisor indexes, promisor reputation
+    pallet_robonomics_liability::migration::MigrationToV2<Runtime, LiabilityAgreementV2>,
+    // Liability IPFS technics of agreements and orders converted to CID,
+    // agreement prices to native variant of multi-asset economics
+    pallet_robonomics_liability::migration::MigrationToV3<
+        Runtime,
+        pallet_robonomics_liability::migration::OldTechnics<LiabilityAgreementV2>,
+        pallet_robonomics_liability::migration::OldTechnics<LiabilityOrderV2>,
+    >,
+    // CPS node storage deposits
+    pallet_robonomics_cps::migration::MigrationToV2<Runtime>,
+    // CPS root nodes indexed by owner
+    pallet_robonomics_cps::migration::MigrationToV3<Runtime>,
+    // CPS nodes indexed by owner
+    pallet_robonomics_cps::migration::MigrationToV4<Runtime>,
+    // CPS subtree heights
+    pallet_robonomics_cps::migration::MigrationToV5<Runtime>,
+    // Digital twin topics bounded by MaxTopics
+    pallet_robonomics_digital_twin::migration::MigrationToV1<Runtime>,
+    // Digital twin topics got multiple sources
+    pallet_robonomics_digital_twin::migration::MigrationToV2<Runtime>,
 );
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -1072,6 +1233,67 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_robonomics_liability::runtime_api::LiabilityApi<Block, AccountId, Balance> for Runtime {
+        fn liabilities(
+            account: AccountId,
+            role: pallet_robonomics_liability::LiabilityRole,
+            status: Option<pallet_robonomics_liability::LiabilityStatus>,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<(pallet_robonomics_liability::Index, pallet_robonomics_liability::LiabilityStatus)> {
+            Liability::liabilities_of(&account, role, status, offset, limit)
+        }
+
+        fn reputation(account: AccountId) -> pallet_robonomics_liability::Reputation<Balance> {
+            Liability::reputation_of(account)
+        }
+    }
+
+    impl pallet_robonomics_cps::runtime_api::CpsApi<Block, AccountId, BlockNumber, u64> for Runtime {
+        fn node(node_id: pallet_robonomics_cps::NodeId) -> Option<pallet_robonomics_cps::Node<AccountId>> {
+            CPS::live_node(node_id)
+        }
+
+        fn children(node_id: pallet_robonomics_cps::NodeId) -> Vec<pallet_robonomics_cps::NodeId> {
+            CPS::nodes_by_parent(node_id).into_inner()
+        }
+
+        fn subtree(
+            node_id: pallet_robonomics_cps::NodeId,
+            max_depth: u32,
+            limit: u32,
+        ) -> Vec<(pallet_robonomics_cps::NodeId, pallet_robonomics_cps::Node<AccountId>)> {
+            CPS::subtree(node_id, max_depth, limit)
+        }
+
+        fn ancestors(node_id: pallet_robonomics_cps::NodeId) -> Vec<pallet_robonomics_cps::NodeId> {
+            CPS::ancestors(node_id)
+        }
+
+        fn nodes_by_owner(
+            account: AccountId,
+            cursor: Option<pallet_robonomics_cps::NodeId>,
+            limit: u32,
+        ) -> Vec<pallet_robonomics_cps::NodeId> {
+            CPS::nodes_of(&account, cursor, limit)
+        }
+
+        fn roots(owner: AccountId) -> Vec<pallet_robonomics_cps::NodeId> {
+            CPS::roots_of(&owner)
+        }
+
+        fn versions(node_id: pallet_robonomics_cps::NodeId) -> (u32, u32) {
+            (CPS::payload_version(node_id), CPS::meta_version(node_id))
+        }
+
+        fn payload_at(
+            node_id: pallet_robonomics_cps::NodeId,
+            version: u32,
+        ) -> Option<pallet_robonomics_cps::PayloadRecord<AccountId, BlockNumber, u64>> {
+            CPS::payload_at(node_id, version)
+        }
+    }
+
     #[cfg(feature = "try-runtime")]
     impl frame_try_runtime::TryRuntime<Block> for Runtime {
         fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
@@ -1350,3 +1572,196 @@ mod author_rewards_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod liability_tests {
+    use super::*;
+    use frame_support::{assert_err, assert_ok, traits::fungibles::InspectHold};
+    use pallet_robonomics_liability::{
+        agreement_payload,
+        economics::{MultiAssetMarket, NativeOrAssetMarket, SimpleMarket},
+        technics::{Technics, IPFS},
+        Error, SignedAgreement, SignedReport,
+    };
+    use parity_scale_codec::Encode;
+    use sp_core::{sr25519, Pair};
+    use sp_runtime::{BuildStorage, MultiSignature};
+
+    fn new_test_ext() -> sp_io::TestExternalities {
+        let mut storage = frame_system::GenesisConfig::<Runtime>::default()
+            .build_storage()
+            .unwrap();
+        pallet_balances::GenesisConfig::<Runtime> {
+            balances: ["//Alice", "//Bob"]
+                .iter()
+                .map(|uri| (account(uri), 1_000 * XRT))
+                .collect(),
+            ..Default::default()
+        }
+        .assimilate_storage(&mut storage)
+        .unwrap();
+        let mut ext = sp_io::TestExternalities::new(storage);
+        ext.execute_with(|| System::set_block_number(1));
+        ext
+    }
+
+    fn pair(uri: &str) -> sr25519::Pair {
+        sr25519::Pair::from_string(uri, None).unwrap()
+    }
+
+    fn account(uri: &str) -> AccountId {
+        pair(uri).public().into()
+    }
+
+    fn agreement(
+        economics: NativeOrAssetMarket<AccountId, Balances, AssetsHolder, ForeignAssetValue>,
+        validator: Option<AccountId>,
+    ) -> <Runtime as pallet_robonomics_liability::Config>::Agreement {
+        let technics: Technics = IPFS {
+            hash: [1; 32].into(),
+        }
+        .into();
+        let payload = agreement_payload(
+            System::block_hash(0).as_ref(),
+            &technics,
+            &economics,
+            &validator,
+            &0,
+        );
+        SignedAgreement {
+            technics,
+            economics,
+            promisee: account("//Alice"),
+            promisor: account("//Bob"),
+            validator,
+            nonce: 0,
+            promisee_signature: MultiSignature::from(pair("//Alice").sign(&payload)),
+            promisor_signature: MultiSignature::from(pair("//Bob").sign(&payload)),
+        }
+    }
+
+    fn native(
+        price: Balance,
+    ) -> NativeOrAssetMarket<AccountId, Balances, AssetsHolder, ForeignAssetValue> {
+        NativeOrAssetMarket::Native(SimpleMarket { price })
+    }
+
+    fn report(index: u32) -> SignedReport<Nonce, AccountId, MultiSignature, IPFS> {
+        let payload = IPFS {
+            hash: [2; 32].into(),
+        };
+        let signature = pair("//Bob").sign(&(index, payload.clone()).encode());
+        SignedReport {
+            index,
+            sender: account("//Bob"),
+            payload,
+            signature: signature.into(),
+        }
+    }
+
+    /// Runtime reports are signed by promisor and never wait for validator verdict,
+    /// so liability with validator couldn't be made.
+    #[test]
+    fn liability_with_validator_is_rejected() {
+        new_test_ext().execute_with(|| {
+            let alice = account("//Alice");
+            let bob = account("//Bob");
+            let validator = account("//Charlie");
+
+            assert_err!(
+                Liability::create(
+                    RuntimeOrigin::signed(bob.clone()),
+                    agreement(native(10 * XRT), Some(validator.clone()))
+                ),
+                Error::<Runtime>::ValidatorNotSupported,
+            );
+            assert_eq!(Balances::free_balance(&alice), 1_000 * XRT);
+
+            // The same liability without validator is settled by promisor report
+            assert_ok!(Liability::create(
+                RuntimeOrigin::signed(bob.clone()),
+                agreement(native(10 * XRT), None)
+            ));
+            assert_eq!(Balances::free_balance(&alice), 990 * XRT);
+
+            assert_ok!(Liability::finalize(
+                RuntimeOrigin::signed(bob.clone()),
+                report(0)
+            ));
+            assert_err!(
+                Liability::confirm(RuntimeOrigin::signed(validator), 0, false),
+                Error::<Runtime>::BadValidator,
+            );
+
+            let bob_free = Balances::free_balance(&bob);
+            System::set_block_number(2 + LiabilityChallengePeriod::get());
+            assert_ok!(Liability::settle(RuntimeOrigin::signed(alice.clone()), 0));
+            assert_eq!(Balances::free_balance(&alice), 990 * XRT);
+            assert_eq!(Balances::free_balance(&bob), bob_free + 10 * XRT);
+        })
+    }
+
+    /// Liability could be paid in foreign asset bridged from Asset Hub.
+    #[test]
+    fn liability_paid_in_foreign_asset() {
+        new_test_ext().execute_with(|| {
+            let alice = account("//Alice");
+            let bob = account("//Bob");
+            let usdt = xcm::latest::Location::new(
+                1,
+                [
+                    xcm::latest::Junction::Parachain(xcm_config::ASSET_HUB_ID),
+                    xcm::latest::Junction::PalletInstance(50),
+                    xcm::latest::Junction::GeneralIndex(1984),
+                ],
+            );
+            assert_ok!(ForeignAssets::force_create(
+                RuntimeOrigin::root(),
+                usdt.clone(),
+                alice.clone().into(),
+                true,
+                1,
+            ));
+            assert_ok!(ForeignAssets::mint(
+                RuntimeOrigin::signed(alice.clone()),
+                usdt.clone(),
+                alice.clone().into(),
+                100,
+            ));
+
+            let economics = NativeOrAssetMarket::Asset(MultiAssetMarket {
+                asset_id: usdt.clone(),
+                amount: 10,
+                valuation: Default::default(),
+            });
+            assert_ok!(Liability::create(
+                RuntimeOrigin::signed(bob.clone()),
+                agreement(economics, None)
+            ));
+            let escrow: RuntimeHoldReason = pallet_robonomics_liability::HoldReason::Escrow.into();
+            assert_eq!(ForeignAssets::balance(usdt.clone(), &alice), 90);
+            assert_eq!(
+                AssetsHolder::balance_on_hold(usdt.clone(), &escrow, &alice),
+                10
+            );
+            // Native balance of promisee is untouched
+            assert_eq!(Balances::free_balance(&alice), 1_000 * XRT);
+
+            assert_ok!(Liability::finalize(
+                RuntimeOrigin::signed(bob.clone()),
+                report(0)
+            ));
+            System::set_block_number(2 + LiabilityChallengePeriod::get());
+            assert_ok!(Liability::settle(RuntimeOrigin::signed(alice.clone()), 0));
+            assert_eq!(
+                AssetsHolder::balance_on_hold(usdt.clone(), &escrow, &alice),
+                0
+            );
+            assert_eq!(ForeignAssets::balance(usdt.clone(), &alice), 90);
+            assert_eq!(ForeignAssets::balance(usdt, &bob), 10);
+
+            // Foreign asset payment has no value in XRT
+            assert_eq!(Liability::reputation_of(&bob).settled, 0);
+        })
+    }
+}
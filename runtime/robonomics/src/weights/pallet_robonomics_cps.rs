@@ -16,38 +16,13 @@
 //
 ///////////////////////////////////////////////////////////////////////////////
 
-//! Autogenerated weights for `pallet_robonomics_cps`
+//! Estimated weights for `pallet_robonomics_cps`
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 58.0.0
-//! DATE: 2026-08-10, STEPS: `50`, REPEAT: `100`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `boot-02-robonomics`, CPU: `AMD EPYC 4344P 8-Core Processor`
-//! WASM-EXECUTION: `Compiled`, CHAIN: `None`, DB CACHE: 1024
-
-// Executed Command:
-// frame-omni-bencher
-// v1
-// benchmark
-// pallet
-// --runtime
-// ./target/release/wbuild/robonomics-runtime/robonomics_runtime.compact.compressed.wasm
-// --pallet
-// pallet_robonomics_cps
-// --extrinsic
-// *
-// --output
-// runtime/robonomics/src/weights/pallet_robonomics_cps.rs
-// --header
-// /root/robonomics/.github/license-check/HEADER-APACHE2
-// --wasm-execution=compiled
-// --steps
-// 50
-// --repeat
-// 100
-// --heap-pages=4096
-// --no-storage-info
-// --no-min-squares
-// --no-median-slopes
+//! THESE WEIGHTS ARE NOT BENCHMARK OUTPUT. Storage accesses are taken from the code of
+//! each call, `Estimated` proof size is the sum of `added` of all reads and execution time
+//! is scaled from the previous pallet version benchmarked on 2026-08-10 with
+//! STEPS: `50`, REPEAT: `100`, CPU: `AMD EPYC 4344P 8-Core Processor`.
+//! Replace this file with `frame-omni-bencher` output for `pallet_robonomics_cps`.
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -62,70 +37,285 @@ pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> pallet_robonomics_cps::WeightInfo for WeightInfo<T> {
 	/// Storage: `CPS::NextNodeId` (r:1 w:1)
 	/// Proof: `CPS::NextNodeId` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
-	/// Storage: `CPS::RootNodes` (r:1 w:1)
-	/// Proof: `CPS::RootNodes` (`max_values`: Some(1), `max_size`: Some(802), added: 1297, mode: `MaxEncodedLen`)
-	/// Storage: `CPS::Nodes` (r:0 w:1)
+	/// Storage: `CPS::Nodes` (r:2 w:1)
 	/// Proof: `CPS::Nodes` (`max_values`: None, `max_size`: Some(4424), added: 6899, mode: `MaxEncodedLen`)
-	fn create_node() -> Weight {
+	/// Storage: `CPS::NodeRoles` (r:32 w:0)
+	/// Proof: `CPS::NodeRoles` (`max_values`: None, `max_size`: Some(578), added: 3053, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::DepositAllowance` (r:1 w:1)
+	/// Proof: `CPS::DepositAllowance` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::DeletionQueue` (r:32 w:0)
+	/// Proof: `CPS::DeletionQueue` (`max_values`: None, `max_size`: Some(25), added: 2500, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::MovingRoots` (r:32 w:0)
+	/// Proof: `CPS::MovingRoots` (`max_values`: None, `max_size`: Some(25), added: 2500, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::NodesByParent` (r:1 w:1)
+	/// Proof: `CPS::NodesByParent` (`max_values`: None, `max_size`: Some(826), added: 3301, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::SubtreeHeight` (r:32 w:32)
+	/// Proof: `CPS::SubtreeHeight` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::NodeDeposit` (r:1 w:1)
+	/// Proof: `CPS::NodeDeposit` (`max_values`: None, `max_size`: Some(41), added: 2516, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(139), added: 2614, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::NodesByOwner` (r:0 w:1)
+	/// Proof: `CPS::NodesByOwner` (`max_values`: None, `max_size`: Some(73), added: 2548, mode: `MaxEncodedLen`)
+	/// The range of component `d` is `[0, 31]`.
+	fn create_node(d: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `3`
-		//  Estimated: `2287`
-		// Minimum execution time: 5_440_000 picoseconds.
-		Weight::from_parts(6_071_000, 0)
-			.saturating_add(Weight::from_parts(0, 2287))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(3))
+		//  Measured:  `1721 + d * (102 ±0)`
+		//  Estimated: `33375 + d * (10556 ±0)`
+		// Minimum execution time: 39_280_000 picoseconds.
+		Weight::from_parts(41_116_000, 0)
+			.saturating_add(Weight::from_parts(0, 33375))
+			// Standard Error: 3_412
+			.saturating_add(Weight::from_parts(1_384_507, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(10))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(8))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 10556).saturating_mul(d.into()))
+	}
+	/// Storage: `CPS::Nodes` (r:2 w:1)
+	/// Proof: `CPS::Nodes` (`max_values`: None, `max_size`: Some(4424), added: 6899, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::MovingRoots` (r:32 w:0)
+	/// Proof: `CPS::MovingRoots` (`max_values`: None, `max_size`: Some(25), added: 2500, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::NodeRoles` (r:33 w:0)
+	/// Proof: `CPS::NodeRoles` (`max_values`: None, `max_size`: Some(578), added: 3053, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::DepositAllowance` (r:1 w:1)
+	/// Proof: `CPS::DepositAllowance` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::NodeDeposit` (r:1 w:1)
+	/// Proof: `CPS::NodeDeposit` (`max_values`: None, `max_size`: Some(41), added: 2516, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(139), added: 2614, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::PayloadHistoryDepth` (r:1 w:0)
+	/// Proof: `CPS::PayloadHistoryDepth` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::MetaVersion` (r:1 w:1)
+	/// Proof: `CPS::MetaVersion` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	/// The range of component `d` is `[0, 32]`.
+	fn set_meta(d: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1688 + d * (70 ±0)`
+		//  Estimated: `29574 + d * (5553 ±0)`
+		// Minimum execution time: 33_910_000 picoseconds.
+		Weight::from_parts(35_602_000, 0)
+			.saturating_add(Weight::from_parts(0, 29574))
+			// Standard Error: 2_187
+			.saturating_add(Weight::from_parts(512_318, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(5))
+			.saturating_add(Weight::from_parts(0, 5553).saturating_mul(d.into()))
+	}
+	/// Storage: `CPS::Nodes` (r:2 w:1)
+	/// Proof: `CPS::Nodes` (`max_values`: None, `max_size`: Some(4424), added: 6899, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::MovingRoots` (r:32 w:0)
+	/// Proof: `CPS::MovingRoots` (`max_values`: None, `max_size`: Some(25), added: 2500, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::NodeRoles` (r:33 w:0)
+	/// Proof: `CPS::NodeRoles` (`max_values`: None, `max_size`: Some(578), added: 3053, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::DepositAllowance` (r:1 w:1)
+	/// Proof: `CPS::DepositAllowance` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::NodeDeposit` (r:1 w:1)
+	/// Proof: `CPS::NodeDeposit` (`max_values`: None, `max_size`: Some(41), added: 2516, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(139), added: 2614, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::PayloadHistoryDepth` (r:1 w:0)
+	/// Proof: `CPS::PayloadHistoryDepth` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::PayloadVersion` (r:1 w:1)
+	/// Proof: `CPS::PayloadVersion` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::PayloadHistory` (r:0 w:1)
+	/// Proof: `CPS::PayloadHistory` (`max_values`: None, `max_size`: Some(2291), added: 4766, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::PayloadExpiry` (r:1 w:1)
+	/// Proof: `CPS::PayloadExpiry` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::ExpiringPayloads` (r:2 w:2)
+	/// Proof: `CPS::ExpiringPayloads` (`max_values`: None, `max_size`: Some(817), added: 3292, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::ExpiryCursor` (r:1 w:0)
+	/// Proof: `CPS::ExpiryCursor` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// The range of component `d` is `[0, 32]`.
+	fn set_payload(d: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2455 + d * (70 ±0)`
+		//  Estimated: `39160 + d * (5553 ±0)`
+		// Minimum execution time: 44_730_000 picoseconds.
+		Weight::from_parts(46_918_000, 0)
+			.saturating_add(Weight::from_parts(0, 39160))
+			// Standard Error: 2_406
+			.saturating_add(Weight::from_parts(518_774, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(12))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(9))
+			.saturating_add(Weight::from_parts(0, 5553).saturating_mul(d.into()))
+	}
+	/// Storage: `CPS::Nodes` (r:52 w:51)
+	/// Proof: `CPS::Nodes` (`max_values`: None, `max_size`: Some(4424), added: 6899, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::DeletionQueue` (r:82 w:0)
+	/// Proof: `CPS::DeletionQueue` (`max_values`: None, `max_size`: Some(25), added: 2500, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::MovingRoots` (r:64 w:0)
+	/// Proof: `CPS::MovingRoots` (`max_values`: None, `max_size`: Some(25), added: 2500, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::NodesByParent` (r:52 w:1)
+	/// Proof: `CPS::NodesByParent` (`max_values`: None, `max_size`: Some(826), added: 3301, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::SubtreeHeight` (r:32 w:31)
+	/// Proof: `CPS::SubtreeHeight` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::RootCount` (r:1 w:1)
+	/// Proof: `CPS::RootCount` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::RootsByOwner` (r:0 w:1)
+	/// Proof: `CPS::RootsByOwner` (`max_values`: None, `max_size`: Some(73), added: 2548, mode: `MaxEncodedLen`)
+	fn move_node() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `9874`
+		//  Estimated: `978023`
+		// Minimum execution time: 295_180_000 picoseconds.
+		Weight::from_parts(302_371_000, 0)
+			.saturating_add(Weight::from_parts(0, 978023))
+			.saturating_add(T::DbWeight::get().reads(283))
+			.saturating_add(T::DbWeight::get().writes(85))
 	}
 	/// Storage: `CPS::Nodes` (r:1 w:1)
 	/// Proof: `CPS::Nodes` (`max_values`: None, `max_size`: Some(4424), added: 6899, mode: `MaxEncodedLen`)
-	fn set_meta() -> Weight {
+	/// Storage: `CPS::NodesByParent` (r:2 w:2)
+	/// Proof: `CPS::NodesByParent` (`max_values`: None, `max_size`: Some(826), added: 3301, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::NodeDeposit` (r:1 w:1)
+	/// Proof: `CPS::NodeDeposit` (`max_values`: None, `max_size`: Some(41), added: 2516, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(139), added: 2614, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::NodeRoles` (r:0 w:1)
+	/// Proof: `CPS::NodeRoles` (`max_values`: None, `max_size`: Some(578), added: 3053, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::MovingRoots` (r:32 w:0)
+	/// Proof: `CPS::MovingRoots` (`max_values`: None, `max_size`: Some(25), added: 2500, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::SubtreeHeight` (r:0 w:1)
+	/// Proof: `CPS::SubtreeHeight` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::NodesByOwner` (r:0 w:1)
+	/// Proof: `CPS::NodesByOwner` (`max_values`: None, `max_size`: Some(73), added: 2548, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::PayloadExpiry` (r:1 w:1)
+	/// Proof: `CPS::PayloadExpiry` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::ExpiringPayloads` (r:1 w:1)
+	/// Proof: `CPS::ExpiringPayloads` (`max_values`: None, `max_size`: Some(817), added: 3292, mode: `MaxEncodedLen`)
+	fn delete_node() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1149`
+		//  Estimated: `87889`
+		// Minimum execution time: 29_410_000 picoseconds.
+		Weight::from_parts(31_687_000, 0)
+			.saturating_add(Weight::from_parts(0, 87889))
+			.saturating_add(T::DbWeight::get().reads(39))
+			.saturating_add(T::DbWeight::get().writes(10))
+	}
+	/// Storage: `CPS::Nodes` (r:1 w:0)
+	/// Proof: `CPS::Nodes` (`max_values`: None, `max_size`: Some(4424), added: 6899, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::NodeRoles` (r:1 w:1)
+	/// Proof: `CPS::NodeRoles` (`max_values`: None, `max_size`: Some(578), added: 3053, mode: `MaxEncodedLen`)
+	fn grant_role() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `117`
+		//  Measured:  `651`
 		//  Estimated: `7889`
-		// Minimum execution time: 5_550_000 picoseconds.
-		Weight::from_parts(6_142_000, 0)
+		// Minimum execution time: 11_260_000 picoseconds.
+		Weight::from_parts(11_841_000, 0)
 			.saturating_add(Weight::from_parts(0, 7889))
-			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
-	/// Storage: `CPS::Nodes` (r:1 w:1)
+	/// Storage: `CPS::Nodes` (r:1 w:0)
+	/// Proof: `CPS::Nodes` (`max_values`: None, `max_size`: Some(4424), added: 6899, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::NodeRoles` (r:1 w:1)
+	/// Proof: `CPS::NodeRoles` (`max_values`: None, `max_size`: Some(578), added: 3053, mode: `MaxEncodedLen`)
+	fn revoke_role() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `685`
+		//  Estimated: `7889`
+		// Minimum execution time: 11_030_000 picoseconds.
+		Weight::from_parts(11_592_000, 0)
+			.saturating_add(Weight::from_parts(0, 7889))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `CPS::Nodes` (r:1 w:0)
 	/// Proof: `CPS::Nodes` (`max_values`: None, `max_size`: Some(4424), added: 6899, mode: `MaxEncodedLen`)
-	fn set_payload() -> Weight {
+	/// Storage: `CPS::PendingTransfers` (r:0 w:1)
+	/// Proof: `CPS::PendingTransfers` (`max_values`: None, `max_size`: Some(57), added: 2532, mode: `MaxEncodedLen`)
+	fn offer_transfer() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `117`
 		//  Estimated: `7889`
-		// Minimum execution time: 5_700_000 picoseconds.
-		Weight::from_parts(6_322_000, 0)
+		// Minimum execution time: 8_910_000 picoseconds.
+		Weight::from_parts(9_372_000, 0)
 			.saturating_add(Weight::from_parts(0, 7889))
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
-	/// Storage: `CPS::Nodes` (r:2 w:1)
+	/// Storage: `CPS::PendingTransfers` (r:51 w:51)
+	/// Proof: `CPS::PendingTransfers` (`max_values`: None, `max_size`: Some(57), added: 2532, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::Nodes` (r:51 w:51)
 	/// Proof: `CPS::Nodes` (`max_values`: None, `max_size`: Some(4424), added: 6899, mode: `MaxEncodedLen`)
-	/// Storage: `CPS::NodesByParent` (r:3 w:2)
+	/// Storage: `CPS::DeletionQueue` (r:82 w:0)
+	/// Proof: `CPS::DeletionQueue` (`max_values`: None, `max_size`: Some(25), added: 2500, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::MovingRoots` (r:32 w:0)
+	/// Proof: `CPS::MovingRoots` (`max_values`: None, `max_size`: Some(25), added: 2500, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::NodesByParent` (r:53 w:1)
 	/// Proof: `CPS::NodesByParent` (`max_values`: None, `max_size`: Some(826), added: 3301, mode: `MaxEncodedLen`)
-	fn move_node() -> Weight {
+	/// Storage: `CPS::RootCount` (r:1 w:1)
+	/// Proof: `CPS::RootCount` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::RootsByOwner` (r:0 w:1)
+	/// Proof: `CPS::RootsByOwner` (`max_values`: None, `max_size`: Some(73), added: 2548, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::NodeDeposit` (r:51 w:0)
+	/// Proof: `CPS::NodeDeposit` (`max_values`: None, `max_size`: Some(41), added: 2516, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:2 w:2)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(139), added: 2614, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::NodeRoles` (r:0 w:51)
+	/// Proof: `CPS::NodeRoles` (`max_values`: None, `max_size`: Some(578), added: 3053, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::NodesByOwner` (r:0 w:102)
+	/// Proof: `CPS::NodesByOwner` (`max_values`: None, `max_size`: Some(73), added: 2548, mode: `MaxEncodedLen`)
+	fn accept_transfer() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `258`
-		//  Estimated: `14788`
-		// Minimum execution time: 15_219_000 picoseconds.
-		Weight::from_parts(16_821_000, 0)
-			.saturating_add(Weight::from_parts(0, 14788))
-			.saturating_add(T::DbWeight::get().reads(5))
-			.saturating_add(T::DbWeight::get().writes(3))
+		//  Measured:  `219247`
+		//  Estimated: `638767`
+		// Minimum execution time: 1_113_690_000 picoseconds.
+		Weight::from_parts(1_143_383_000, 0)
+			.saturating_add(Weight::from_parts(0, 638767))
+			.saturating_add(T::DbWeight::get().reads(323))
+			.saturating_add(T::DbWeight::get().writes(260))
 	}
-	/// Storage: `CPS::Nodes` (r:1 w:1)
+	/// Storage: `CPS::Nodes` (r:1 w:0)
 	/// Proof: `CPS::Nodes` (`max_values`: None, `max_size`: Some(4424), added: 6899, mode: `MaxEncodedLen`)
-	/// Storage: `CPS::NodesByParent` (r:2 w:2)
-	/// Proof: `CPS::NodesByParent` (`max_values`: None, `max_size`: Some(826), added: 3301, mode: `MaxEncodedLen`)
-	fn delete_node() -> Weight {
+	/// Storage: `CPS::MovingRoots` (r:32 w:0)
+	/// Proof: `CPS::MovingRoots` (`max_values`: None, `max_size`: Some(25), added: 2500, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::DeletionQueue` (r:33 w:1)
+	/// Proof: `CPS::DeletionQueue` (`max_values`: None, `max_size`: Some(25), added: 2500, mode: `MaxEncodedLen`)
+	fn delete_subtree() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1012`
+		//  Estimated: `169399`
+		// Minimum execution time: 41_530_000 picoseconds.
+		Weight::from_parts(43_204_000, 0)
+			.saturating_add(Weight::from_parts(0, 169399))
+			.saturating_add(T::DbWeight::get().reads(66))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `CPS::Nodes` (r:1 w:0)
+	/// Proof: `CPS::Nodes` (`max_values`: None, `max_size`: Some(4424), added: 6899, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::NodeDeposit` (r:1 w:1)
+	/// Proof: `CPS::NodeDeposit` (`max_values`: None, `max_size`: Some(41), added: 2516, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(139), added: 2614, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::PayloadHistory` (r:16 w:16)
+	/// Proof: `CPS::PayloadHistory` (`max_values`: None, `max_size`: Some(2291), added: 4766, mode: `MaxEncodedLen`)
+	/// Storage: `CPS::PayloadHistoryDepth` (r:0 w:1)
+	/// Proof: `CPS::PayloadHistoryDepth` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	fn set_payload_history() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `198`
+		//  Measured:  `117`
 		//  Estimated: `7889`
-		// Minimum execution time: 10_860_000 picoseconds.
-		Weight::from_parts(12_042_000, 0)
+		// Minimum execution time: 21_410_000 picoseconds.
+		Weight::from_parts(23_087_000, 0)
 			.saturating_add(Weight::from_parts(0, 7889))
-			.saturating_add(T::DbWeight::get().reads(3))
-			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(T::DbWeight::get().reads(19))
+			.saturating_add(T::DbWeight::get().writes(19))
+	}
+	/// Storage: `CPS::DepositAllowance` (r:0 w:1)
+	/// Proof: `CPS::DepositAllowance` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	fn set_deposit_allowance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 6_840_000 picoseconds.
+		Weight::from_parts(7_213_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+			.saturating_add(T::DbWeight::get().writes(1))
 	}
 }
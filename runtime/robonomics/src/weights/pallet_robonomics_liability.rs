@@ -16,38 +16,14 @@
 //
 ///////////////////////////////////////////////////////////////////////////////
 
-//! Autogenerated weights for `pallet_robonomics_liability`
+//! Estimated weights for `pallet_robonomics_liability`
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 58.0.0
-//! DATE: 2026-08-10, STEPS: `50`, REPEAT: `100`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `boot-02-robonomics`, CPU: `AMD EPYC 4344P 8-Core Processor`
-//! WASM-EXECUTION: `Compiled`, CHAIN: `None`, DB CACHE: 1024
-
-// Executed Command:
-// frame-omni-bencher
-// v1
-// benchmark
-// pallet
-// --runtime
-// ./target/release/wbuild/robonomics-runtime/robonomics_runtime.compact.compressed.wasm
-// --pallet
-// pallet_robonomics_liability
-// --extrinsic
-// *
-// --output
-// runtime/robonomics/src/weights/pallet_robonomics_liability.rs
-// --header
-// /root/robonomics/.github/license-check/HEADER-APACHE2
-// --wasm-execution=compiled
-// --steps
-// 50
-// --repeat
-// 100
-// --heap-pages=4096
-// --no-storage-info
-// --no-min-squares
-// --no-median-slopes
+//! THESE WEIGHTS ARE NOT BENCHMARK OUTPUT. Storage accesses are taken from the code of
+//! each call, `Estimated` proof size is the sum of `added` of all reads and execution time
+//! is scaled from `create` and `finalize` of the previous pallet version benchmarked on
+//! 2026-08-10 with STEPS: `50`, REPEAT: `100`, CPU: `AMD EPYC 4344P 8-Core Processor`.
+//! `confirm` benchmark is skipped by this runtime, its reports are never pending.
+//! Replace this file with `frame-omni-bencher` output for `pallet_robonomics_liability`.
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -60,36 +36,310 @@ use core::marker::PhantomData;
 /// Weight functions for `pallet_robonomics_liability`.
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> pallet_robonomics_liability::WeightInfo for WeightInfo<T> {
+	/// Storage: `System::BlockHash` (r:1 w:0)
+	/// Proof: `System::BlockHash` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::NonceOf` (r:1 w:1)
+	/// Proof: `Liability::NonceOf` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	/// Storage: `Liability::NextIndex` (r:1 w:1)
 	/// Proof: `Liability::NextIndex` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
 	/// Storage: `Liability::AgreementOf` (r:0 w:1)
-	/// Proof: `Liability::AgreementOf` (`max_values`: None, `max_size`: Some(256), added: 2731, mode: `MaxEncodedLen`)
+	/// Proof: `Liability::AgreementOf` (`max_values`: None, `max_size`: Some(290), added: 2765, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::PromiseeLiabilities` (r:0 w:1)
+	/// Proof: `Liability::PromiseeLiabilities` (`max_values`: None, `max_size`: Some(61), added: 2536, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::PromisorLiabilities` (r:0 w:1)
+	/// Proof: `Liability::PromisorLiabilities` (`max_values`: None, `max_size`: Some(61), added: 2536, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::ReputationOf` (r:1 w:1)
+	/// Proof: `Liability::ReputationOf` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::DepositOf` (r:0 w:1)
+	/// Proof: `Liability::DepositOf` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(175), added: 2650, mode: `MaxEncodedLen`)
 	fn create() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `107`
-		//  Estimated: `3593`
+		//  Estimated: `13361`
 		// Minimum execution time: 14_437_000 picoseconds.
 		Weight::from_parts(15_288_000, 0)
-			.saturating_add(Weight::from_parts(0, 3593))
+			.saturating_add(Weight::from_parts(0, 13361))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(9))
+	}
+	/// Storage: `Liability::ReportOf` (r:1 w:1)
+	/// Proof: `Liability::ReportOf` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::AgreementOf` (r:1 w:0)
+	/// Proof: `Liability::AgreementOf` (`max_values`: None, `max_size`: Some(290), added: 2765, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::SettlementOf` (r:0 w:1)
+	/// Proof: `Liability::SettlementOf` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	fn finalize() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `425`
+		//  Estimated: `5386`
+		// Minimum execution time: 15_904_000 picoseconds.
+		Weight::from_parts(16_712_000, 0)
+			.saturating_add(Weight::from_parts(0, 5386))
 			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(T::DbWeight::get().writes(2))
 	}
+	/// Storage: `Liability::AgreementOf` (r:1 w:0)
+	/// Proof: `Liability::AgreementOf` (`max_values`: None, `max_size`: Some(290), added: 2765, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::MilestoneReportOf` (r:1 w:1)
+	/// Proof: `Liability::MilestoneReportOf` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
 	/// Storage: `Liability::ReportOf` (r:1 w:1)
 	/// Proof: `Liability::ReportOf` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::SettlementOf` (r:0 w:1)
+	/// Proof: `Liability::SettlementOf` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	fn confirm() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `512`
+		//  Estimated: `7909`
+		// Minimum execution time: 17_236_000 picoseconds.
+		Weight::from_parts(18_051_000, 0)
+			.saturating_add(Weight::from_parts(0, 7909))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Liability::AgreementOf` (r:1 w:0)
+	/// Proof: `Liability::AgreementOf` (`max_values`: None, `max_size`: Some(290), added: 2765, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::SettlementOf` (r:1 w:1)
+	/// Proof: `Liability::SettlementOf` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::ReputationOf` (r:1 w:1)
+	/// Proof: `Liability::ReputationOf` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(175), added: 2650, mode: `MaxEncodedLen`)
+	fn dispute() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `13127`
+		// Minimum execution time: 19_384_000 picoseconds.
+		Weight::from_parts(20_127_000, 0)
+			.saturating_add(Weight::from_parts(0, 13127))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
 	/// Storage: `Liability::AgreementOf` (r:1 w:0)
-	/// Proof: `Liability::AgreementOf` (`max_values`: None, `max_size`: Some(256), added: 2731, mode: `MaxEncodedLen`)
+	/// Proof: `Liability::AgreementOf` (`max_values`: None, `max_size`: Some(290), added: 2765, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::SettlementOf` (r:1 w:1)
+	/// Proof: `Liability::SettlementOf` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:2 w:2)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	fn finalize() -> Weight {
+	/// Storage: `Liability::PromiseeLiabilities` (r:0 w:1)
+	/// Proof: `Liability::PromiseeLiabilities` (`max_values`: None, `max_size`: Some(61), added: 2536, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::PromisorLiabilities` (r:0 w:1)
+	/// Proof: `Liability::PromisorLiabilities` (`max_values`: None, `max_size`: Some(61), added: 2536, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::ReputationOf` (r:1 w:1)
+	/// Proof: `Liability::ReputationOf` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::FinalizedAt` (r:0 w:1)
+	/// Proof: `Liability::FinalizedAt` (`max_values`: None, `max_size`: Some(16), added: 2491, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(175), added: 2650, mode: `MaxEncodedLen`)
+	fn resolve() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `499`
-		//  Estimated: `6196`
-		// Minimum execution time: 21_751_000 picoseconds.
-		Weight::from_parts(22_842_000, 0)
-			.saturating_add(Weight::from_parts(0, 6196))
-			.saturating_add(T::DbWeight::get().reads(4))
-			.saturating_add(T::DbWeight::get().writes(3))
+		//  Measured:  `521`
+		//  Estimated: `15730`
+		// Minimum execution time: 27_905_000 picoseconds.
+		Weight::from_parts(28_736_000, 0)
+			.saturating_add(Weight::from_parts(0, 15730))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(8))
+	}
+	/// Storage: `Liability::SettlementOf` (r:1 w:1)
+	/// Proof: `Liability::SettlementOf` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::AgreementOf` (r:1 w:0)
+	/// Proof: `Liability::AgreementOf` (`max_values`: None, `max_size`: Some(290), added: 2765, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::PromiseeLiabilities` (r:0 w:1)
+	/// Proof: `Liability::PromiseeLiabilities` (`max_values`: None, `max_size`: Some(61), added: 2536, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::PromisorLiabilities` (r:0 w:1)
+	/// Proof: `Liability::PromisorLiabilities` (`max_values`: None, `max_size`: Some(61), added: 2536, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::ReputationOf` (r:1 w:1)
+	/// Proof: `Liability::ReputationOf` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::FinalizedAt` (r:0 w:1)
+	/// Proof: `Liability::FinalizedAt` (`max_values`: None, `max_size`: Some(16), added: 2491, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(175), added: 2650, mode: `MaxEncodedLen`)
+	fn settle() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `487`
+		//  Estimated: `15730`
+		// Minimum execution time: 22_418_000 picoseconds.
+		Weight::from_parts(23_309_000, 0)
+			.saturating_add(Weight::from_parts(0, 15730))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(8))
+	}
+	/// Storage: `Liability::AgreementOf` (r:1 w:0)
+	/// Proof: `Liability::AgreementOf` (`max_values`: None, `max_size`: Some(290), added: 2765, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::ReportOf` (r:1 w:0)
+	/// Proof: `Liability::ReportOf` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::ExpiredOf` (r:1 w:1)
+	/// Proof: `Liability::ExpiredOf` (`max_values`: None, `max_size`: Some(16), added: 2491, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::PromiseeLiabilities` (r:0 w:1)
+	/// Proof: `Liability::PromiseeLiabilities` (`max_values`: None, `max_size`: Some(61), added: 2536, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::PromisorLiabilities` (r:0 w:1)
+	/// Proof: `Liability::PromisorLiabilities` (`max_values`: None, `max_size`: Some(61), added: 2536, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::ReputationOf` (r:1 w:1)
+	/// Proof: `Liability::ReputationOf` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::FinalizedAt` (r:0 w:1)
+	/// Proof: `Liability::FinalizedAt` (`max_values`: None, `max_size`: Some(16), added: 2491, mode: `MaxEncodedLen`)
+	fn expire() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `463`
+		//  Estimated: `15642`
+		// Minimum execution time: 23_076_000 picoseconds.
+		Weight::from_parts(23_914_000, 0)
+			.saturating_add(Weight::from_parts(0, 15642))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+	/// Storage: `Liability::AgreementOf` (r:1 w:0)
+	/// Proof: `Liability::AgreementOf` (`max_values`: None, `max_size`: Some(290), added: 2765, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::MilestonesPaid` (r:1 w:1)
+	/// Proof: `Liability::MilestonesPaid` (`max_values`: None, `max_size`: Some(16), added: 2491, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::ExpiredOf` (r:1 w:0)
+	/// Proof: `Liability::ExpiredOf` (`max_values`: None, `max_size`: Some(16), added: 2491, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::PromiseeLiabilities` (r:0 w:1)
+	/// Proof: `Liability::PromiseeLiabilities` (`max_values`: None, `max_size`: Some(61), added: 2536, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::PromisorLiabilities` (r:0 w:1)
+	/// Proof: `Liability::PromisorLiabilities` (`max_values`: None, `max_size`: Some(61), added: 2536, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::ReputationOf` (r:1 w:1)
+	/// Proof: `Liability::ReputationOf` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::FinalizedAt` (r:0 w:1)
+	/// Proof: `Liability::FinalizedAt` (`max_values`: None, `max_size`: Some(16), added: 2491, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::MilestoneReportOf` (r:1 w:0)
+	/// Proof: `Liability::MilestoneReportOf` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::SettlementOf` (r:1 w:0)
+	/// Proof: `Liability::SettlementOf` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	fn report_milestone() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `463`
+		//  Estimated: `20585`
+		// Minimum execution time: 22_531_000 picoseconds.
+		Weight::from_parts(23_402_000, 0)
+			.saturating_add(Weight::from_parts(0, 20585))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+	/// Storage: `Liability::NonceOf` (r:1 w:1)
+	/// Proof: `Liability::NonceOf` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `System::BlockHash` (r:1 w:0)
+	/// Proof: `System::BlockHash` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::NextOrderIndex` (r:1 w:1)
+	/// Proof: `Liability::NextOrderIndex` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::OrderBook` (r:1 w:1)
+	/// Proof: `Liability::OrderBook` (`max_values`: None, `max_size`: Some(434), added: 2909, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::MatchQueue` (r:1 w:1)
+	/// Proof: `Liability::MatchQueue` (`max_values`: Some(1), `max_size`: Some(402), added: 897, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::OrderDepositOf` (r:0 w:1)
+	/// Proof: `Liability::OrderDepositOf` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::OrderOf` (r:0 w:1)
+	/// Proof: `Liability::OrderOf` (`max_values`: None, `max_size`: Some(158), added: 2633, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(175), added: 2650, mode: `MaxEncodedLen`)
+	fn place_order() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `245`
+		//  Estimated: `14616`
+		// Minimum execution time: 71_045_000 picoseconds.
+		Weight::from_parts(72_893_000, 0)
+			.saturating_add(Weight::from_parts(0, 14616))
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(8))
+	}
+	/// Storage: `Liability::OrderOf` (r:1 w:1)
+	/// Proof: `Liability::OrderOf` (`max_values`: None, `max_size`: Some(158), added: 2633, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::OrderDepositOf` (r:1 w:1)
+	/// Proof: `Liability::OrderDepositOf` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::OrderBook` (r:1 w:1)
+	/// Proof: `Liability::OrderBook` (`max_values`: None, `max_size`: Some(434), added: 2909, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(175), added: 2650, mode: `MaxEncodedLen`)
+	fn cancel_order() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `482`
+		//  Estimated: `13338`
+		// Minimum execution time: 27_114_000 picoseconds.
+		Weight::from_parts(28_046_000, 0)
+			.saturating_add(Weight::from_parts(0, 13338))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	/// Storage: `Liability::OrderOf` (r:2 w:2)
+	/// Proof: `Liability::OrderOf` (`max_values`: None, `max_size`: Some(158), added: 2633, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::OrderBook` (r:1 w:1)
+	/// Proof: `Liability::OrderBook` (`max_values`: None, `max_size`: Some(434), added: 2909, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::NextIndex` (r:1 w:1)
+	/// Proof: `Liability::NextIndex` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::AgreementOf` (r:0 w:1)
+	/// Proof: `Liability::AgreementOf` (`max_values`: None, `max_size`: Some(290), added: 2765, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::PromiseeLiabilities` (r:0 w:1)
+	/// Proof: `Liability::PromiseeLiabilities` (`max_values`: None, `max_size`: Some(61), added: 2536, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::PromisorLiabilities` (r:0 w:1)
+	/// Proof: `Liability::PromisorLiabilities` (`max_values`: None, `max_size`: Some(61), added: 2536, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::ReputationOf` (r:1 w:1)
+	/// Proof: `Liability::ReputationOf` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::DepositOf` (r:0 w:1)
+	/// Proof: `Liability::DepositOf` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::OrderDepositOf` (r:2 w:2)
+	/// Proof: `Liability::OrderDepositOf` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::MatchedOrdersOf` (r:0 w:1)
+	/// Proof: `Liability::MatchedOrdersOf` (`max_values`: None, `max_size`: Some(312), added: 2787, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(175), added: 2650, mode: `MaxEncodedLen`)
+	fn match_orders() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `782`
+		//  Estimated: `21572`
+		// Minimum execution time: 44_208_000 picoseconds.
+		Weight::from_parts(45_630_000, 0)
+			.saturating_add(Weight::from_parts(0, 21572))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(14))
+	}
+	/// Storage: `Liability::AgreementOf` (r:1 w:1)
+	/// Proof: `Liability::AgreementOf` (`max_values`: None, `max_size`: Some(290), added: 2765, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::PromiseeLiabilities` (r:1 w:1)
+	/// Proof: `Liability::PromiseeLiabilities` (`max_values`: None, `max_size`: Some(61), added: 2536, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::FinalizedAt` (r:1 w:1)
+	/// Proof: `Liability::FinalizedAt` (`max_values`: None, `max_size`: Some(16), added: 2491, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::ReportOf` (r:1 w:1)
+	/// Proof: `Liability::ReportOf` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::DepositOf` (r:1 w:1)
+	/// Proof: `Liability::DepositOf` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::ExpiredOf` (r:0 w:1)
+	/// Proof: `Liability::ExpiredOf` (`max_values`: None, `max_size`: Some(16), added: 2491, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::MilestonesPaid` (r:0 w:1)
+	/// Proof: `Liability::MilestonesPaid` (`max_values`: None, `max_size`: Some(16), added: 2491, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::PromisorLiabilities` (r:0 w:1)
+	/// Proof: `Liability::PromisorLiabilities` (`max_values`: None, `max_size`: Some(61), added: 2536, mode: `MaxEncodedLen`)
+	/// Storage: `Liability::MatchedOrdersOf` (r:0 w:1)
+	/// Proof: `Liability::MatchedOrdersOf` (`max_values`: None, `max_size`: Some(312), added: 2787, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(175), added: 2650, mode: `MaxEncodedLen`)
+	fn prune() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `571`
+		//  Estimated: `18209`
+		// Minimum execution time: 24_903_000 picoseconds.
+		Weight::from_parts(25_716_000, 0)
+			.saturating_add(Weight::from_parts(0, 18209))
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(11))
 	}
 }
@@ -16,18 +16,21 @@
 //
 ///////////////////////////////////////////////////////////////////////////////
 use super::{
-    AccountId, AllPalletsWithSystem, Balances, DealWithFees, MessageQueue, ParachainInfo,
-    ParachainSystem, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, WeightToFee, XcmPallet,
-    XcmpQueue, COASE, TREASURY_PALLET_ID,
+    AccountId, AllPalletsWithSystem, Balance, Balances, DealWithFees, ForeignAssets, MessageQueue,
+    ParachainInfo, ParachainSystem, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, WeightToFee,
+    XcmPallet, XcmpQueue, COASE, TREASURY_PALLET_ID,
 };
 use cumulus_primitives_core::{AggregateMessageOrigin, IsSystem, ParaId};
 use frame_support::{
     pallet_prelude::PhantomData,
     parameter_types,
-    traits::{Contains, Disabled, Equals, Everything, Nothing, TransformOrigin},
+    traits::{
+        Contains, ContainsPair, Disabled, EnsureOrigin, EnsureOriginWithArg, Equals, Everything,
+        Nothing, TransformOrigin,
+    },
 };
 use polkadot_parachain_primitives::primitives::Sibling;
-use sp_runtime::traits::{AccountIdConversion, ConstU32};
+use sp_runtime::traits::{AccountIdConversion, ConstU32, TryConvertInto};
 
 // Polkadot imports
 use xcm::latest::prelude::*;
@@ -35,13 +38,14 @@ use xcm_builder::{
     AccountId32Aliases, AllowExplicitUnpaidExecutionFrom, AllowHrmpNotificationsFromRelayChain,
     AllowKnownQueryResponses, AllowSubscriptionsFrom, AllowTopLevelPaidExecutionFrom,
     DenyRecursively, DenyReserveTransferToRelayChain, DenyThenTry, DescribeAllTerminal,
-    DescribeFamily, EnsureXcmOrigin, FungibleAdapter, HashedDescription, IsConcrete,
-    ParentIsPreset, RelayChainAsNative, SendXcmFeeToAccount, SiblingParachainAsNative,
-    SiblingParachainConvertsVia, SignedAccountId32AsNative, SignedToAccountId32,
-    SovereignSignedViaLocation, TakeWeightCredit, TrailingSetTopicAsId, UsingComponents,
-    WeightInfoBounds, WithComputedOrigin, XcmFeeManagerFromComponents,
+    DescribeFamily, EnsureXcmOrigin, FungibleAdapter, FungiblesAdapter, HashedDescription,
+    IsConcrete, MatchedConvertedConcreteId, NoChecking, ParentIsPreset, RelayChainAsNative,
+    SendXcmFeeToAccount, SiblingParachainAsNative, SiblingParachainConvertsVia,
+    SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, StartsWith,
+    TakeWeightCredit, TrailingSetTopicAsId, UsingComponents, WeightInfoBounds, WithComputedOrigin,
+    WithLatestLocationConverter, XcmFeeManagerFromComponents,
 };
-use xcm_executor::{Config, XcmExecutor};
+use xcm_executor::{traits::ConvertLocation, Config, XcmExecutor};
 
 pub const ASSET_HUB_ID: u32 = 1000;
 
@@ -92,8 +96,79 @@ pub type FungibleTransactor = FungibleAdapter<
     (),
 >;
 
+/// Means for transacting foreign assets (relay chain token, Asset Hub assets) on this chain.
+pub type ForeignAssetsTransactor = FungiblesAdapter<
+    // Use this fungibles implementation:
+    ForeignAssets,
+    // Foreign assets are identified by their location, native asset is handled above:
+    MatchedConvertedConcreteId<
+        Location,
+        Balance,
+        StartsWith<RelayLocation>,
+        WithLatestLocationConverter<Location>,
+        TryConvertInto,
+    >,
+    // Convert an XCM Location into a local account id:
+    LocationToAccountId,
+    // Our chain's account ID type (we can't get away without mentioning it explicitly):
+    AccountId,
+    // We don't track any teleports of foreign assets.
+    NoChecking,
+    // The account to use for tracking teleports.
+    CheckingAccount,
+>;
+
 /// Means for transacting assets on this chain.
-pub type AssetTransactors = (FungibleTransactor,);
+pub type AssetTransactors = (FungibleTransactor, ForeignAssetsTransactor);
+
+/// Foreign assets which reserve location is Asset Hub: relay chain token and Asset Hub assets.
+pub struct AssetHubReserveAssets;
+impl ContainsPair<Asset, Location> for AssetHubReserveAssets {
+    fn contains(asset: &Asset, origin: &Location) -> bool {
+        origin == &AssetHubLocation::get()
+            && matches!(
+                asset.id.0.unpack(),
+                (1, []) | (1, [Parachain(ASSET_HUB_ID), ..])
+            )
+    }
+}
+
+/// Foreign assets registration origin: sibling parachain could create assets under its own
+/// location and pays deposit from its sovereign account.
+pub struct ForeignCreators;
+impl EnsureOriginWithArg<RuntimeOrigin, Location> for ForeignCreators {
+    type Success = AccountId;
+
+    fn try_origin(
+        origin: RuntimeOrigin,
+        asset_location: &Location,
+    ) -> Result<Self::Success, RuntimeOrigin> {
+        let origin_location = pallet_xcm::EnsureXcm::<Everything>::try_origin(origin.clone())?;
+        if !matches!(origin_location.unpack(), (1, [Parachain(_)]))
+            || !asset_location.starts_with(&origin_location)
+        {
+            return Err(origin);
+        }
+        LocationToAccountId::convert_location(&origin_location).ok_or(origin)
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn try_successful_origin(asset_location: &Location) -> Result<RuntimeOrigin, ()> {
+        Ok(pallet_xcm::Origin::Xcm(asset_location.clone()).into())
+    }
+}
+
+/// Foreign assets benchmarks use sibling parachain locations as asset ids.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct ForeignAssetBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_assets::BenchmarkHelper<Location, ()> for ForeignAssetBenchmarkHelper {
+    fn create_asset_id_parameter(id: u32) -> Location {
+        Location::new(1, [Parachain(id)])
+    }
+
+    fn create_reserve_id_parameter(_id: u32) {}
+}
 
 /// This is the type we use to convert an (incoming) XCM origin into a local `Origin` instance,
 /// ready for dispatching a transaction with Xcm's `Transact`. There is an `OriginKind` which can
@@ -195,7 +270,7 @@ impl Config for XcmConfig {
     type XcmEventEmitter = XcmPallet;
     type AssetTransactor = AssetTransactors;
     type OriginConverter = XcmOriginToTransactDispatchOrigin;
-    type IsReserve = ();
+    type IsReserve = AssetHubReserveAssets;
     type IsTeleporter = TrustedTeleporters;
     type UniversalLocation = UniversalLocation;
     type Barrier = Barrier;
@@ -0,0 +1,185 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2026 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Liability CLI command implementations.
+//!
+//! This module provides CLI-specific wrappers around the library liability API,
+//! adding colored output and agreement draft file handling.
+
+use crate::display;
+use anyhow::{anyhow, Result};
+use colored::*;
+use libcps::blockchain::{Client, Config};
+use libcps::liability::{self, AgreementDraft, Liability, Technics};
+use subxt::utils::AccountId32;
+
+fn read_draft(path: &str) -> Result<AgreementDraft> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed to read agreement draft {}: {}", path, e))?;
+    serde_json::from_str(&json).map_err(|e| anyhow!("Invalid agreement draft {}: {}", path, e))
+}
+
+fn write_draft(draft: &AgreementDraft, path: Option<&str>) -> Result<()> {
+    let json = serde_json::to_string_pretty(draft)?;
+    match path {
+        Some(path) => {
+            std::fs::write(path, json)
+                .map_err(|e| anyhow!("Failed to write agreement draft {}: {}", path, e))?;
+            display::success(&format!("Agreement draft saved to {}", path.bright_cyan()));
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+/// Build unsigned agreement draft.
+#[allow(clippy::too_many_arguments)]
+pub async fn build(
+    config: &Config,
+    technics: &str,
+    price: u128,
+    promisee: AccountId32,
+    promisor: AccountId32,
+    validator: Option<AccountId32>,
+    nonce: Option<u64>,
+    output: Option<&str>,
+) -> Result<()> {
+    let technics: Technics = technics.parse()?;
+
+    display::progress("Connecting to blockchain...");
+    let client = Client::new(config).await?;
+    display::info(&format!("Connected to {}", config.ws_url));
+
+    let draft = AgreementDraft::new(
+        &client, &technics, price, promisee, promisor, validator, nonce,
+    )
+    .await?;
+    display::info(&format!(
+        "Agreement nonce: {}",
+        draft.nonce.to_string().bright_cyan()
+    ));
+
+    write_draft(&draft, output)
+}
+
+/// Sign agreement draft file by client account.
+pub fn sign(config: &Config, path: &str) -> Result<()> {
+    let suri = config
+        .suri
+        .as_ref()
+        .ok_or_else(|| anyhow!("SURI required for signing"))?;
+    let uri = suri
+        .parse()
+        .map_err(|e| anyhow!("Failed to parse SURI: {e}"))?;
+    let keypair = subxt_signer::sr25519::Keypair::from_uri(&uri)
+        .map_err(|e| anyhow!("Failed to create keypair: {e}"))?;
+
+    let mut draft = read_draft(path)?;
+    draft.sign(&keypair)?;
+    display::info(&format!(
+        "Signed by {}",
+        AccountId32::from(keypair.public_key().0)
+    ));
+
+    write_draft(&draft, Some(path))
+}
+
+/// Create liability of signed agreement draft.
+pub async fn create(config: &Config, path: &str) -> Result<()> {
+    let agreement = read_draft(path)?.agreement()?;
+
+    display::progress("Connecting to blockchain...");
+    let client = Client::new(config).await?;
+    let _keypair = client.require_keypair()?;
+    display::info(&format!("Connected to {}", config.ws_url));
+
+    let spinner = display::spinner("Submitting transaction...");
+    let liability = Liability::create(&client, &agreement).await?;
+    spinner.finish_and_clear();
+
+    display::success(&format!(
+        "Liability created with index: {}",
+        liability.index().to_string().bright_cyan()
+    ));
+
+    Ok(())
+}
+
+/// Watch new liabilities of client account and execute them by shell hook.
+pub async fn watch(config: &Config, exec: Option<String>) -> Result<()> {
+    display::progress("Connecting to blockchain...");
+    let client = Client::new(config).await?;
+    let keypair = client.require_keypair()?;
+    display::info(&format!("Connected to {}", config.ws_url));
+    display::info(&format!(
+        "👀 Watching liabilities of {}...",
+        AccountId32::from(keypair.public_key().0)
+            .to_string()
+            .bright_cyan()
+    ));
+
+    liability::watch(&client, |task| {
+        let exec = exec.clone();
+        async move {
+            println!(
+                "[{}] {} Liability {} from {}: {}",
+                chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+                "📥".bright_green(),
+                task.index.to_string().bright_cyan(),
+                task.promisee,
+                task.technics.to_string().bright_white()
+            );
+
+            let report = match exec {
+                Some(command) => liability::shell_hook(&command, &task).await?,
+                None => None,
+            };
+            if let Some(report) = report {
+                println!(
+                    "[{}] {} Reporting liability {}: {:?}",
+                    chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+                    "📤".bright_blue(),
+                    task.index.to_string().bright_cyan(),
+                    report
+                );
+            }
+            Ok::<_, anyhow::Error>(report)
+        }
+    })
+    .await
+}
+
+/// Publish signed liability report.
+pub async fn report(config: &Config, index: u32, report: &str) -> Result<()> {
+    let payload = liability::report_hash(report)?;
+
+    display::progress("Connecting to blockchain...");
+    let client = Client::new(config).await?;
+    let _keypair = client.require_keypair()?;
+    display::info(&format!("Connected to {}", config.ws_url));
+
+    let spinner = display::spinner("Submitting transaction...");
+    let _events = Liability::new(&client, index).report(payload).await?;
+    spinner.finish_and_clear();
+
+    display::success(&format!(
+        "Liability {} reported",
+        index.to_string().bright_cyan()
+    ));
+
+    Ok(())
+}
@@ -18,10 +18,12 @@
 //! CLI command implementations.
 
 pub mod create;
+pub mod liability;
 pub mod move_node;
 #[cfg(feature = "mqtt")]
 pub mod mqtt;
 pub mod remove;
+pub mod roots;
 pub mod set_meta;
 pub mod set_payload;
 pub mod show;
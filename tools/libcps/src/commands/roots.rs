@@ -0,0 +1,46 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2026 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Roots command implementation.
+
+use crate::display;
+use anyhow::Result;
+use colored::*;
+use libcps::blockchain::{Client, Config};
+use libcps::node::Node;
+use subxt::utils::AccountId32;
+
+pub async fn execute(config: &Config, owner: AccountId32) -> Result<()> {
+    display::progress("Connecting to blockchain...");
+
+    let client = Client::new(config).await?;
+
+    display::info(&format!("Connected to {}", config.ws_url));
+    display::progress(&format!("Fetching root nodes of {owner}..."));
+
+    let roots = Node::roots(&client, owner).await?;
+    if roots.is_empty() {
+        display::info("No root nodes found");
+        return Ok(());
+    }
+
+    for root in roots {
+        println!("{}", root.id().to_string().bright_cyan());
+    }
+
+    Ok(())
+}
@@ -37,6 +37,7 @@ pub async fn execute(
     data: String,
     receiver_public: Option<[u8; 32]>,
     algorithm: Option<libcps::crypto::EncryptionAlgorithm>,
+    ttl: Option<u32>,
 ) -> Result<()> {
     // CLI display: show connection progress
     display::progress("Connecting to blockchain...");
@@ -71,13 +72,16 @@ pub async fn execute(
     let node = Node::new(&client, node_id);
 
     let spinner = display::spinner("Submitting transaction...");
-    let _events = node.set_payload(Some(payload_data)).await?;
+    let _events = node.set_payload_with_ttl(Some(payload_data), ttl).await?;
     spinner.finish_and_clear();
 
     display::success(&format!(
         "Payload updated for node {}",
         node_id.to_string().bright_cyan()
     ));
+    if let Some(ttl) = ttl {
+        display::info(&format!("Payload expires in {ttl} blocks"));
+    }
 
     Ok(())
 }
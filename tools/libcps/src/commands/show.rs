@@ -21,10 +21,14 @@ use crate::display;
 use anyhow::Result;
 use libcps::blockchain::{BoundedVec, Client, Config};
 use libcps::crypto::{Cipher, EncryptedMessage};
-use libcps::node::Node;
+use libcps::node::{Node, NodeInfo};
 use parity_scale_codec::Decode;
-use std::future::Future;
-use std::pin::Pin;
+use std::collections::BTreeMap;
+
+/// Maximum depth of displayed tree
+const MAX_DEPTH: u32 = 32;
+/// Maximum number of displayed nodes
+const MAX_NODES: u32 = 1000;
 
 pub async fn execute(config: &Config, cipher: Option<&Cipher>, node_id: u64) -> Result<()> {
     display::progress("Connecting to blockchain...");
@@ -34,82 +38,90 @@ pub async fn execute(config: &Config, cipher: Option<&Cipher>, node_id: u64) ->
     display::info(&format!("Connected to {}", config.ws_url));
     display::progress(&format!("Fetching node tree from node {node_id}..."));
 
+    // Fetch the whole tree in a single runtime API call
+    let nodes = Node::new(&client, node_id)
+        .subtree(MAX_DEPTH, MAX_NODES)
+        .await?;
+    let truncated = nodes.len() as u32 == MAX_NODES;
+    let nodes: BTreeMap<u64, NodeInfo> = nodes.into_iter().map(|info| (info.id, info)).collect();
+
     // Print the tree recursively
-    print_node_tree(&client, node_id, cipher, "", true).await?;
+    print_node_tree(&nodes, node_id, cipher, "", true)?;
+
+    if truncated {
+        display::info(&format!("Tree truncated to {MAX_NODES} nodes"));
+    }
 
     Ok(())
 }
 
 /// Recursively print a node and all its children in tree format
-fn print_node_tree<'a>(
-    client: &'a Client,
+fn print_node_tree(
+    nodes: &BTreeMap<u64, NodeInfo>,
     node_id: u64,
-    cipher: Option<&'a Cipher>,
-    prefix: &'a str,
+    cipher: Option<&Cipher>,
+    prefix: &str,
     is_last: bool,
-) -> Pin<Box<dyn Future<Output = Result<()>> + 'a>> {
-    Box::pin(async move {
-        // Query node using Node API
-        let node = Node::new(client, node_id);
-        let node_info = node.query().await?;
-
-        let node_data_to_string = |nd: BoundedVec<u8>| {
-            // Try to decode as EncryptedMessage first
-            if let Ok(message) = EncryptedMessage::decode(&mut nd.0.as_slice()) {
-                if let Some(cipher) = cipher {
-                    let decrypted = cipher
-                        .decrypt(&message, None)
-                        .map_err(|e| anyhow::anyhow!("Failed to decrypt message: {}.", e))?;
-                    String::from_utf8(decrypted)
-                        .map_err(|_| anyhow::anyhow!("Invalid UTF-8 character"))
-                } else {
-                    serde_json::to_string(&message)
-                        .map_err(|e| {
-                            anyhow::anyhow!("Failed to convert encrypted message into JSON: {}.", e)
-                        })
-                        .map(|json_msg| format!("Encrypted: {}", json_msg))
-                }
+) -> Result<()> {
+    let Some(node_info) = nodes.get(&node_id) else {
+        return Ok(());
+    };
+
+    let node_data_to_string = |nd: BoundedVec<u8>| {
+        // Try to decode as EncryptedMessage first
+        if let Ok(message) = EncryptedMessage::decode(&mut nd.0.as_slice()) {
+            if let Some(cipher) = cipher {
+                let decrypted = cipher
+                    .decrypt(&message, None)
+                    .map_err(|e| anyhow::anyhow!("Failed to decrypt message: {}.", e))?;
+                String::from_utf8(decrypted).map_err(|_| anyhow::anyhow!("Invalid UTF-8 character"))
             } else {
-                // Treat as plain data
-                String::from_utf8(nd.0).map_err(|_| anyhow::anyhow!("Invalid UTF-8 character"))
+                serde_json::to_string(&message)
+                    .map_err(|e| {
+                        anyhow::anyhow!("Failed to convert encrypted message into JSON: {}.", e)
+                    })
+                    .map(|json_msg| format!("Encrypted: {}", json_msg))
             }
-        };
+        } else {
+            // Treat as plain data
+            String::from_utf8(nd.0).map_err(|_| anyhow::anyhow!("Invalid UTF-8 character"))
+        }
+    };
 
-        // Try to decrypt if requested and data is encrypted
-        let meta_str = match node_info.meta {
-            Some(meta) => Some(node_data_to_string(meta)?),
-            _ => None,
-        };
+    // Try to decrypt if requested and data is encrypted
+    let meta_str = match node_info.meta.clone() {
+        Some(meta) => Some(node_data_to_string(meta)?),
+        _ => None,
+    };
 
-        let payload_str = match node_info.payload {
-            Some(payload) => Some(node_data_to_string(payload)?),
-            _ => None,
-        };
+    let payload_str = match node_info.payload.clone() {
+        Some(payload) => Some(node_data_to_string(payload)?),
+        _ => None,
+    };
 
-        // Print this node
-        display::tree::print_node_recursive(
-            node_id,
-            node_info.owner,
-            meta_str.as_deref(),
-            payload_str.as_deref(),
-            prefix,
-            is_last,
-        );
-
-        // Recursively print children
-        if !node_info.children.is_empty() {
-            let child_prefix = if is_last {
-                format!("{}    ", prefix)
-            } else {
-                format!("{}|   ", prefix)
-            };
+    // Print this node
+    display::tree::print_node_recursive(
+        node_id,
+        node_info.owner.clone(),
+        meta_str.as_deref(),
+        payload_str.as_deref(),
+        prefix,
+        is_last,
+    );
 
-            for (i, child_id) in node_info.children.iter().enumerate() {
-                let is_last_child = i == node_info.children.len() - 1;
-                print_node_tree(client, *child_id, cipher, &child_prefix, is_last_child).await?;
-            }
+    // Recursively print children
+    if !node_info.children.is_empty() {
+        let child_prefix = if is_last {
+            format!("{}    ", prefix)
+        } else {
+            format!("{}|   ", prefix)
+        };
+
+        for (i, child_id) in node_info.children.iter().enumerate() {
+            let is_last_child = i == node_info.children.len() - 1;
+            print_node_tree(nodes, *child_id, cipher, &child_prefix, is_last_child)?;
         }
+    }
 
-        Ok(())
-    })
+    Ok(())
 }
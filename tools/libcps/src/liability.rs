@@ -0,0 +1,663 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2026 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Liability API for Robonomics liability pallet interactions.
+//!
+//! This module lets robots and their clients make agreements, start liabilities
+//! and publish execution reports. It covers the whole liability lifecycle:
+//!
+//! 1. **Build**: promisee or promisor makes an [`AgreementDraft`] with task technics and price
+//! 2. **Sign**: both parties sign the draft, signatures are compatible with pallet
+//!    `AgreementProofBuilder`
+//! 3. **Create**: anyone submits the signed agreement with [`Liability::create`]
+//! 4. **Watch**: promisor daemon waits for new liabilities with [`watch`] and executes them
+//! 5. **Report**: promisor publishes signed execution report with [`Liability::report`]
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use libcps::blockchain::{Client, Config};
+//! use libcps::liability;
+//!
+//! #[tokio::main]
+//! async fn main() -> anyhow::Result<()> {
+//!     let config = Config {
+//!         ws_url: "ws://localhost:9944".to_string(),
+//!         suri: Some("//Bob".to_string()),
+//!     };
+//!     let client = Client::new(&config).await?;
+//!
+//!     // Execute each new liability and report its result
+//!     liability::watch(&client, |task| async move {
+//!         println!("Executing liability {}: {}", task.index, task.technics);
+//!         let report = liability::report_hash("QmbWqxBEKC3P8tqsKc98xmWNzrzDtRLMiMPL8wBuTGsMnR")?;
+//!         Ok::<_, anyhow::Error>(Some(report))
+//!     })
+//!     .await?;
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use crate::blockchain::{api, Client, ExtrinsicEvents, RobonomicsConfig};
+use anyhow::{anyhow, Result};
+use log::{debug, error, trace};
+use parity_scale_codec::{Decode, Encode};
+use robonomics_liability_technics::SHA2_256;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use subxt::events::EventDetails;
+use subxt::utils::{AccountId32, MultiSignature, H256};
+use subxt_signer::sr25519::Keypair;
+
+/// Liability index.
+pub type Index = u32;
+
+/// Liability task description, matches pallet `Technics` encoding.
+///
+/// Text form of technics is a CID string (`Qm...`, `b...` or `z...`),
+/// `node:<id>` for CPS node or `inline:<text>` for task stored on chain.
+pub type Technics = robonomics_liability_technics::Technics<Vec<u8>, Vec<u8>>;
+
+/// Liability price in native token, matches pallet `SimpleMarket` encoding.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct SimpleMarket {
+    /// Liability price paid by promisee to promisor
+    #[codec(compact)]
+    pub price: u128,
+}
+
+/// Liability economics, matches pallet `NativeOrAssetMarket` encoding.
+///
+/// Liabilities paid in foreign assets aren't supported, their agreements
+/// and events fail to decode.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub enum Economics {
+    /// Price paid in native token
+    #[codec(index = 0)]
+    Native(SimpleMarket),
+}
+
+impl Economics {
+    /// Liability price in native token.
+    pub fn price(&self) -> u128 {
+        match self {
+            Economics::Native(market) => market.price,
+        }
+    }
+}
+
+/// Agreement signed by both parties, matches pallet `SignedAgreement` encoding.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct Agreement {
+    /// Task description
+    pub technics: Technics,
+    /// Task price
+    pub economics: Economics,
+    /// Client account
+    pub promisee: AccountId32,
+    /// Executor account
+    pub promisor: AccountId32,
+    /// Optional account that confirms execution report
+    pub validator: Option<AccountId32>,
+    /// Promisee agreement nonce
+    #[codec(compact)]
+    pub nonce: u64,
+    /// Promisee signature of agreement parameters
+    pub promisee_signature: MultiSignature,
+    /// Promisor signature of agreement parameters
+    pub promisor_signature: MultiSignature,
+}
+
+/// Execution report signed by promisor, matches pallet `SignedReport` encoding.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct SignedReport {
+    /// Liability index
+    pub index: Index,
+    /// Report sender account
+    pub sender: AccountId32,
+    /// SHA2-256 digest of report content in IPFS
+    pub payload: H256,
+    /// Sender signature of liability index and payload
+    pub signature: MultiSignature,
+}
+
+impl SignedReport {
+    /// Make report of liability `index` signed by `keypair`.
+    pub fn new(index: Index, payload: H256, keypair: &Keypair) -> Self {
+        let signature = keypair.sign(&(index, payload).encode()).into();
+        Self {
+            index,
+            sender: AccountId32::from(keypair.public_key().0),
+            payload,
+            signature,
+        }
+    }
+}
+
+/// New liability started on chain, decoded from `NewLiability` event.
+#[derive(Clone, Debug, PartialEq, Eq, Decode)]
+pub struct NewLiability {
+    /// Liability index
+    pub index: Index,
+    /// Task description
+    pub technics: Technics,
+    /// Task price
+    pub economics: Economics,
+    /// Client account
+    pub promisee: AccountId32,
+    /// Executor account
+    pub promisor: AccountId32,
+}
+
+impl NewLiability {
+    /// Decode `NewLiability` event, returns `None` for other events.
+    pub fn from_event(event: &EventDetails<RobonomicsConfig>) -> Option<Self> {
+        if event.pallet_name() != "Liability" || event.variant_name() != "NewLiability" {
+            return None;
+        }
+        Self::decode(&mut event.field_bytes()).ok()
+    }
+}
+
+/// Agreement parameters exchanged between parties before liability creation.
+///
+/// Draft is serialized to JSON, each party adds its signature with [`AgreementDraft::sign`]
+/// and when both signatures present draft could be turned into [`Agreement`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AgreementDraft {
+    /// Genesis hash of the chain agreement is made for
+    pub genesis_hash: H256,
+    /// Task description in text form
+    pub technics: String,
+    /// Task price
+    pub price: u128,
+    /// Client account
+    pub promisee: AccountId32,
+    /// Executor account
+    pub promisor: AccountId32,
+    /// Optional account that confirms execution report
+    #[serde(default)]
+    pub validator: Option<AccountId32>,
+    /// Promisee agreement nonce
+    pub nonce: u64,
+    /// Hex-encoded promisee sr25519 signature
+    #[serde(default)]
+    pub promisee_signature: Option<String>,
+    /// Hex-encoded promisor sr25519 signature
+    #[serde(default)]
+    pub promisor_signature: Option<String>,
+}
+
+impl AgreementDraft {
+    /// Make unsigned agreement draft for connected chain.
+    ///
+    /// When `nonce` isn't given the next promisee nonce is fetched from chain.
+    pub async fn new(
+        client: &Client,
+        technics: &Technics,
+        price: u128,
+        promisee: AccountId32,
+        promisor: AccountId32,
+        validator: Option<AccountId32>,
+        nonce: Option<u64>,
+    ) -> Result<Self> {
+        let nonce = match nonce {
+            Some(nonce) => nonce,
+            None => next_nonce(client, &promisee).await?,
+        };
+        debug!(
+            "Building agreement draft: promisee={}, promisor={}, nonce={}",
+            promisee, promisor, nonce
+        );
+
+        Ok(Self {
+            genesis_hash: client.api.genesis_hash(),
+            technics: technics.to_string(),
+            price,
+            promisee,
+            promisor,
+            validator,
+            nonce,
+            promisee_signature: None,
+            promisor_signature: None,
+        })
+    }
+
+    /// Agreement parameters signed by both parties.
+    pub fn payload(&self) -> Result<Vec<u8>> {
+        let technics: Technics = self.technics.parse()?;
+        let economics = Economics::Native(SimpleMarket { price: self.price });
+        Ok((
+            self.genesis_hash.as_bytes(),
+            &technics,
+            &economics,
+            &self.validator,
+            &self.nonce,
+        )
+            .encode())
+    }
+
+    /// Sign draft by promisee or promisor `keypair`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `keypair` account isn't an agreement party.
+    pub fn sign(&mut self, keypair: &Keypair) -> Result<()> {
+        let account = AccountId32::from(keypair.public_key().0);
+        if account != self.promisee && account != self.promisor {
+            return Err(anyhow!(
+                "Account {} is neither promisee nor promisor of agreement",
+                account
+            ));
+        }
+
+        let signature = format!("0x{}", hex::encode(keypair.sign(&self.payload()?).0));
+        trace!("Agreement signed by {}", account);
+        if account == self.promisee {
+            self.promisee_signature = Some(signature.clone());
+        }
+        if account == self.promisor {
+            self.promisor_signature = Some(signature);
+        }
+        Ok(())
+    }
+
+    /// Agreement of signed draft.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any party signature is missing or malformed.
+    pub fn agreement(&self) -> Result<Agreement> {
+        let promisee_signature = self
+            .promisee_signature
+            .as_deref()
+            .ok_or_else(|| anyhow!("Agreement isn't signed by promisee"))
+            .and_then(parse_signature)?;
+        let promisor_signature = self
+            .promisor_signature
+            .as_deref()
+            .ok_or_else(|| anyhow!("Agreement isn't signed by promisor"))
+            .and_then(parse_signature)?;
+
+        Ok(Agreement {
+            technics: self.technics.parse()?,
+            economics: Economics::Native(SimpleMarket { price: self.price }),
+            promisee: self.promisee.clone(),
+            promisor: self.promisor.clone(),
+            validator: self.validator.clone(),
+            nonce: self.nonce,
+            promisee_signature,
+            promisor_signature,
+        })
+    }
+}
+
+/// A handle to a specific liability on the blockchain.
+///
+/// Like [`crate::node::Node`], it holds a reference to the blockchain client
+/// and the liability index.
+pub struct Liability<'a> {
+    client: &'a Client,
+    index: Index,
+}
+
+impl<'a> Liability<'a> {
+    /// Create a new `Liability` handle for an existing liability.
+    pub fn new(client: &'a Client, index: Index) -> Self {
+        Self { client, index }
+    }
+
+    /// Get the liability index.
+    pub fn index(&self) -> Index {
+        self.index
+    }
+
+    /// Start liability of agreement signed by both parties.
+    ///
+    /// Transaction sender pays liability storage deposit.
+    ///
+    /// # Returns
+    ///
+    /// A `Liability` handle to the newly created liability.
+    pub async fn create(client: &'a Client, agreement: &Agreement) -> Result<Self> {
+        debug!(
+            "Creating liability: promisee={}, promisor={}, nonce={}",
+            agreement.promisee, agreement.promisor, agreement.nonce
+        );
+        let keypair = client.require_keypair()?;
+
+        // Runtime agreement type has the same encoding
+        let agreement = Decode::decode(&mut &agreement.encode()[..])
+            .map_err(|e| anyhow!("Agreement doesn't match runtime type: {}", e))?;
+        let create_call = api::tx().liability().create(agreement);
+
+        trace!("Submitting and watching transaction");
+        let events = client
+            .api
+            .tx()
+            .sign_and_submit_then_watch_default(&create_call, keypair)
+            .await
+            .map_err(|e| anyhow!("Failed to submit create transaction: {}", e))?
+            .wait_for_finalized_success()
+            .await
+            .map_err(|e| anyhow!("Transaction failed: {}", e))?;
+
+        let liability = events
+            .iter()
+            .filter_map(|event| event.ok())
+            .find_map(|event| NewLiability::from_event(&event))
+            .ok_or_else(|| anyhow!("NewLiability event not found in transaction events"))?;
+        debug!("Liability created successfully: index={}", liability.index);
+
+        Ok(Self {
+            client,
+            index: liability.index,
+        })
+    }
+
+    /// Query liability agreement at latest finalized block.
+    pub async fn agreement(&self) -> Result<Agreement> {
+        let agreement_query = api::storage().liability().agreement_of(self.index);
+        let agreement = self
+            .client
+            .api
+            .storage()
+            .at_latest()
+            .await?
+            .fetch(&agreement_query)
+            .await
+            .map_err(|e| anyhow!("Failed to query agreement storage: {}", e))?
+            .ok_or_else(|| anyhow!("Liability {} not found", self.index))?;
+
+        Agreement::decode(&mut &agreement.encode()[..])
+            .map_err(|e| anyhow!("Unsupported runtime agreement type: {}", e))
+    }
+
+    /// Publish execution report signed by client account.
+    ///
+    /// # Arguments
+    ///
+    /// * `payload` - SHA2-256 digest of report content in IPFS, see [`report_hash`]
+    ///
+    /// # Returns
+    ///
+    /// ExtrinsicEvents with transaction hash and events.
+    pub async fn report(&self, payload: H256) -> Result<ExtrinsicEvents> {
+        debug!("Reporting liability {}: payload={:?}", self.index, payload);
+        let keypair = self.client.require_keypair()?;
+        let report = SignedReport::new(self.index, payload, keypair);
+
+        // Runtime report type has the same encoding
+        let report = Decode::decode(&mut &report.encode()[..])
+            .map_err(|e| anyhow!("Report doesn't match runtime type: {}", e))?;
+        let finalize_call = api::tx().liability().finalize(report);
+
+        let events = self
+            .client
+            .api
+            .tx()
+            .sign_and_submit_then_watch_default(&finalize_call, keypair)
+            .await
+            .map_err(|e| anyhow!("Failed to submit finalize transaction: {}", e))?
+            .wait_for_finalized_success()
+            .await
+            .map_err(|e| anyhow!("Transaction failed: {}", e))?;
+
+        debug!("Liability {} reported successfully", self.index);
+        Ok(events)
+    }
+}
+
+/// Next agreement nonce acceptable for promisee account.
+pub async fn next_nonce(client: &Client, promisee: &AccountId32) -> Result<u64> {
+    let nonce_query = api::storage().liability().nonce_of(promisee.clone());
+    let nonce = client
+        .api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&nonce_query)
+        .await
+        .map_err(|e| anyhow!("Failed to query agreement nonce: {}", e))?
+        .unwrap_or_default();
+    Ok(nonce)
+}
+
+/// Report payload of SHA2-256 CID or hex-encoded 32-byte digest.
+pub fn report_hash(cid_or_hex: &str) -> Result<H256> {
+    if let Some(hex_str) = cid_or_hex.strip_prefix("0x") {
+        let bytes = hex::decode(hex_str).map_err(|e| anyhow!("Invalid report hash: {}", e))?;
+        if bytes.len() != 32 {
+            return Err(anyhow!(
+                "Invalid report hash: expected 32 bytes, got {}",
+                bytes.len()
+            ));
+        }
+        return Ok(H256::from_slice(&bytes));
+    }
+
+    match cid_or_hex.parse::<Technics>()? {
+        Technics::Cid { hash, digest, .. } if hash == SHA2_256 && digest.len() == 32 => {
+            Ok(H256::from_slice(&digest))
+        }
+        _ => Err(anyhow!(
+            "Report should be SHA2-256 CID, got: {}",
+            cid_or_hex
+        )),
+    }
+}
+
+/// Watch new liabilities of client account as promisor and execute them.
+///
+/// For each `NewLiability` event in finalized blocks where client account is promisor
+/// `handler` is called. When handler returns report payload it's published with
+/// [`Liability::report`], `None` leaves liability without report.
+///
+/// This function runs indefinitely and only returns on fatal errors.
+pub async fn watch<H, F>(client: &Client, handler: H) -> Result<()>
+where
+    H: Fn(NewLiability) -> F,
+    F: Future<Output = Result<Option<H256>>>,
+{
+    let keypair = client.require_keypair()?;
+    let account = AccountId32::from(keypair.public_key().0);
+    debug!("Watching liabilities of {}", account);
+
+    let mut blocks_sub = client
+        .api
+        .blocks()
+        .subscribe_finalized()
+        .await
+        .map_err(|e| anyhow!("Failed to subscribe to finalized blocks: {}", e))?;
+
+    while let Some(block_result) = blocks_sub.next().await {
+        let Ok(block) = block_result else {
+            continue;
+        };
+        let Ok(events) = block.events().await else {
+            continue;
+        };
+
+        for event in events.iter().filter_map(|event| event.ok()) {
+            let Some(liability) = NewLiability::from_event(&event) else {
+                continue;
+            };
+            if liability.promisor != account {
+                continue;
+            }
+
+            let index = liability.index;
+            debug!("New liability {} at block {}", index, block.number());
+            match handler(liability).await {
+                Ok(Some(payload)) => {
+                    if let Err(e) = Liability::new(client, index).report(payload).await {
+                        error!("Failed to report liability {}: {}", index, e);
+                    }
+                }
+                Ok(None) => debug!("Liability {} left without report", index),
+                Err(e) => error!("Liability {} handler failed: {}", index, e),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Execute liability by shell `command`, usable as [`watch`] handler.
+///
+/// Liability parameters are passed by `LIABILITY_INDEX`, `LIABILITY_TECHNICS`,
+/// `LIABILITY_PRICE`, `LIABILITY_PROMISEE` and `LIABILITY_PROMISOR` environment
+/// variables. Command prints report CID to stdout, empty output means no report.
+pub async fn shell_hook(command: &str, liability: &NewLiability) -> Result<Option<H256>> {
+    trace!(
+        "Running hook for liability {}: {}",
+        liability.index,
+        command
+    );
+    let output = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("LIABILITY_INDEX", liability.index.to_string())
+        .env("LIABILITY_TECHNICS", liability.technics.to_string())
+        .env("LIABILITY_PRICE", liability.economics.price().to_string())
+        .env("LIABILITY_PROMISEE", liability.promisee.to_string())
+        .env("LIABILITY_PROMISOR", liability.promisor.to_string())
+        .output()
+        .await
+        .map_err(|e| anyhow!("Failed to run hook: {}", e))?;
+
+    if !output.status.success() {
+        return Err(anyhow!("Hook exited with {}", output.status));
+    }
+
+    let stdout = String::from_utf8(output.stdout)
+        .map_err(|e| anyhow!("Invalid UTF-8 in hook output: {}", e))?;
+    match stdout.trim() {
+        "" => Ok(None),
+        report => report_hash(report).map(Some),
+    }
+}
+
+fn parse_signature(hex_str: &str) -> Result<MultiSignature> {
+    let bytes = hex::decode(hex_str.strip_prefix("0x").unwrap_or(hex_str))
+        .map_err(|e| anyhow!("Invalid signature: {}", e))?;
+    let signature: [u8; 64] = bytes
+        .try_into()
+        .map_err(|_| anyhow!("Invalid signature: expected 64 bytes"))?;
+    Ok(MultiSignature::Sr25519(signature))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sp_core::{sr25519, Pair};
+    use std::str::FromStr;
+    use subxt_signer::sr25519::dev;
+
+    const CID_V0: &str = "QmbWqxBEKC3P8tqsKc98xmWNzrzDtRLMiMPL8wBuTGsMnR";
+    const CID_V1: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+
+    #[test]
+    fn test_technics_from_str() {
+        let technics = Technics::from_str(CID_V0).unwrap();
+        assert_eq!(technics, Technics::from_str(CID_V1).unwrap());
+        assert_eq!(technics.to_string(), CID_V1);
+
+        assert_eq!(
+            Technics::from_str("node:42").unwrap(),
+            Technics::CpsNode(42)
+        );
+        assert_eq!(
+            Technics::from_str("inline:move to A").unwrap().to_string(),
+            "inline:move to A"
+        );
+        assert!(Technics::from_str("unknown").is_err());
+        assert!(Technics::from_str("bafy").is_err());
+    }
+
+    #[test]
+    fn test_report_hash() {
+        let hash = report_hash(CID_V0).unwrap();
+        assert_eq!(report_hash(CID_V1).unwrap(), hash);
+        assert_eq!(report_hash(&format!("{:?}", hash)).unwrap(), hash);
+        assert!(report_hash("node:1").is_err());
+    }
+
+    /// Encoding of `pallet_robonomics_liability::signed::agreement_payload` for
+    /// genesis hash `0x0101..01`, `CID_V1` technics, price 10, no validator and nonce 7.
+    const AGREEMENT_PAYLOAD: &str = concat!(
+        // domain: compact length 32 and genesis hash
+        "80",
+        "0101010101010101010101010101010101010101010101010101010101010101",
+        // technics: Cid variant, compact dag-pb codec, compact sha2-256 hash, digest
+        "00",
+        "c101",
+        "48",
+        "80",
+        "c3c4733ec8affd06cf9e9ff50ffc6bcd2ec85a6170004bb709669c31de94391a",
+        // economics: native variant, compact price
+        "00",
+        "28",
+        // validator: none
+        "00",
+        // promisee nonce: u64
+        "0700000000000000",
+    );
+
+    #[test]
+    fn test_agreement_signature() {
+        let alice = dev::alice();
+        let bob = dev::bob();
+        let mut draft = AgreementDraft {
+            genesis_hash: H256::repeat_byte(1),
+            technics: CID_V1.to_string(),
+            price: 10,
+            promisee: AccountId32::from(alice.public_key().0),
+            promisor: AccountId32::from(bob.public_key().0),
+            validator: None,
+            nonce: 7,
+            promisee_signature: None,
+            promisor_signature: None,
+        };
+        let payload = hex::decode(AGREEMENT_PAYLOAD).unwrap();
+        assert_eq!(draft.payload().unwrap(), payload);
+
+        assert!(draft.sign(&dev::charlie()).is_err());
+        assert!(draft.agreement().is_err());
+
+        draft.sign(&alice).unwrap();
+        draft.sign(&bob).unwrap();
+        let agreement = draft.agreement().unwrap();
+        assert_eq!(agreement.nonce, 7);
+
+        // Pallet verifies signatures of the fixed payload encoding
+        for (signature, account) in [
+            (&agreement.promisee_signature, &agreement.promisee),
+            (&agreement.promisor_signature, &agreement.promisor),
+        ] {
+            let MultiSignature::Sr25519(signature) = signature else {
+                panic!("sr25519 signature expected");
+            };
+            assert!(sr25519::Pair::verify(
+                &sr25519::Signature::from_raw(*signature),
+                &payload,
+                &sr25519::Public::from_raw(account.0),
+            ));
+        }
+    }
+}
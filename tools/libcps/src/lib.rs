@@ -56,6 +56,7 @@
 //!
 //! - [`blockchain`]: Blockchain client and connection management
 //! - [`crypto`]: Encryption and key derivation utilities
+//! - [`liability`]: Liability agreements, execution watcher and signed reports
 //! - [`mqtt`]: MQTT bridge configuration and types (optional feature)
 //! - [`node`]: Node-oriented API with type definitions and async methods for CPS operations
 //!
@@ -221,6 +222,7 @@
 
 pub mod blockchain;
 pub mod crypto;
+pub mod liability;
 #[cfg(feature = "mqtt")]
 pub mod mqtt;
 pub mod node;
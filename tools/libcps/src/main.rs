@@ -262,7 +262,10 @@ EXAMPLES:
     # Update with ED25519 and AES-GCM
     cps set-payload 5 'encrypted telemetry' \\
         --receiver-public 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY \\
-        --scheme ed25519 --cipher aesgcm256")]
+        --scheme ed25519 --cipher aesgcm256
+
+    # Reading which expires after 100 blocks
+    cps set-payload 5 '23.1C' --ttl 100")]
     SetPayload {
         /// Node ID
         node_id: u64,
@@ -282,6 +285,10 @@ EXAMPLES:
         /// Cryptographic scheme for encryption (sr25519, ed25519)
         #[arg(long, default_value = "sr25519", value_parser = clap::value_parser!(libcps::crypto::CryptoScheme))]
         scheme: libcps::crypto::CryptoScheme,
+
+        /// Number of blocks after which payload expires
+        #[arg(long)]
+        ttl: Option<u32>,
     },
 
     /// Move a node to a new parent
@@ -302,6 +309,17 @@ FEATURES:
         new_parent_id: u64,
     },
 
+    /// List root nodes owned by an account
+    #[command(long_about = "List root nodes owned by an account.
+
+EXAMPLES:
+    # List root nodes of account
+    cps roots 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")]
+    Roots {
+        /// Owner account (SS58 address or hex public key)
+        owner: String,
+    },
+
     /// Delete a node (must have no children)
     #[command(long_about = "Delete a node (must have no children).
 
@@ -320,12 +338,128 @@ EXAMPLES:
         force: bool,
     },
 
+    /// Liability agreements and execution commands
+    #[command(subcommand)]
+    Liability(LiabilityCommands),
+
     /// MQTT bridge commands
     #[cfg(feature = "mqtt")]
     #[command(subcommand)]
     Mqtt(MqttCommands),
 }
 
+#[derive(Subcommand)]
+enum LiabilityCommands {
+    /// Build unsigned agreement draft
+    #[command(long_about = "Build unsigned agreement draft.
+
+Agreement draft is a JSON file that should be signed by both promisee and
+promisor before liability creation. Technics could be IPFS CID, CPS node
+reference (node:<id>) or inline task description (inline:<text>).
+
+EXAMPLES:
+    # Build agreement for task in IPFS
+    cps liability build QmbWqxBEKC3P8tqsKc98xmWNzrzDtRLMiMPL8wBuTGsMnR \\
+        --price 1000000000 \\
+        --promisee 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY \\
+        --promisor 5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty \\
+        -o agreement.json
+
+    # Build agreement for task described by CPS node
+    cps liability build node:5 --price 1000000000 \\
+        --promisee 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY \\
+        --promisor 5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty")]
+    Build {
+        /// Task technics: CID, node:<id> or inline:<text>
+        technics: String,
+
+        /// Task price in the smallest units
+        #[arg(long)]
+        price: u128,
+
+        /// Promisee (client) SS58 address or hex public key
+        #[arg(long)]
+        promisee: String,
+
+        /// Promisor (executor) SS58 address or hex public key
+        #[arg(long)]
+        promisor: String,
+
+        /// Optional report validator SS58 address or hex public key,
+        /// rejected by runtimes without validated reports
+        #[arg(long)]
+        validator: Option<String>,
+
+        /// Promisee agreement nonce (fetched from chain if omitted)
+        #[arg(long)]
+        nonce: Option<u64>,
+
+        /// Output file (stdout if omitted)
+        #[arg(short = 'o', long)]
+        output: Option<String>,
+    },
+
+    /// Sign agreement draft by promisee or promisor account
+    #[command(long_about = "Sign agreement draft by promisee or promisor account.
+
+EXAMPLES:
+    # Sign by promisee and promisor
+    cps liability sign agreement.json --suri //Alice
+    cps liability sign agreement.json --suri //Bob")]
+    Sign {
+        /// Agreement draft file
+        draft: String,
+    },
+
+    /// Create liability of agreement signed by both parties
+    #[command(long_about = "Create liability of agreement signed by both parties.
+
+Transaction sender pays liability storage deposit.
+
+EXAMPLES:
+    # Create liability
+    cps liability create agreement.json --suri //Bob")]
+    Create {
+        /// Signed agreement draft file
+        draft: String,
+    },
+
+    /// Watch new liabilities of account and execute them
+    #[command(long_about = "Watch new liabilities of account and execute them.
+
+Monitors finalized blocks for NewLiability events where account is promisor.
+Each liability is executed by shell hook, liability parameters are passed by
+LIABILITY_INDEX, LIABILITY_TECHNICS, LIABILITY_PRICE, LIABILITY_PROMISEE and
+LIABILITY_PROMISOR environment variables. Report CID printed by hook is
+published as signed liability report, empty output means no report.
+
+EXAMPLES:
+    # Only display new liabilities
+    cps liability watch --suri //Bob
+
+    # Execute liabilities by script
+    cps liability watch --suri //Bob --exec ./execute_task.sh")]
+    Watch {
+        /// Shell command executing liability
+        #[arg(short = 'e', long)]
+        exec: Option<String>,
+    },
+
+    /// Publish signed liability report
+    #[command(long_about = "Publish signed liability report.
+
+EXAMPLES:
+    # Report liability 0 result stored in IPFS
+    cps liability report 0 QmbWqxBEKC3P8tqsKc98xmWNzrzDtRLMiMPL8wBuTGsMnR --suri //Bob")]
+    Report {
+        /// Liability index
+        index: u32,
+
+        /// Report SHA2-256 CID or hex-encoded 32-byte digest
+        report: String,
+    },
+}
+
 #[cfg(feature = "mqtt")]
 #[derive(Subcommand)]
 enum MqttCommands {
@@ -577,6 +711,7 @@ async fn main() -> Result<()> {
             receiver_public,
             cipher,
             scheme,
+            ttl,
         } => {
             // Parse receiver public key if provided (supports both SS58 address and hex)
             let receiver_pub_bytes = if let Some(ref addr_or_hex) = receiver_public {
@@ -603,6 +738,7 @@ async fn main() -> Result<()> {
                 data,
                 receiver_pub_bytes,
                 algorithm_opt,
+                ttl,
             )
             .await?;
         }
@@ -612,9 +748,55 @@ async fn main() -> Result<()> {
         } => {
             commands::move_node::execute(&blockchain_config, node_id, new_parent_id).await?;
         }
+        Commands::Roots { owner } => {
+            let owner = AccountId32::from(parse_receiver_public_key(&owner)?);
+            commands::roots::execute(&blockchain_config, owner).await?;
+        }
         Commands::Remove { node_id, force } => {
             commands::remove::execute(&blockchain_config, node_id, force).await?;
         }
+        Commands::Liability(liability_cmd) => match liability_cmd {
+            LiabilityCommands::Build {
+                technics,
+                price,
+                promisee,
+                promisor,
+                validator,
+                nonce,
+                output,
+            } => {
+                let promisee = AccountId32::from(parse_receiver_public_key(&promisee)?);
+                let promisor = AccountId32::from(parse_receiver_public_key(&promisor)?);
+                let validator = if let Some(ref addr_or_hex) = validator {
+                    Some(AccountId32::from(parse_receiver_public_key(addr_or_hex)?))
+                } else {
+                    None
+                };
+                commands::liability::build(
+                    &blockchain_config,
+                    &technics,
+                    price,
+                    promisee,
+                    promisor,
+                    validator,
+                    nonce,
+                    output.as_deref(),
+                )
+                .await?;
+            }
+            LiabilityCommands::Sign { draft } => {
+                commands::liability::sign(&blockchain_config, &draft)?;
+            }
+            LiabilityCommands::Create { draft } => {
+                commands::liability::create(&blockchain_config, &draft).await?;
+            }
+            LiabilityCommands::Watch { exec } => {
+                commands::liability::watch(&blockchain_config, exec).await?;
+            }
+            LiabilityCommands::Report { index, report } => {
+                commands::liability::report(&blockchain_config, index, &report).await?;
+            }
+        },
         #[cfg(feature = "mqtt")]
         Commands::Mqtt(mqtt_cmd) => match mqtt_cmd {
             MqttCommands::Subscribe {
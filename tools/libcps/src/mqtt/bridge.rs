@@ -146,7 +146,7 @@
 
 use crate::blockchain::{BoundedVec, Client, Config as BlockchainConfig};
 use crate::crypto::{Cipher, CryptoScheme, EncryptedMessage, EncryptionAlgorithm};
-use crate::node::{Node, PayloadSet};
+use crate::node::{Node, NodeId};
 use anyhow::{anyhow, Result};
 use log::{debug, error, trace};
 use parity_scale_codec::Decode;
@@ -768,24 +768,14 @@ impl Config {
                 }
             };
 
-            // Look for PayloadSet events for our node
-            let payload_set_events = events.find::<PayloadSet>();
-
-            let mut payload_updated = false;
-            for event in payload_set_events {
-                match event {
-                    Ok(payload_event) => {
-                        // Check if this event is for our node
-                        if payload_event.0 .0 == node_id {
-                            payload_updated = true;
-                            break;
-                        }
-                    }
-                    Err(_e) => {
-                        // Failed to decode event, skip
-                    }
-                }
-            }
+            // Look for PayloadSet events for our node, only leading node ID
+            // is decoded so the rest of event fields could be extended
+            let payload_updated = events.iter().filter_map(|event| event.ok()).any(|event| {
+                event.pallet_name() == "CPS"
+                    && event.variant_name() == "PayloadSet"
+                    && NodeId::decode(&mut event.field_bytes())
+                        .is_ok_and(|event_node| event_node.0 == node_id)
+            });
 
             // Only query and publish if the payload was actually updated
             if payload_updated {
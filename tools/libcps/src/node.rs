@@ -360,6 +360,182 @@ impl<'a> Node<'a> {
         tokio::runtime::Handle::current().block_on(self.query())
     }
 
+    /// Query this node with its descendants in a single runtime API call.
+    ///
+    /// Nodes are returned breadth-first up to `max_depth` levels below this node,
+    /// at most `limit` nodes. Children of each node are limited to returned nodes.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use libcps::blockchain::{Client, Config};
+    /// use libcps::node::Node;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// # let config = Config {
+    /// #     ws_url: "ws://localhost:9944".to_string(),
+    /// #     suri: Some("//Alice".to_string()),
+    /// # };
+    /// # let client = Client::new(&config).await?;
+    /// let node = Node::new(&client, 0);
+    /// for info in node.subtree(32, 1000).await? {
+    ///     println!("Node {} has {} children", info.id, info.children.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn subtree(&self, max_depth: u32, limit: u32) -> Result<Vec<NodeInfo>> {
+        trace!(
+            "Querying subtree of node {} at latest finalized block",
+            self.id
+        );
+        let block_hash = self
+            .client
+            .api
+            .backend()
+            .latest_finalized_block_ref()
+            .await?
+            .hash();
+
+        let nodes = self
+            .client
+            .api
+            .runtime_api()
+            .at(block_hash)
+            .call(
+                api::apis()
+                    .cps_api()
+                    .subtree(NodeId(self.id), max_depth, limit),
+            )
+            .await
+            .map_err(|e| anyhow!("Failed to query subtree: {}", e))?;
+
+        if nodes.is_empty() {
+            return Err(anyhow!("Node {} not found", self.id));
+        }
+
+        let children_of = |id: u64| {
+            nodes
+                .iter()
+                .filter(|(_, node)| node.parent.as_ref().map(|p| p.0) == Some(id))
+                .map(|(child, _)| child.0)
+                .collect::<Vec<_>>()
+        };
+        let infos = nodes
+            .iter()
+            .map(|(id, node)| NodeInfo {
+                id: id.0,
+                owner: node.owner.0.into(),
+                parent: node.parent.as_ref().map(|p| p.0),
+                meta: node.meta.clone(),
+                payload: node.payload.clone(),
+                children: children_of(id.0),
+            })
+            .collect();
+        Ok(infos)
+    }
+
+    /// Query this node with its descendants (blocking).
+    ///
+    /// This is the blocking variant of `subtree()`.
+    pub fn subtree_blocking(&self, max_depth: u32, limit: u32) -> Result<Vec<NodeInfo>> {
+        tokio::runtime::Handle::current().block_on(self.subtree(max_depth, limit))
+    }
+
+    /// List nodes owned by an account.
+    ///
+    /// Returns up to `limit` nodes after `cursor` node, the last returned node
+    /// is a cursor for the next page.
+    pub async fn owned_by(
+        client: &'a Client,
+        owner: AccountId32,
+        cursor: Option<u64>,
+        limit: u32,
+    ) -> Result<Vec<Self>> {
+        trace!("Querying nodes of {} at latest finalized block", owner);
+        let block_hash = client
+            .api
+            .backend()
+            .latest_finalized_block_ref()
+            .await?
+            .hash();
+
+        let nodes = client
+            .api
+            .runtime_api()
+            .at(block_hash)
+            .call(
+                api::apis()
+                    .cps_api()
+                    .nodes_by_owner(owner, cursor.map(NodeId), limit),
+            )
+            .await
+            .map_err(|e| anyhow!("Failed to query owned nodes: {}", e))?;
+
+        Ok(nodes
+            .into_iter()
+            .map(|id| Self::new(client, id.0))
+            .collect())
+    }
+
+    /// List root nodes owned by an account.
+    ///
+    /// Roots are enumerated with the `CpsApi` runtime API at the latest finalized block.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Reference to the blockchain client
+    /// * `owner` - Account owning the root nodes
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use libcps::blockchain::{Client, Config};
+    /// use libcps::node::Node;
+    /// use subxt::utils::AccountId32;
+    ///
+    /// # async fn example(owner: AccountId32) -> anyhow::Result<()> {
+    /// # let config = Config {
+    /// #     ws_url: "ws://localhost:9944".to_string(),
+    /// #     suri: Some("//Alice".to_string()),
+    /// # };
+    /// # let client = Client::new(&config).await?;
+    /// for root in Node::roots(&client, owner).await? {
+    ///     println!("Root node: {}", root.id());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn roots(client: &'a Client, owner: AccountId32) -> Result<Vec<Self>> {
+        trace!("Querying root nodes of {} at latest finalized block", owner);
+        let block_hash = client
+            .api
+            .backend()
+            .latest_finalized_block_ref()
+            .await?
+            .hash();
+
+        let roots = client
+            .api
+            .runtime_api()
+            .at(block_hash)
+            .call(api::apis().cps_api().roots(owner))
+            .await
+            .map_err(|e| anyhow!("Failed to query root nodes: {}", e))?;
+
+        Ok(roots
+            .into_iter()
+            .map(|id| Self::new(client, id.0))
+            .collect())
+    }
+
+    /// List root nodes owned by an account (blocking).
+    ///
+    /// This is the blocking variant of `roots()`.
+    pub fn roots_blocking(client: &'a Client, owner: AccountId32) -> Result<Vec<Self>> {
+        tokio::runtime::Handle::current().block_on(Self::roots(client, owner))
+    }
+
     /// Update the metadata of this node.
     ///
     /// Returns subxt's ExtrinsicEvents containing transaction hash and events.
@@ -456,17 +632,38 @@ impl<'a> Node<'a> {
     /// # }
     /// ```
     pub async fn set_payload(&self, payload: Option<BoundedVec<u8>>) -> Result<ExtrinsicEvents> {
+        self.set_payload_with_ttl(payload, None).await
+    }
+
+    /// Update the payload of this node, expiring it `ttl` blocks later.
+    ///
+    /// Expired payload is cleared on chain and reported with `PayloadExpired` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `payload` - Optional payload for the node
+    /// * `ttl` - Optional number of blocks the payload stays valid
+    ///
+    /// # Returns
+    ///
+    /// ExtrinsicEvents with transaction hash and events.
+    pub async fn set_payload_with_ttl(
+        &self,
+        payload: Option<BoundedVec<u8>>,
+        ttl: Option<u32>,
+    ) -> Result<ExtrinsicEvents> {
         debug!(
-            "Setting payload for node {}: has_data={}",
+            "Setting payload for node {}: has_data={}, ttl={:?}",
             self.id,
-            payload.is_some()
+            payload.is_some(),
+            ttl
         );
         let keypair = self.client.require_keypair()?;
         let node_id = NodeId(self.id);
 
         // Build the set_payload transaction
         trace!("Building set_payload transaction");
-        let set_payload_call = api::tx().cps().set_payload(node_id, payload);
+        let set_payload_call = api::tx().cps().set_payload(node_id, payload, ttl);
 
         // Submit and watch the transaction
         trace!("Submitting set_payload transaction for node {}", self.id);